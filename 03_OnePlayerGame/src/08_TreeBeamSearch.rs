@@ -0,0 +1,384 @@
+use std::collections::BinaryHeap;
+
+use rand;
+use rand::prelude::*;
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct Coord {
+    y_: i32,
+    x_: i32,
+}
+impl Coord {
+    pub fn new(y_: i32, x_: i32) -> Self {
+        Self { y_, x_ }
+    }
+}
+
+type ScoreType = i64;
+const INF: ScoreType = 1000000000;
+const H: i32 = 3;
+const W: i32 = 4;
+const END_TURN: i32 = 4;
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct MazeState {
+    points_: [[i32; W as usize]; H as usize],
+    turn_: i32,
+    pub charcter_: Coord,
+    pub game_score_: i32,
+    pub evaluated_score_: ScoreType,
+    pub first_action_: usize,
+}
+impl MazeState {
+    const dx: [i32; 4] = [1, -1, 0, 0];
+    const dy: [i32; 4] = [0, 0, 1, -1];
+    pub fn new(seed: usize) -> Self {
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(seed as u64);
+        let mut maze_state = MazeState {
+            points_: [[0 as i32; W as usize]; H as usize],
+            turn_: 0,
+            charcter_: Coord { y_: 0, x_: 0 },
+            game_score_: 0,
+            evaluated_score_: 0,
+            first_action_: 10,
+        };
+        maze_state.charcter_.y_ = rng.gen_range(0..=10) % H;
+        maze_state.charcter_.x_ = rng.gen_range(0..=10) % W;
+        for y in 0..H {
+            for x in 0..W {
+                if y == maze_state.charcter_.y_ && x == maze_state.charcter_.x_ {
+                    continue;
+                }
+                maze_state.points_[y as usize][x as usize] = rng.gen_range(1..=9);
+            }
+        }
+        maze_state
+    }
+
+    pub fn isDone(&self) -> bool {
+        return self.turn_ == END_TURN;
+    }
+
+    pub fn evaluateScore(&mut self) {
+        self.evaluated_score_ = self.game_score_ as ScoreType;
+    }
+
+    pub fn advance(&mut self, action: usize) {
+        self.charcter_.x_ += Self::dx[action];
+        self.charcter_.y_ += Self::dy[action];
+        let point = &mut self.points_[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+        if *point > 0 {
+            self.game_score_ += *point;
+            *point = 0;
+        }
+        self.turn_ += 1;
+    }
+    // advanceと同じ遷移をし、undoで元に戻すために拾った得点を返す
+    pub fn advanceWithUndo(&mut self, action: usize) -> i32 {
+        self.charcter_.x_ += Self::dx[action];
+        self.charcter_.y_ += Self::dy[action];
+        let point = &mut self.points_[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+        let taken_point = *point;
+        if *point > 0 {
+            self.game_score_ += *point;
+            *point = 0;
+        }
+        self.turn_ += 1;
+        taken_point
+    }
+    pub fn undo(&mut self, action: usize, taken_point: i32) {
+        self.turn_ -= 1;
+        if taken_point > 0 {
+            self.points_[self.charcter_.y_ as usize][self.charcter_.x_ as usize] = taken_point;
+            self.game_score_ -= taken_point;
+        }
+        self.charcter_.x_ -= Self::dx[action];
+        self.charcter_.y_ -= Self::dy[action];
+    }
+    pub fn legalActions(&self) -> Vec<usize> {
+        let mut actions = Vec::new();
+        for action in 0..4 {
+            let ty = self.charcter_.y_ + Self::dy[action];
+            let tx = self.charcter_.x_ + Self::dx[action];
+            if ty >= 0 && ty < H && tx >= 0 && tx < W {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+    pub fn toString(&self) -> String {
+        let mut s = String::new();
+        s += "turn:\t";
+        s += &self.turn_.to_string();
+        s += "\n";
+        s += "score:\t";
+        s += &self.game_score_.to_string();
+        s += "\n";
+        for h in 0..H {
+            for w in 0..W {
+                if self.charcter_.y_ == h && self.charcter_.x_ == w {
+                    s.push('@');
+                } else if self.points_[h as usize][w as usize] > 0 {
+                    s += &self.points_[h as usize][w as usize].to_string();
+                } else {
+                    s.push('.');
+                }
+            }
+            s.push('\n');
+        }
+        s
+    }
+}
+
+impl Ord for MazeState {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.evaluated_score_.cmp(&other.evaluated_score_)
+    }
+}
+impl PartialOrd for MazeState {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+type State = MazeState;
+
+fn randomAction(state: &State) -> usize {
+    let mut rng = rand::thread_rng();
+
+    let legal_actions = state.legalActions();
+    return legal_actions[(rng.gen_range(0..=10) as usize % legal_actions.len())];
+}
+
+fn greedyAction(state: &State) -> usize {
+    let legal_actions = state.legalActions();
+    let mut best_score: ScoreType = -1;
+    let mut best_action = 10;
+    for action in legal_actions {
+        let mut now_state = state.clone();
+        now_state.advance(action);
+        now_state.evaluateScore();
+        if now_state.evaluated_score_ > best_score {
+            best_score = now_state.evaluated_score_;
+            best_action = action;
+        }
+    }
+    best_action
+}
+
+fn beamSearchAction(state: &State, beam_width: usize, beam_depth: usize) -> usize {
+    let mut now_beam: BinaryHeap<State> = BinaryHeap::new();
+    let mut best_state: State = State::new(11);
+    now_beam.push(state.clone());
+    for t in 0..beam_depth {
+        let mut next_beam: BinaryHeap<State> = BinaryHeap::new();
+        for i in 0..beam_width {
+            if now_beam.is_empty() {
+                break;
+            }
+            let mut now_state = now_beam.pop().unwrap();
+            let legal_actions = now_state.legalActions();
+            for action in legal_actions {
+                let mut next_state = now_state.clone();
+                next_state.advance(action);
+                next_state.evaluateScore();
+                if t == 0 {
+                    next_state.first_action_ = action;
+                }
+                next_beam.push(next_state);
+            }
+        }
+        now_beam = next_beam.clone();
+        best_state = next_beam.pop().unwrap();
+        if best_state.isDone() {
+            break;
+        }
+    }
+    best_state.first_action_
+}
+
+struct TreeNode {
+    parent_: usize,
+    action_: usize,
+    children_: Vec<usize>,
+}
+
+struct Candidate {
+    score_: ScoreType,
+    parent_: usize,
+    action_: usize,
+    is_done_: bool,
+}
+
+// 状態は1つだけ持ち、行動だけを保存した探索木をオイラーツアーの順に辿りながら
+// advanceとundoで差分更新するビームサーチ
+// 各深さで新しく作るのは候補の評価値だけで、状態のコピーは作らない
+struct TreeBeamSearch {
+    state_: State,
+    nodes_: Vec<TreeNode>,
+    free_ids_: Vec<usize>,
+}
+impl TreeBeamSearch {
+    const ROOT: usize = 0;
+
+    pub fn new(state: &State) -> Self {
+        Self {
+            state_: state.clone(),
+            nodes_: vec![TreeNode {
+                parent_: Self::ROOT,
+                action_: 0,
+                children_: Vec::new(),
+            }],
+            free_ids_: Vec::new(),
+        }
+    }
+
+    fn addNode(&mut self, parent_id: usize, action: usize) -> usize {
+        let node = TreeNode {
+            parent_: parent_id,
+            action_: action,
+            children_: Vec::new(),
+        };
+        let node_id = match self.free_ids_.pop() {
+            Some(node_id) => {
+                self.nodes_[node_id] = node;
+                node_id
+            }
+            None => {
+                self.nodes_.push(node);
+                self.nodes_.len() - 1
+            }
+        };
+        self.nodes_[parent_id].children_.push(node_id);
+        node_id
+    }
+
+    // 子を持たなくなった葉を削除し、その結果子がなくなった祖先も削除する
+    fn removeLeaf(&mut self, node_id: usize) {
+        let mut now_id = node_id;
+        while now_id != Self::ROOT && self.nodes_[now_id].children_.is_empty() {
+            let parent_id = self.nodes_[now_id].parent_;
+            let children = &mut self.nodes_[parent_id].children_;
+            let index = children.iter().position(|&id| id == now_id).unwrap();
+            children.swap_remove(index);
+            self.free_ids_.push(now_id);
+            now_id = parent_id;
+        }
+    }
+
+    // 深さtarget_depthにある葉の全ての子を評価してcandidatesに入れる
+    fn collectCandidates(
+        &mut self,
+        node_id: usize,
+        depth: usize,
+        target_depth: usize,
+        candidates: &mut Vec<Candidate>,
+    ) {
+        if depth == target_depth {
+            if self.state_.isDone() {
+                return;
+            }
+            for action in self.state_.legalActions() {
+                let point = self.state_.advanceWithUndo(action);
+                self.state_.evaluateScore();
+                candidates.push(Candidate {
+                    score_: self.state_.evaluated_score_,
+                    parent_: node_id,
+                    action_: action,
+                    is_done_: self.state_.isDone(),
+                });
+                self.state_.undo(action, point);
+            }
+            return;
+        }
+        for i in 0..self.nodes_[node_id].children_.len() {
+            let child_id = self.nodes_[node_id].children_[i];
+            let action = self.nodes_[child_id].action_;
+            let point = self.state_.advanceWithUndo(action);
+            self.collectCandidates(child_id, depth + 1, target_depth, candidates);
+            self.state_.undo(action, point);
+        }
+    }
+
+    fn firstAction(&self, node_id: usize) -> usize {
+        let mut now_id = node_id;
+        while self.nodes_[now_id].parent_ != Self::ROOT {
+            now_id = self.nodes_[now_id].parent_;
+        }
+        self.nodes_[now_id].action_
+    }
+}
+
+fn treeBeamSearchAction(state: &State, beam_width: usize, beam_depth: usize) -> usize {
+    let mut search = TreeBeamSearch::new(state);
+    let mut leaves = vec![TreeBeamSearch::ROOT];
+    let mut best_leaf = TreeBeamSearch::ROOT;
+    for t in 0..beam_depth {
+        let mut candidates = Vec::new();
+        search.collectCandidates(TreeBeamSearch::ROOT, 0, t, &mut candidates);
+        if candidates.is_empty() {
+            break;
+        }
+        candidates.sort_by(|a, b| b.score_.cmp(&a.score_));
+        candidates.truncate(beam_width);
+
+        let mut next_leaves = Vec::new();
+        for candidate in candidates.iter() {
+            next_leaves.push(search.addNode(candidate.parent_, candidate.action_));
+        }
+        for leaf in leaves {
+            search.removeLeaf(leaf);
+        }
+        leaves = next_leaves;
+        best_leaf = leaves[0];
+        if candidates[0].is_done_ {
+            break;
+        }
+    }
+    search.firstAction(best_leaf)
+}
+
+fn playGame(seed: usize) {
+    let mut state = State::new(seed);
+    println!("{}", state.toString());
+    while !state.isDone() {
+        // state.advance(randomAction(&state));
+        state.advance(greedyAction(&state));
+        println!("{}", state.toString());
+    }
+}
+
+type AIFunction = fn(&State) -> usize;
+
+fn testAiScore(name: &str, ai: AIFunction, game_number: usize) {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0);
+    let mut score_mean = 0.0;
+    let start_time = std::time::Instant::now();
+    for _ in 0..game_number {
+        let mut state = State::new(rng.gen_range(0..1000) as usize);
+        while !state.isDone() {
+            state.advance(ai(&state));
+        }
+        score_mean += state.game_score_ as f64;
+    }
+    score_mean /= game_number as f64;
+    println!(
+        "Score of {}:\t {}\t{}ms",
+        name,
+        score_mean,
+        start_time.elapsed().as_millis()
+    );
+}
+
+fn main() {
+    testAiScore(
+        "beamSearchAction",
+        |state| beamSearchAction(state, 2, END_TURN as usize),
+        100,
+    );
+    testAiScore(
+        "treeBeamSearchAction",
+        |state| treeBeamSearchAction(state, 2, END_TURN as usize),
+        100,
+    );
+}
@@ -0,0 +1,394 @@
+use std::collections::{BinaryHeap, HashMap};
+
+use rand;
+use rand::prelude::*;
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct Coord {
+    y_: i32,
+    x_: i32,
+}
+impl Coord {
+    pub fn new(y_: i32, x_: i32) -> Self {
+        Self { y_, x_ }
+    }
+}
+
+type ScoreType = i64;
+const INF: ScoreType = 1000000000;
+const H: i32 = 3;
+const W: i32 = 4;
+const END_TURN: i32 = 4;
+const INVALID_ACTION: usize = 100000;
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct MazeState {
+    points_: [[i32; W as usize]; H as usize],
+    turn_: i32,
+    pub charcter_: Coord,
+    pub game_score_: i32,
+    pub evaluated_score_: ScoreType,
+    pub first_action_: usize,
+}
+impl MazeState {
+    const dx: [i32; 4] = [1, -1, 0, 0];
+    const dy: [i32; 4] = [0, 0, 1, -1];
+    pub fn new(seed: usize) -> Self {
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(seed as u64);
+        let mut maze_state = MazeState {
+            points_: [[0 as i32; W as usize]; H as usize],
+            turn_: 0,
+            charcter_: Coord { y_: 0, x_: 0 },
+            game_score_: 0,
+            evaluated_score_: 0,
+            first_action_: 10,
+        };
+        maze_state.charcter_.y_ = rng.gen_range(0..=10) % H;
+        maze_state.charcter_.x_ = rng.gen_range(0..=10) % W;
+        for y in 0..H {
+            for x in 0..W {
+                if y == maze_state.charcter_.y_ && x == maze_state.charcter_.x_ {
+                    continue;
+                }
+                maze_state.points_[y as usize][x as usize] = rng.gen_range(1..=9);
+            }
+        }
+        maze_state
+    }
+
+    pub fn isDone(&self) -> bool {
+        return self.turn_ == END_TURN;
+    }
+
+    pub fn evaluateScore(&mut self) {
+        self.evaluated_score_ = self.game_score_ as ScoreType;
+    }
+
+    pub fn advance(&mut self, action: usize) {
+        if action == INVALID_ACTION {
+            self.turn_ += 1;
+            return;
+        }
+        self.charcter_.x_ += Self::dx[action];
+        self.charcter_.y_ += Self::dy[action];
+        let point = &mut self.points_[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+        if *point > 0 {
+            self.game_score_ += *point;
+            *point = 0;
+        }
+        self.turn_ += 1;
+    }
+    pub fn legalActions(&self) -> Vec<usize> {
+        let mut actions = Vec::new();
+        for action in 0..4 {
+            let ty = self.charcter_.y_ + Self::dy[action];
+            let tx = self.charcter_.x_ + Self::dx[action];
+            if ty >= 0 && ty < H && tx >= 0 && tx < W {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+    pub fn toString(&self) -> String {
+        let mut s = String::new();
+        s += "turn:\t";
+        s += &self.turn_.to_string();
+        s += "\n";
+        s += "score:\t";
+        s += &self.game_score_.to_string();
+        s += "\n";
+        for h in 0..H {
+            for w in 0..W {
+                if self.charcter_.y_ == h && self.charcter_.x_ == w {
+                    s.push('@');
+                } else if self.points_[h as usize][w as usize] > 0 {
+                    s += &self.points_[h as usize][w as usize].to_string();
+                } else {
+                    s.push('.');
+                }
+            }
+            s.push('\n');
+        }
+        s
+    }
+}
+
+impl Ord for MazeState {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.evaluated_score_.cmp(&other.evaluated_score_)
+    }
+}
+impl PartialOrd for MazeState {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+type State = MazeState;
+
+fn randomAction(state: &State) -> usize {
+    let mut rng = rand::thread_rng();
+
+    let legal_actions = state.legalActions();
+    return legal_actions[(rng.gen_range(0..=10) as usize % legal_actions.len())];
+}
+
+fn greedyAction(state: &State) -> usize {
+    let legal_actions = state.legalActions();
+    let mut best_score: ScoreType = -1;
+    let mut best_action = 10;
+    for action in legal_actions {
+        let mut now_state = state.clone();
+        now_state.advance(action);
+        now_state.evaluateScore();
+        if now_state.evaluated_score_ > best_score {
+            best_score = now_state.evaluated_score_;
+            best_action = action;
+        }
+    }
+    best_action
+}
+
+fn beamSearchAction(state: &State, beam_width: usize, beam_depth: usize) -> usize {
+    let mut now_beam: BinaryHeap<State> = BinaryHeap::new();
+    let mut best_state: State = State::new(11);
+    now_beam.push(state.clone());
+    for t in 0..beam_depth {
+        let mut next_beam: BinaryHeap<State> = BinaryHeap::new();
+        for i in 0..beam_width {
+            if now_beam.is_empty() {
+                break;
+            }
+            let mut now_state = now_beam.pop().unwrap();
+            let legal_actions = now_state.legalActions();
+            for action in legal_actions {
+                let mut next_state = now_state.clone();
+                next_state.advance(action);
+                next_state.evaluateScore();
+                if t == 0 {
+                    next_state.first_action_ = action;
+                }
+                next_beam.push(next_state);
+            }
+        }
+        now_beam = next_beam.clone();
+        best_state = next_beam.pop().unwrap();
+        if best_state.isDone() {
+            break;
+        }
+    }
+    best_state.first_action_
+}
+
+fn chokudaiSearchAction(
+    state: &State,
+    beam_width: usize,
+    beam_depth: usize,
+    beam_number: usize,
+) -> usize {
+    let mut beam = Vec::new();
+    for t in 0..beam_depth + 1 {
+        beam.push(BinaryHeap::<State>::new());
+    }
+    beam[0].push(state.clone());
+    for cnt in 0..beam_number {
+        for t in 0..beam_depth {
+            for i in 0..beam_width {
+                if beam[t].is_empty() {
+                    break;
+                }
+                let mut now_state = beam[t].peek().unwrap().clone();
+                if now_state.isDone() {
+                    break;
+                }
+                beam[t].pop();
+                let legal_actions = now_state.legalActions();
+                for action in legal_actions {
+                    let mut next_state = now_state.clone();
+                    next_state.advance(action);
+                    next_state.evaluateScore();
+                    if t == 0 {
+                        next_state.first_action_ = action;
+                    }
+                    beam[t + 1].push(next_state);
+                }
+            }
+        }
+    }
+    for t in 0..=beam_depth {
+        let now_beam = &beam[beam_depth - t];
+        if !now_beam.is_empty() {
+            return now_beam.peek().unwrap().first_action_;
+        }
+    }
+    INVALID_ACTION
+}
+
+// 盤面全体を探索して最適な得点と行動列を求める
+// 同じ(位置, ターン, 残っている得点)の局面は一度だけ読むようにメモ化し、
+// 残りターンで取れる得点の上界が必要な値に届かない局面は枝刈りする
+struct ExactSolver {
+    memo_: HashMap<(i32, i32, i32, [[i32; W as usize]; H as usize]), i32>,
+    node_number_: usize,
+}
+impl ExactSolver {
+    pub fn new() -> Self {
+        Self {
+            memo_: HashMap::new(),
+            node_number_: 0,
+        }
+    }
+
+    fn key(state: &State) -> (i32, i32, i32, [[i32; W as usize]; H as usize]) {
+        (
+            state.charcter_.y_,
+            state.charcter_.x_,
+            state.turn_,
+            state.points_,
+        )
+    }
+
+    // 残りターンで取れる得点の上界
+    fn upperBound(state: &State) -> i32 {
+        let remaining_turn = (END_TURN - state.turn_) as usize;
+        let mut points = Vec::new();
+        for y in 0..H {
+            for x in 0..W {
+                let point = state.points_[y as usize][x as usize];
+                let distance = (y - state.charcter_.y_).abs() + (x - state.charcter_.x_).abs();
+                if point > 0 && distance as usize <= remaining_turn {
+                    points.push(point);
+                }
+            }
+        }
+        points.sort_unstable_by(|a, b| b.cmp(a));
+        points.iter().take(remaining_turn).sum()
+    }
+
+    // stateから先に取れる得点の最大値を返す
+    // 戻り値がneedより大きいときは厳密値で、need以下のときは真の値の上界になっている
+    fn search(&mut self, state: &State, need: i32) -> i32 {
+        if state.isDone() {
+            return 0;
+        }
+        let key = Self::key(state);
+        if let Some(&value) = self.memo_.get(&key) {
+            return value;
+        }
+        let upper_bound = Self::upperBound(state);
+        if upper_bound <= need {
+            return upper_bound;
+        }
+        self.node_number_ += 1;
+        let mut best = -1;
+        for action in state.legalActions() {
+            let mut next_state = state.clone();
+            next_state.advance(action);
+            let point = next_state.game_score_ - state.game_score_;
+            let value = point + self.search(&next_state, need.max(best) - point);
+            if value > best {
+                best = value;
+            }
+        }
+        if best > need {
+            self.memo_.insert(key, best);
+        }
+        best
+    }
+
+    pub fn optimalScore(&mut self, state: &State) -> i32 {
+        state.game_score_ + self.search(state, -1)
+    }
+
+    pub fn optimalActions(&mut self, state: &State) -> Vec<usize> {
+        let mut actions = Vec::new();
+        let mut now_state = state.clone();
+        let mut remaining = self.search(&now_state, -1);
+        while !now_state.isDone() {
+            for action in now_state.legalActions() {
+                let mut next_state = now_state.clone();
+                next_state.advance(action);
+                let point = next_state.game_score_ - now_state.game_score_;
+                if point + self.search(&next_state, remaining - point - 1) == remaining {
+                    actions.push(action);
+                    remaining -= point;
+                    now_state = next_state;
+                    break;
+                }
+            }
+        }
+        actions
+    }
+}
+
+fn exactSolverAction(state: &State) -> usize {
+    ExactSolver::new().optimalActions(state)[0]
+}
+
+fn playGame(seed: usize) {
+    let mut state = State::new(seed);
+    println!("{}", state.toString());
+    while !state.isDone() {
+        // state.advance(randomAction(&state));
+        state.advance(greedyAction(&state));
+        println!("{}", state.toString());
+    }
+}
+
+type AIFunction = fn(&State) -> usize;
+type StringAIPair = (String, AIFunction);
+
+// 各AIの平均得点を、同じ盤面での最適解の平均得点と並べて表示する
+fn testAiScore(ais: &[StringAIPair], game_number: usize) {
+    let mut optimal_scores = Vec::new();
+    let mut node_number = 0;
+    for i in 0..game_number {
+        let state = State::new(i);
+        let mut solver = ExactSolver::new();
+        optimal_scores.push(solver.optimalScore(&state) as f64);
+        node_number += solver.node_number_;
+    }
+    let optimal_mean = optimal_scores.iter().sum::<f64>() / game_number as f64;
+    println!(
+        "Optimal score:\t{}\t(searched nodes per game: {})",
+        optimal_mean,
+        node_number / game_number
+    );
+    for ai in ais.iter() {
+        let mut score_mean = 0.0;
+        let mut optimal_number = 0;
+        for i in 0..game_number {
+            let mut state = State::new(i);
+            while !state.isDone() {
+                state.advance(ai.1(&state));
+            }
+            score_mean += state.game_score_ as f64;
+            if state.game_score_ as f64 == optimal_scores[i] {
+                optimal_number += 1;
+            }
+        }
+        score_mean /= game_number as f64;
+        println!(
+            "Score of {}:\t{}\tgap {:.3}\toptimal {}/{}",
+            ai.0,
+            score_mean,
+            optimal_mean - score_mean,
+            optimal_number,
+            game_number
+        );
+    }
+}
+
+fn main() {
+    let f0: AIFunction = |state: &State| greedyAction(state);
+    let f1: AIFunction = |state: &State| beamSearchAction(state, 2, END_TURN as usize);
+    let f2: AIFunction = |state: &State| chokudaiSearchAction(state, 1, END_TURN as usize, 2);
+    let f3: AIFunction = |state: &State| exactSolverAction(state);
+    let ais: [StringAIPair; 4] = [
+        ("greedyAction".to_string(), f0),
+        ("beamSearchAction".to_string(), f1),
+        ("chokudaiSearchAction".to_string(), f2),
+        ("exactSolverAction".to_string(), f3),
+    ];
+    testAiScore(&ais, 100);
+}
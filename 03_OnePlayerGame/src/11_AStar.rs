@@ -0,0 +1,389 @@
+use std::collections::{BinaryHeap, HashSet};
+use std::time;
+
+use rand;
+use rand::prelude::*;
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct Coord {
+    y_: i32,
+    x_: i32,
+}
+impl Coord {
+    pub fn new(y_: i32, x_: i32) -> Self {
+        Self { y_, x_ }
+    }
+}
+
+type ScoreType = i64;
+const INF: ScoreType = 1000000000;
+const H: i32 = 3;
+const W: i32 = 4;
+const END_TURN: i32 = 4;
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct MazeState {
+    points_: [[i32; W as usize]; H as usize],
+    turn_: i32,
+    pub charcter_: Coord,
+    pub game_score_: i32,
+    pub evaluated_score_: ScoreType,
+    pub first_action_: usize,
+}
+impl MazeState {
+    const dx: [i32; 4] = [1, -1, 0, 0];
+    const dy: [i32; 4] = [0, 0, 1, -1];
+    pub fn new(seed: usize) -> Self {
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(seed as u64);
+        let mut maze_state = MazeState {
+            points_: [[0 as i32; W as usize]; H as usize],
+            turn_: 0,
+            charcter_: Coord { y_: 0, x_: 0 },
+            game_score_: 0,
+            evaluated_score_: 0,
+            first_action_: 10,
+        };
+        maze_state.charcter_.y_ = rng.gen_range(0..=10) % H;
+        maze_state.charcter_.x_ = rng.gen_range(0..=10) % W;
+        for y in 0..H {
+            for x in 0..W {
+                if y == maze_state.charcter_.y_ && x == maze_state.charcter_.x_ {
+                    continue;
+                }
+                maze_state.points_[y as usize][x as usize] = rng.gen_range(1..=9);
+            }
+        }
+        maze_state
+    }
+
+    pub fn isDone(&self) -> bool {
+        return self.turn_ == END_TURN;
+    }
+
+    pub fn evaluateScore(&mut self) {
+        self.evaluated_score_ = self.game_score_ as ScoreType;
+    }
+
+    pub fn advance(&mut self, action: usize) {
+        self.charcter_.x_ += Self::dx[action];
+        self.charcter_.y_ += Self::dy[action];
+        let point = &mut self.points_[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+        if *point > 0 {
+            self.game_score_ += *point;
+            *point = 0;
+        }
+        self.turn_ += 1;
+    }
+    pub fn legalActions(&self) -> Vec<usize> {
+        let mut actions = Vec::new();
+        for action in 0..4 {
+            let ty = self.charcter_.y_ + Self::dy[action];
+            let tx = self.charcter_.x_ + Self::dx[action];
+            if ty >= 0 && ty < H && tx >= 0 && tx < W {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+    pub fn toString(&self) -> String {
+        let mut s = String::new();
+        s += "turn:\t";
+        s += &self.turn_.to_string();
+        s += "\n";
+        s += "score:\t";
+        s += &self.game_score_.to_string();
+        s += "\n";
+        for h in 0..H {
+            for w in 0..W {
+                if self.charcter_.y_ == h && self.charcter_.x_ == w {
+                    s.push('@');
+                } else if self.points_[h as usize][w as usize] > 0 {
+                    s += &self.points_[h as usize][w as usize].to_string();
+                } else {
+                    s.push('.');
+                }
+            }
+            s.push('\n');
+        }
+        s
+    }
+}
+
+impl Ord for MazeState {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.evaluated_score_.cmp(&other.evaluated_score_)
+    }
+}
+impl PartialOrd for MazeState {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+type State = MazeState;
+
+fn randomAction(state: &State) -> usize {
+    let mut rng = rand::thread_rng();
+
+    let legal_actions = state.legalActions();
+    return legal_actions[(rng.gen_range(0..=10) as usize % legal_actions.len())];
+}
+
+fn greedyAction(state: &State) -> usize {
+    let legal_actions = state.legalActions();
+    let mut best_score: ScoreType = -1;
+    let mut best_action = 10;
+    for action in legal_actions {
+        let mut now_state = state.clone();
+        now_state.advance(action);
+        now_state.evaluateScore();
+        if now_state.evaluated_score_ > best_score {
+            best_score = now_state.evaluated_score_;
+            best_action = action;
+        }
+    }
+    best_action
+}
+
+fn beamSearchAction(state: &State, beam_width: usize, beam_depth: usize) -> usize {
+    let mut now_beam: BinaryHeap<State> = BinaryHeap::new();
+    let mut best_state: State = State::new(11);
+    now_beam.push(state.clone());
+    for t in 0..beam_depth {
+        let mut next_beam: BinaryHeap<State> = BinaryHeap::new();
+        for i in 0..beam_width {
+            if now_beam.is_empty() {
+                break;
+            }
+            let mut now_state = now_beam.pop().unwrap();
+            let legal_actions = now_state.legalActions();
+            for action in legal_actions {
+                let mut next_state = now_state.clone();
+                next_state.advance(action);
+                next_state.evaluateScore();
+                if t == 0 {
+                    next_state.first_action_ = action;
+                }
+                next_beam.push(next_state);
+            }
+        }
+        now_beam = next_beam.clone();
+        best_state = next_beam.pop().unwrap();
+        if best_state.isDone() {
+            break;
+        }
+    }
+    best_state.first_action_
+}
+
+type StateKey = (i32, i32, i32, [[i32; W as usize]; H as usize]);
+
+// 同じ位置、ターン、残りの得点の局面は、そこまでに取った得点も同じになる
+fn stateKey(state: &State) -> StateKey {
+    (
+        state.charcter_.y_,
+        state.charcter_.x_,
+        state.turn_,
+        state.points_,
+    )
+}
+
+// 残りターンで取れる得点の楽観的な見積もり
+// 残りターン以内に届くマスの得点を大きい順に残りターン数だけ足したもので、真の値を下回らない
+fn optimisticRemainingScore(state: &State) -> i32 {
+    let remaining_turn = (END_TURN - state.turn_) as usize;
+    let mut points = Vec::new();
+    for y in 0..H {
+        for x in 0..W {
+            let point = state.points_[y as usize][x as usize];
+            let distance = (y - state.charcter_.y_).abs() + (x - state.charcter_.x_).abs();
+            if point > 0 && distance as usize <= remaining_turn {
+                points.push(point);
+            }
+        }
+    }
+    points.sort_unstable_by(|a, b| b.cmp(a));
+    points.iter().take(remaining_turn).sum()
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct AStarNode {
+    f_: i32,
+    state_: State,
+    actions_: Vec<usize>,
+}
+impl Ord for AStarNode {
+    // f値が同じなら、既に多く得点している(より深い)ノードを先に取り出す
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.f_, self.state_.game_score_).cmp(&(other.f_, other.state_.game_score_))
+    }
+}
+impl PartialOrd for AStarNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// f = 得点 + 楽観的な見積もり が最大のノードから展開する
+// 見積もりが真の値以上なので、最初に取り出した終局ノードが最適解になる
+// 戻り値は(最適な行動列, 展開したノード数)
+fn aStarActions(state: &State) -> (Vec<usize>, usize) {
+    let mut open: BinaryHeap<AStarNode> = BinaryHeap::new();
+    let mut closed = HashSet::new();
+    let mut node_number = 0;
+    open.push(AStarNode {
+        f_: state.game_score_ + optimisticRemainingScore(state),
+        state_: state.clone(),
+        actions_: Vec::new(),
+    });
+    while let Some(node) = open.pop() {
+        if node.state_.isDone() {
+            return (node.actions_, node_number);
+        }
+        if !closed.insert(stateKey(&node.state_)) {
+            continue;
+        }
+        node_number += 1;
+        for action in node.state_.legalActions() {
+            let mut next_state = node.state_.clone();
+            next_state.advance(action);
+            if closed.contains(&stateKey(&next_state)) {
+                continue;
+            }
+            let mut actions = node.actions_.clone();
+            actions.push(action);
+            open.push(AStarNode {
+                f_: next_state.game_score_ + optimisticRemainingScore(&next_state),
+                state_: next_state,
+                actions_: actions,
+            });
+        }
+    }
+    (Vec::new(), node_number)
+}
+
+// f値がthreshold未満のノードを枝刈りする深さ優先探索
+// 見つからなければ、枝刈りしたノードのf値の最大値を次のthresholdにする
+fn idaStarSearch(
+    state: &State,
+    threshold: i32,
+    actions: &mut Vec<usize>,
+    next_threshold: &mut i32,
+    node_number: &mut usize,
+) -> bool {
+    let f = state.game_score_ + optimisticRemainingScore(state);
+    if f < threshold {
+        *next_threshold = (*next_threshold).max(f);
+        return false;
+    }
+    if state.isDone() {
+        return true;
+    }
+    *node_number += 1;
+    for action in state.legalActions() {
+        let mut next_state = state.clone();
+        next_state.advance(action);
+        actions.push(action);
+        if idaStarSearch(&next_state, threshold, actions, next_threshold, node_number) {
+            return true;
+        }
+        actions.pop();
+    }
+    false
+}
+
+fn idaStarActions(state: &State) -> (Vec<usize>, usize) {
+    let mut threshold = state.game_score_ + optimisticRemainingScore(state);
+    let mut node_number = 0;
+    loop {
+        let mut actions = Vec::new();
+        let mut next_threshold = -INF as i32;
+        if idaStarSearch(
+            state,
+            threshold,
+            &mut actions,
+            &mut next_threshold,
+            &mut node_number,
+        ) {
+            return (actions, node_number);
+        }
+        threshold = next_threshold;
+    }
+}
+
+// 比較用に、枝刈りもメモ化もせずに全ての行動列を調べる
+fn bruteForceScore(state: &State, node_number: &mut usize) -> i32 {
+    if state.isDone() {
+        return state.game_score_;
+    }
+    *node_number += 1;
+    let mut best = 0;
+    for action in state.legalActions() {
+        let mut next_state = state.clone();
+        next_state.advance(action);
+        best = best.max(bruteForceScore(&next_state, node_number));
+    }
+    best
+}
+
+fn playGame(seed: usize) {
+    let mut state = State::new(seed);
+    println!("{}", state.toString());
+    while !state.isDone() {
+        // state.advance(randomAction(&state));
+        state.advance(greedyAction(&state));
+        println!("{}", state.toString());
+    }
+}
+
+type PlanFunction = fn(&State) -> (Vec<usize>, usize);
+type StringPlanPair = (String, PlanFunction);
+
+fn testOptimalSearch(plans: &[StringPlanPair], game_number: usize) {
+    let mut optimal_scores = Vec::new();
+    let start_time = time::Instant::now();
+    let mut node_number = 0;
+    for i in 0..game_number {
+        let state = State::new(i);
+        optimal_scores.push(bruteForceScore(&state, &mut node_number));
+    }
+    println!(
+        "bruteForceScore:\tscore {}\tnodes {}\t{}ms",
+        optimal_scores.iter().sum::<i32>() as f64 / game_number as f64,
+        node_number / game_number,
+        start_time.elapsed().as_millis()
+    );
+    for plan in plans.iter() {
+        let start_time = time::Instant::now();
+        let mut node_number = 0;
+        let mut score_sum = 0;
+        for i in 0..game_number {
+            let mut state = State::new(i);
+            let (actions, searched_number) = plan.1(&state);
+            for action in actions {
+                state.advance(action);
+            }
+            assert!(state.isDone());
+            assert_eq!(state.game_score_, optimal_scores[i]);
+            score_sum += state.game_score_;
+            node_number += searched_number;
+        }
+        println!(
+            "{}:\tscore {}\tnodes {}\t{}ms",
+            plan.0,
+            score_sum as f64 / game_number as f64,
+            node_number / game_number,
+            start_time.elapsed().as_millis()
+        );
+    }
+}
+
+fn main() {
+    let p0: PlanFunction = |state: &State| aStarActions(state);
+    let p1: PlanFunction = |state: &State| idaStarActions(state);
+    let plans: [StringPlanPair; 2] = [
+        ("aStarActions".to_string(), p0),
+        ("idaStarActions".to_string(), p1),
+    ];
+    testOptimalSearch(&plans, 100);
+}
@@ -0,0 +1,426 @@
+use std::collections::BinaryHeap;
+
+use rand;
+use rand::prelude::*;
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct Coord {
+    y_: i32,
+    x_: i32,
+}
+impl Coord {
+    pub fn new(y_: i32, x_: i32) -> Self {
+        Self { y_, x_ }
+    }
+}
+
+type ScoreType = i64;
+const INF: ScoreType = 1000000000;
+const H: i32 = 3;
+const W: i32 = 4;
+const END_TURN: i32 = 4;
+const INVALID_ACTION: usize = 100000;
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct MazeState {
+    points_: [[i32; W as usize]; H as usize],
+    turn_: i32,
+    pub charcter_: Coord,
+    pub game_score_: i32,
+    pub evaluated_score_: ScoreType,
+    pub first_action_: usize,
+}
+impl MazeState {
+    const dx: [i32; 4] = [1, -1, 0, 0];
+    const dy: [i32; 4] = [0, 0, 1, -1];
+    pub fn new(seed: usize) -> Self {
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(seed as u64);
+        let mut maze_state = MazeState {
+            points_: [[0 as i32; W as usize]; H as usize],
+            turn_: 0,
+            charcter_: Coord { y_: 0, x_: 0 },
+            game_score_: 0,
+            evaluated_score_: 0,
+            first_action_: 10,
+        };
+        maze_state.charcter_.y_ = rng.gen_range(0..=10) % H;
+        maze_state.charcter_.x_ = rng.gen_range(0..=10) % W;
+        for y in 0..H {
+            for x in 0..W {
+                if y == maze_state.charcter_.y_ && x == maze_state.charcter_.x_ {
+                    continue;
+                }
+                maze_state.points_[y as usize][x as usize] = rng.gen_range(1..=9);
+            }
+        }
+        maze_state
+    }
+
+    pub fn isDone(&self) -> bool {
+        return self.turn_ == END_TURN;
+    }
+
+    pub fn evaluateScore(&mut self) {
+        self.evaluated_score_ = self.game_score_ as ScoreType;
+    }
+
+    pub fn advance(&mut self, action: usize) {
+        if action == INVALID_ACTION {
+            self.turn_ += 1;
+            return;
+        }
+        self.charcter_.x_ += Self::dx[action];
+        self.charcter_.y_ += Self::dy[action];
+        let point = &mut self.points_[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+        if *point > 0 {
+            self.game_score_ += *point;
+            *point = 0;
+        }
+        self.turn_ += 1;
+    }
+    pub fn legalActions(&self) -> Vec<usize> {
+        let mut actions = Vec::new();
+        for action in 0..4 {
+            let ty = self.charcter_.y_ + Self::dy[action];
+            let tx = self.charcter_.x_ + Self::dx[action];
+            if ty >= 0 && ty < H && tx >= 0 && tx < W {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+    pub fn toString(&self) -> String {
+        let mut s = String::new();
+        s += "turn:\t";
+        s += &self.turn_.to_string();
+        s += "\n";
+        s += "score:\t";
+        s += &self.game_score_.to_string();
+        s += "\n";
+        for h in 0..H {
+            for w in 0..W {
+                if self.charcter_.y_ == h && self.charcter_.x_ == w {
+                    s.push('@');
+                } else if self.points_[h as usize][w as usize] > 0 {
+                    s += &self.points_[h as usize][w as usize].to_string();
+                } else {
+                    s.push('.');
+                }
+            }
+            s.push('\n');
+        }
+        s
+    }
+}
+
+impl Ord for MazeState {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.evaluated_score_.cmp(&other.evaluated_score_)
+    }
+}
+impl PartialOrd for MazeState {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+type State = MazeState;
+
+fn randomAction(state: &State) -> usize {
+    let mut rng = rand::thread_rng();
+
+    let legal_actions = state.legalActions();
+    return legal_actions[(rng.gen_range(0..=10) as usize % legal_actions.len())];
+}
+
+fn greedyAction(state: &State) -> usize {
+    let legal_actions = state.legalActions();
+    let mut best_score: ScoreType = -1;
+    let mut best_action = 10;
+    for action in legal_actions {
+        let mut now_state = state.clone();
+        now_state.advance(action);
+        now_state.evaluateScore();
+        if now_state.evaluated_score_ > best_score {
+            best_score = now_state.evaluated_score_;
+            best_action = action;
+        }
+    }
+    best_action
+}
+
+fn beamSearchAction(state: &State, beam_width: usize, beam_depth: usize) -> usize {
+    let mut now_beam: BinaryHeap<State> = BinaryHeap::new();
+    let mut best_state: State = State::new(11);
+    now_beam.push(state.clone());
+    for t in 0..beam_depth {
+        let mut next_beam: BinaryHeap<State> = BinaryHeap::new();
+        for i in 0..beam_width {
+            if now_beam.is_empty() {
+                break;
+            }
+            let mut now_state = now_beam.pop().unwrap();
+            let legal_actions = now_state.legalActions();
+            for action in legal_actions {
+                let mut next_state = now_state.clone();
+                next_state.advance(action);
+                next_state.evaluateScore();
+                if t == 0 {
+                    next_state.first_action_ = action;
+                }
+                next_beam.push(next_state);
+            }
+        }
+        now_beam = next_beam.clone();
+        best_state = next_beam.pop().unwrap();
+        if best_state.isDone() {
+            break;
+        }
+    }
+    best_state.first_action_
+}
+
+fn chokudaiSearchAction(
+    state: &State,
+    beam_width: usize,
+    beam_depth: usize,
+    beam_number: usize,
+) -> usize {
+    let mut beam = Vec::new();
+    for t in 0..beam_depth + 1 {
+        beam.push(BinaryHeap::<State>::new());
+    }
+    beam[0].push(state.clone());
+    for cnt in 0..beam_number {
+        for t in 0..beam_depth {
+            for i in 0..beam_width {
+                if beam[t].is_empty() {
+                    break;
+                }
+                let mut now_state = beam[t].peek().unwrap().clone();
+                if now_state.isDone() {
+                    break;
+                }
+                beam[t].pop();
+                let legal_actions = now_state.legalActions();
+                for action in legal_actions {
+                    let mut next_state = now_state.clone();
+                    next_state.advance(action);
+                    next_state.evaluateScore();
+                    if t == 0 {
+                        next_state.first_action_ = action;
+                    }
+                    beam[t + 1].push(next_state);
+                }
+            }
+        }
+    }
+    for t in 0..=beam_depth {
+        let now_beam = &beam[beam_depth - t];
+        if !now_beam.is_empty() {
+            return now_beam.peek().unwrap().first_action_;
+        }
+    }
+    INVALID_ACTION
+}
+
+pub mod sp_mcts {
+    use rand::Rng;
+
+    use crate::State;
+    use crate::H;
+    use crate::INF;
+    use crate::W;
+
+    const C: f64 = 0.5;
+    // 分散項に足す定数。訪問回数が少ないノードの分散を大きめに見積もる
+    const D: f64 = 0.01;
+    const EXPAND_THRESHOLD: usize = 10;
+
+    // 探索全体で共有する情報
+    struct Context {
+        rng_: rand::rngs::StdRng,
+        score_scale_: f64,
+        actions_: Vec<usize>,
+        best_score_: i32,
+        best_actions_: Vec<usize>,
+    }
+    impl Context {
+        // 根からの行動列actions_にrest_actionsを続けた行動列で、scoreが得られた
+        fn record(&mut self, score: i32, rest_actions: &[usize]) {
+            if score > self.best_score_ {
+                self.best_score_ = score;
+                self.best_actions_ = self.actions_.clone();
+                self.best_actions_.extend_from_slice(rest_actions);
+            }
+        }
+    }
+
+    fn playout(state: &State, rng: &mut rand::rngs::StdRng) -> (i32, Vec<usize>) {
+        let mut now_state = state.clone();
+        let mut actions = Vec::new();
+        while !now_state.isDone() {
+            let legal_actions = now_state.legalActions();
+            let action = legal_actions[rng.gen_range(0..legal_actions.len())];
+            now_state.advance(action);
+            actions.push(action);
+        }
+        (now_state.game_score_, actions)
+    }
+
+    // 勝敗ではなく、得点を[0, 1]に正規化した値を逆伝播する
+    struct Node {
+        state_: State,
+        w_: f64,
+        w2_: f64,
+        pub child_nodes: Vec<Self>,
+        pub n_: f64,
+        legal_actions_: Vec<usize>,
+    }
+    impl Node {
+        pub fn new(state: &State) -> Self {
+            Self {
+                state_: state.clone(),
+                w_: 0.0,
+                w2_: 0.0,
+                child_nodes: Vec::new(),
+                n_: 0.0,
+                legal_actions_: Vec::new(),
+            }
+        }
+
+        fn update(&mut self, value: f64) {
+            self.w_ += value;
+            self.w2_ += value * value;
+            self.n_ += 1.0;
+        }
+
+        fn evaluate(&mut self, context: &mut Context) -> f64 {
+            if self.state_.isDone() {
+                context.record(self.state_.game_score_, &[]);
+                let value = self.state_.game_score_ as f64 / context.score_scale_;
+                self.update(value);
+                return value;
+            }
+            if self.child_nodes.is_empty() {
+                let (score, rest_actions) = playout(&self.state_, &mut context.rng_);
+                context.record(score, &rest_actions);
+                let value = score as f64 / context.score_scale_;
+                self.update(value);
+                if self.n_ == EXPAND_THRESHOLD as f64 {
+                    self.expand();
+                }
+                return value;
+            } else {
+                let index = self.nextChildIndex();
+                context.actions_.push(self.legal_actions_[index]);
+                let value = self.child_nodes[index].evaluate(context);
+                context.actions_.pop();
+                self.update(value);
+                return value;
+            }
+        }
+
+        fn expand(&mut self) {
+            self.legal_actions_ = self.state_.legalActions();
+            self.child_nodes.clear();
+            for &action in self.legal_actions_.iter() {
+                let mut add_state = Self::new(&self.state_);
+                add_state.state_.advance(action);
+                self.child_nodes.push(add_state);
+            }
+        }
+
+        // UCB1に、報酬の分散が大きい子ほど大きくなる項を加えて選ぶ
+        fn nextChildIndex(&self) -> usize {
+            for i in 0..self.child_nodes.len() {
+                if self.child_nodes[i].n_ == 0.0 {
+                    return i;
+                }
+            }
+            let mut t = 0.0;
+            for child_node in self.child_nodes.iter() {
+                t += child_node.n_;
+            }
+            let mut best_value = -INF as f64;
+            let mut best_action_index = 0;
+            for i in 0..self.child_nodes.len() {
+                let child_node = &self.child_nodes[i];
+                let mean = child_node.w_ / child_node.n_;
+                let variance = (child_node.w2_ - child_node.n_ * mean * mean + D) / child_node.n_;
+                let ucb_value =
+                    mean + C * (2.0 * t.ln() / child_node.n_).sqrt() + variance.max(0.0).sqrt();
+                if ucb_value > best_value {
+                    best_action_index = i;
+                    best_value = ucb_value;
+                }
+            }
+            best_action_index
+        }
+    }
+
+    // 探索中に見つかった最も得点の高い行動列を返す
+    pub fn spMctsActions(state: &State, playout_number: usize) -> Vec<usize> {
+        let mut remaining_point = 0;
+        for y in 0..H as usize {
+            for x in 0..W as usize {
+                remaining_point += state.points_[y][x];
+            }
+        }
+        let mut context = Context {
+            rng_: rand::SeedableRng::seed_from_u64(0),
+            score_scale_: (state.game_score_ + remaining_point).max(1) as f64,
+            actions_: Vec::new(),
+            best_score_: -1,
+            best_actions_: Vec::new(),
+        };
+        let mut root_node = Node::new(state);
+        root_node.expand();
+        for _ in 0..playout_number {
+            root_node.evaluate(&mut context);
+        }
+        context.best_actions_
+    }
+
+    pub fn spMctsAction(state: &State, playout_number: usize) -> usize {
+        spMctsActions(state, playout_number)[0]
+    }
+}
+
+fn playGame(seed: usize) {
+    let mut state = State::new(seed);
+    println!("{}", state.toString());
+    while !state.isDone() {
+        // state.advance(randomAction(&state));
+        state.advance(greedyAction(&state));
+        println!("{}", state.toString());
+    }
+}
+
+type AIFunction = fn(&State) -> usize;
+type StringAIPair = (String, AIFunction);
+
+fn testAiScore(ai: &StringAIPair, game_number: usize) {
+    let mut score_mean = 0.0;
+    for i in 0..game_number {
+        let mut state = State::new(i);
+        while !state.isDone() {
+            state.advance(ai.1(&state));
+        }
+        score_mean += state.game_score_ as f64;
+    }
+    score_mean /= game_number as f64;
+    println!("Score of {}:\t{}", ai.0, score_mean);
+}
+
+fn main() {
+    let f0: AIFunction = |state: &State| beamSearchAction(state, 2, END_TURN as usize);
+    let f1: AIFunction = |state: &State| sp_mcts::spMctsAction(state, 100);
+    let ais: [StringAIPair; 2] = [
+        ("beamSearchAction".to_string(), f0),
+        ("spMctsAction".to_string(), f1),
+    ];
+    for ai in ais.iter() {
+        testAiScore(ai, 100);
+    }
+}
@@ -140,7 +140,7 @@ impl AutoMoveMazeState {
         }
     }
     pub fn transition(&mut self) {
-        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0 as u64);
+        let mut rng = rand::thread_rng();
         let character = &mut self.characters_[rng.gen::<usize>() % CHARACTER_N];
         character.y_ = rng.gen_range(0..H);
         character.x_ = rng.gen_range(0..W);
@@ -164,7 +164,7 @@ fn hillClimb(state: &State, number: i32) -> State {
     let mut best_score = now_state.getScore(false);
     for i in 0..number {
         let mut next_state = now_state.clone();
-        now_state.transition();
+        next_state.transition();
         let next_score = next_state.getScore(false);
         if next_score > best_score {
             best_score = next_score;
@@ -141,7 +141,7 @@ impl AutoMoveMazeState {
         }
     }
     pub fn transition(&mut self) {
-        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0 as u64);
+        let mut rng = rand::thread_rng();
         let character = &mut self.characters_[rng.gen::<usize>() % CHARACTER_N];
         character.y_ = rng.gen_range(0..H);
         character.x_ = rng.gen_range(0..W);
@@ -165,7 +165,7 @@ fn hillClimb(state: &State, number: i32) -> State {
     let mut best_score = now_state.getScore(false);
     for i in 0..number {
         let mut next_state = now_state.clone();
-        now_state.transition();
+        next_state.transition();
         let next_score = next_state.getScore(false);
         if next_score > best_score {
             best_score = next_score;
@@ -184,7 +184,7 @@ fn simulatedAnnealing(state: &State, number: i32, start_temp: f64, end_temp: f64
     let mut best_state = now_state.clone();
     for i in 0..number {
         let mut next_state = now_state.clone();
-        now_state.transition();
+        next_state.transition();
         let next_score = next_state.getScore(false);
         let temp = start_temp + (end_temp - start_temp) * (i as f64 / number as f64);
         let probability = ((next_score - now_score) as f64 / temp).exp();
@@ -195,10 +195,10 @@ fn simulatedAnnealing(state: &State, number: i32, start_temp: f64, end_temp: f64
         }
         if next_score > best_score {
             best_score = next_score;
-            now_state = next_state;
+            best_state = next_state;
         }
     }
-    now_state
+    best_state
 }
 
 // type AIFunction =
@@ -208,19 +208,127 @@ fn playGame(seed: usize) {
     let score = state.getScore(true);
     println!("Score of hillClimb: {}", score);
 }
+// 得点はキャラクターの並び順によらない(全員が動いてから得点を取るので、同じターンの移動は互いに影響しない)。
+// そのためキャラクターの置くマスの番号が昇順の配置だけを調べれば十分で、25^3通りが2925通りに減る
+fn searchPlacements(
+    now_state: &mut State,
+    character_id: usize,
+    min_cell: usize,
+    best: &mut ([usize; CHARACTER_N], ScoreType),
+    cells: &mut [usize; CHARACTER_N],
+) {
+    if character_id == CHARACTER_N {
+        let score = now_state.getScore(false);
+        // スレッド数によらず同じ解を返すよう、同点ならマスの番号が辞書順で小さい配置を選ぶ
+        if score > best.1 || (score == best.1 && *cells < best.0) {
+            *best = (*cells, score);
+        }
+        return;
+    }
+    for cell in min_cell..(H * W) as usize {
+        cells[character_id] = cell;
+        now_state.setCharacter(character_id, (cell as i32) / W, (cell as i32) % W);
+        searchPlacements(now_state, character_id + 1, cell, best, cells);
+    }
+}
+
+// 全ての配置を調べて最適な配置とその得点を返す
+// 1人目のキャラクターのマスをthread_number本のスレッドに分けて並列に探索する
+fn exhaustiveSearch(state: &State, thread_number: usize) -> (State, ScoreType) {
+    let thread_number = thread_number.max(1);
+    let cell_n = (H * W) as usize;
+    let results: Vec<([usize; CHARACTER_N], ScoreType)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..thread_number)
+            .map(|thread_id| {
+                scope.spawn(move || {
+                    let mut now_state = state.clone();
+                    let mut best = ([cell_n; CHARACTER_N], -INF);
+                    let mut cells = [0; CHARACTER_N];
+                    for first_cell in (thread_id..cell_n).step_by(thread_number) {
+                        cells[0] = first_cell;
+                        now_state.setCharacter(0, (first_cell as i32) / W, (first_cell as i32) % W);
+                        searchPlacements(&mut now_state, 1, first_cell, &mut best, &mut cells);
+                    }
+                    best
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    });
+    let mut best = results[0];
+    for result in results.into_iter() {
+        if result.1 > best.1 || (result.1 == best.1 && result.0 < best.0) {
+            best = result;
+        }
+    }
+    let mut best_state = state.clone();
+    for (character_id, cell) in best.0.iter().enumerate() {
+        best_state.setCharacter(character_id, (*cell as i32) / W, (*cell as i32) % W);
+    }
+    (best_state, best.1)
+}
+
+// seed 0..game_numberの盤面の最適な得点を、04_Annealerと06_MetaheuristicsのOPTIMAL_SCORESに貼れる形で表示する
+fn printOptimalScores(game_number: usize) {
+    let thread_number = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let optimal_scores: Vec<String> = (0..game_number)
+        .map(|seed| {
+            exhaustiveSearch(&State::new(seed), thread_number)
+                .1
+                .to_string()
+        })
+        .collect();
+    println!(
+        "const OPTIMAL_SCORES: [ScoreType; {}] = [{}];",
+        game_number,
+        optimal_scores.join(", ")
+    );
+}
+
+// シードごとに最適解を求め、山登り法と焼きなまし法の得点との差を表示する
+// 焼きなましの設定ごとの差は04_Annealer、タブー探索などほかの手法の差は06_Metaheuristicsで表示する
 fn testAiScore(game_number: usize) {
-    let mut rng = rand::thread_rng();
-    let mut score_mean = 0.0;
+    let thread_number = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let mut hill_climb_gap_mean = 0.0;
+    let mut annealing_gap_mean = 0.0;
+    let mut optimal_score_mean = 0.0;
+    let mut annealing_score_mean = 0.0;
 
-    for i in 0..game_number {
-        let mut state = State::new(rng.gen_range(0..INF) as usize);
-        state = simulatedAnnealing(&state, 10000, 500.0, 10.0);
-        score_mean += state.getScore(false) as f64;
+    for seed in 0..game_number {
+        let state = State::new(seed);
+        let (_, optimal_score) = exhaustiveSearch(&state, thread_number);
+        let hill_climb_score = hillClimb(&state, 10000).getScore(false);
+        let annealing_score = simulatedAnnealing(&state, 10000, 500.0, 10.0).getScore(false);
+        println!(
+            "seed {}:\toptimal {}\thillClimb {} (gap {})\tsimulatedAnnealing {} (gap {})",
+            seed,
+            optimal_score,
+            hill_climb_score,
+            optimal_score - hill_climb_score,
+            annealing_score,
+            optimal_score - annealing_score
+        );
+        hill_climb_gap_mean += (optimal_score - hill_climb_score) as f64;
+        annealing_gap_mean += (optimal_score - annealing_score) as f64;
+        optimal_score_mean += optimal_score as f64;
+        annealing_score_mean += annealing_score as f64;
     }
-    score_mean /= game_number as f64;
-    println!("Score of Annewaling:\t {}", score_mean);
+    hill_climb_gap_mean /= game_number as f64;
+    annealing_gap_mean /= game_number as f64;
+    optimal_score_mean /= game_number as f64;
+    annealing_score_mean /= game_number as f64;
+    println!("Score of Optimal:\t {}", optimal_score_mean);
+    println!("Score of Annewaling:\t {}", annealing_score_mean);
+    println!(
+        "Mean gap:\thillClimb {}\tsimulatedAnnealing {}",
+        hill_climb_gap_mean, annealing_gap_mean
+    );
 }
 
 fn main() {
     testAiScore(10);
+    printOptimalScores(100);
 }
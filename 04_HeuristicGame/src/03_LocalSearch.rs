@@ -0,0 +1,377 @@
+use rand;
+use rand::prelude::*;
+
+const H: i32 = 5;
+const W: i32 = 5;
+const END_TURN: usize = 5;
+const CHARACTER_N: usize = 3;
+type ScoreType = i32;
+const INF: ScoreType = 100000000;
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Copy)]
+struct Coord {
+    y_: i32,
+    x_: i32,
+}
+impl Coord {
+    pub fn new(y_: i32, x_: i32) -> Self {
+        Self { y_, x_ }
+    }
+}
+#[derive(Debug, Default, Clone, Eq, PartialEq, Copy)]
+struct AutoMoveMazeState {
+    points_: [[i32; W as usize]; H as usize],
+    turn_: i32,
+    pub characters_: [Coord; CHARACTER_N],
+    pub game_score_: i32,
+    pub evaluated_score_: ScoreType,
+}
+impl AutoMoveMazeState {
+    const dx: [i32; 4] = [1, -1, 0, 0];
+    const dy: [i32; 4] = [0, 0, 1, -1];
+
+    fn movePlayer(&mut self, character_id: usize) {
+        let character = &mut self.characters_[character_id];
+        let mut best_point = -INF;
+        let mut best_action_index = 0;
+        for action in 0..4 {
+            let ty = character.y_ + Self::dy[action];
+            let tx: i32 = character.x_ + Self::dx[action];
+            if ty >= 0 && ty < H && tx >= 0 && tx < W {
+                let point = self.points_[ty as usize][tx as usize];
+                if point > best_point {
+                    best_point = point;
+                    best_action_index = action;
+                }
+            }
+        }
+        character.y_ += Self::dy[best_action_index];
+        character.x_ += Self::dx[best_action_index];
+    }
+    fn advance(&mut self) {
+        for character_id in 0..CHARACTER_N {
+            self.movePlayer(character_id);
+        }
+        for character in self.characters_.iter() {
+            let point = &mut self.points_[character.y_ as usize][character.x_ as usize];
+            self.game_score_ += *point;
+            *point = 0;
+        }
+        self.turn_ += 1;
+    }
+
+    pub fn new(seed: usize) -> Self {
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(seed as u64);
+        let mut maze_state = AutoMoveMazeState {
+            points_: [[0 as i32; W as usize]; H as usize],
+            turn_: 0,
+            game_score_: 0,
+            evaluated_score_: 0,
+            characters_: [Coord::new(0, 0); CHARACTER_N],
+        };
+        for y in 0..H {
+            for x in 0..W {
+                maze_state.points_[y as usize][x as usize] = rng.gen_range(0..9) + 1;
+            }
+        }
+        maze_state
+    }
+
+    pub fn setCharacter(&mut self, character_id: usize, y: i32, x: i32) {
+        self.characters_[character_id].y_ = y;
+        self.characters_[character_id].x_ = x;
+    }
+
+    pub fn isDone(&self) -> bool {
+        return self.turn_ == END_TURN as i32;
+    }
+    pub fn toString(&self) -> String {
+        let mut ss = String::new();
+        ss += format!("turn:\t{}\n", self.turn_).as_str();
+        ss += format!("score:\t{}\n", self.game_score_).as_str();
+        let mut board_chars = [['.'; W as usize]; H as usize];
+        for h in 0..H {
+            for w in 0..W {
+                let mut is_written = false;
+                for character in self.characters_ {
+                    if character.y_ == h && character.x_ == w {
+                        ss += "@";
+                        is_written = true;
+                        break;
+                    }
+                    board_chars[character.y_ as usize][character.x_ as usize] = '@';
+                }
+                if !is_written {
+                    if self.points_[h as usize][w as usize] > 0 {
+                        ss += self.points_[h as usize][w as usize].to_string().as_str();
+                    } else {
+                        ss += ".";
+                    }
+                }
+            }
+            ss += "\n";
+        }
+        ss
+    }
+
+    pub fn getScore(&self, is_print: bool) -> ScoreType {
+        let mut tmp_state = self.clone();
+        for character in self.characters_ {
+            let point = &mut tmp_state.points_[character.y_ as usize][character.x_ as usize];
+            *point = 0;
+        }
+        while !tmp_state.isDone() {
+            tmp_state.advance();
+            if is_print {
+                println!("{}", tmp_state.toString());
+            }
+        }
+        tmp_state.game_score_
+    }
+}
+
+type State = AutoMoveMazeState;
+fn randomAction(state: &State) -> State {
+    let mut rng = rand::thread_rng();
+    let mut now_state = state.clone();
+    for character_id in 0..CHARACTER_N {
+        let y = rng.gen_range(0..H);
+        let x = rng.gen_range(0..W);
+        now_state.setCharacter(character_id, y, x);
+    }
+    now_state
+}
+// 局所探索で解く問題
+// deltaScoreは近傍解の得点とnow_scoreとの差を返す。差分計算できる問題は上書きして高速化する
+trait LocalSearchProblem {
+    type Solution: Clone;
+    fn initialSolution(&self, rng: &mut rand::rngs::StdRng) -> Self::Solution;
+    fn neighbour(&self, solution: &Self::Solution, rng: &mut rand::rngs::StdRng) -> Self::Solution;
+    fn score(&self, solution: &Self::Solution) -> ScoreType;
+    fn deltaScore(
+        &self,
+        _now_solution: &Self::Solution,
+        now_score: ScoreType,
+        next_solution: &Self::Solution,
+    ) -> ScoreType {
+        self.score(next_solution) - now_score
+    }
+}
+
+// 近傍の作り方
+trait Neighbourhood {
+    fn name(&self) -> String;
+    fn apply(&self, state: &State, rng: &mut rand::rngs::StdRng) -> State;
+}
+
+// キャラクターを1人選び、上下左右のいずれかに1マス動かす
+struct MoveOneCell;
+impl Neighbourhood for MoveOneCell {
+    fn name(&self) -> String {
+        "moveOneCell".to_string()
+    }
+    fn apply(&self, state: &State, rng: &mut rand::rngs::StdRng) -> State {
+        let mut next_state = state.clone();
+        let character_id = rng.gen_range(0..CHARACTER_N);
+        let character = next_state.characters_[character_id];
+        loop {
+            let action = rng.gen_range(0..4);
+            let ty = character.y_ + State::dy[action];
+            let tx = character.x_ + State::dx[action];
+            if ty >= 0 && ty < H && tx >= 0 && tx < W {
+                next_state.setCharacter(character_id, ty, tx);
+                return next_state;
+            }
+        }
+    }
+}
+
+// キャラクターを2人選び、それぞれ上下左右のいずれかに1マス動かす
+// 1人ずつ動かす近傍では、途中で一度得点が下がる配置を通らないと届かない配置にも1回で移れる
+// 2人の位置を入れ替える近傍は使わない。advanceでは全員が動いてから得点を取るので、
+// キャラクターの順番が変わっても軌跡は同じになり、得点が変わらないため
+struct MoveTwoCharacters;
+impl Neighbourhood for MoveTwoCharacters {
+    fn name(&self) -> String {
+        "moveTwoCharacters".to_string()
+    }
+    fn apply(&self, state: &State, rng: &mut rand::rngs::StdRng) -> State {
+        let mut next_state = state.clone();
+        let character_id0 = rng.gen_range(0..CHARACTER_N);
+        let character_id1 = (character_id0 + rng.gen_range(1..CHARACTER_N)) % CHARACTER_N;
+        for character_id in [character_id0, character_id1] {
+            let character = next_state.characters_[character_id];
+            loop {
+                let action = rng.gen_range(0..4);
+                let ty = character.y_ + State::dy[action];
+                let tx = character.x_ + State::dx[action];
+                if ty >= 0 && ty < H && tx >= 0 && tx < W {
+                    next_state.setCharacter(character_id, ty, tx);
+                    break;
+                }
+            }
+        }
+        next_state
+    }
+}
+
+// キャラクターを1人選び、盤面全体からランダムに置き直す
+struct Rerandomize;
+impl Neighbourhood for Rerandomize {
+    fn name(&self) -> String {
+        "rerandomize".to_string()
+    }
+    fn apply(&self, state: &State, rng: &mut rand::rngs::StdRng) -> State {
+        let mut next_state = state.clone();
+        let character_id = rng.gen_range(0..CHARACTER_N);
+        next_state.setCharacter(character_id, rng.gen_range(0..H), rng.gen_range(0..W));
+        next_state
+    }
+}
+
+// 重みに比例した確率で近傍を選んで使う
+struct WeightedNeighbourhood {
+    neighbourhoods_: Vec<(Box<dyn Neighbourhood>, f64)>,
+}
+impl WeightedNeighbourhood {
+    pub fn new(neighbourhoods: Vec<(Box<dyn Neighbourhood>, f64)>) -> Self {
+        Self {
+            neighbourhoods_: neighbourhoods,
+        }
+    }
+}
+impl Neighbourhood for WeightedNeighbourhood {
+    fn name(&self) -> String {
+        let names: Vec<String> = self
+            .neighbourhoods_
+            .iter()
+            .map(|(neighbourhood, weight)| format!("{}*{}", neighbourhood.name(), weight))
+            .collect();
+        names.join("+")
+    }
+    fn apply(&self, state: &State, rng: &mut rand::rngs::StdRng) -> State {
+        let weight_sum: f64 = self.neighbourhoods_.iter().map(|(_, weight)| weight).sum();
+        let mut r = rng.gen::<f64>() * weight_sum;
+        for (neighbourhood, weight) in self.neighbourhoods_.iter() {
+            if r < *weight {
+                return neighbourhood.apply(state, rng);
+            }
+            r -= weight;
+        }
+        self.neighbourhoods_.last().unwrap().0.apply(state, rng)
+    }
+}
+
+// キャラクターの初期配置を決める問題
+struct CharacterPlacementProblem {
+    state_: State,
+    neighbourhood_: Box<dyn Neighbourhood>,
+}
+impl CharacterPlacementProblem {
+    pub fn new(state: &State, neighbourhood: Box<dyn Neighbourhood>) -> Self {
+        Self {
+            state_: state.clone(),
+            neighbourhood_: neighbourhood,
+        }
+    }
+}
+impl LocalSearchProblem for CharacterPlacementProblem {
+    type Solution = State;
+    fn initialSolution(&self, rng: &mut rand::rngs::StdRng) -> State {
+        let mut now_state = self.state_.clone();
+        for character_id in 0..CHARACTER_N {
+            now_state.setCharacter(character_id, rng.gen_range(0..H), rng.gen_range(0..W));
+        }
+        now_state
+    }
+    fn neighbour(&self, solution: &State, rng: &mut rand::rngs::StdRng) -> State {
+        self.neighbourhood_.apply(solution, rng)
+    }
+    fn score(&self, solution: &State) -> ScoreType {
+        solution.getScore(false)
+    }
+}
+
+fn hillClimb<P: LocalSearchProblem>(
+    problem: &P,
+    number: usize,
+    rng: &mut rand::rngs::StdRng,
+) -> P::Solution {
+    let mut now_solution = problem.initialSolution(rng);
+    let mut now_score = problem.score(&now_solution);
+    for _ in 0..number {
+        let next_solution = problem.neighbour(&now_solution, rng);
+        let delta = problem.deltaScore(&now_solution, now_score, &next_solution);
+        if delta > 0 {
+            now_score += delta;
+            now_solution = next_solution;
+        }
+    }
+    now_solution
+}
+
+fn simulatedAnnealing<P: LocalSearchProblem>(
+    problem: &P,
+    number: usize,
+    start_temp: f64,
+    end_temp: f64,
+    rng: &mut rand::rngs::StdRng,
+) -> P::Solution {
+    let mut now_solution = problem.initialSolution(rng);
+    let mut now_score = problem.score(&now_solution);
+    let mut best_score = now_score;
+    let mut best_solution = now_solution.clone();
+    for i in 0..number {
+        let next_solution = problem.neighbour(&now_solution, rng);
+        let delta = problem.deltaScore(&now_solution, now_score, &next_solution);
+        let temp = start_temp + (end_temp - start_temp) * (i as f64 / number as f64);
+        let probability = (delta as f64 / temp).exp();
+        if delta > 0 || probability > rng.gen::<f64>() {
+            now_score += delta;
+            now_solution = next_solution;
+            if now_score > best_score {
+                best_score = now_score;
+                best_solution = now_solution.clone();
+            }
+        }
+    }
+    best_solution
+}
+
+fn testAiScore(game_number: usize) {
+    let neighbourhoods: Vec<fn() -> Box<dyn Neighbourhood>> = vec![
+        || Box::new(Rerandomize),
+        || Box::new(MoveOneCell),
+        || Box::new(MoveTwoCharacters),
+        || {
+            Box::new(WeightedNeighbourhood::new(vec![
+                (Box::new(MoveOneCell), 6.0),
+                (Box::new(MoveTwoCharacters), 1.0),
+                (Box::new(Rerandomize), 3.0),
+            ]))
+        },
+    ];
+    for make_neighbourhood in neighbourhoods.iter() {
+        let name = make_neighbourhood().name();
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0);
+        let mut hill_climb_score_mean = 0.0;
+        let mut annealing_score_mean = 0.0;
+        for i in 0..game_number {
+            let problem = CharacterPlacementProblem::new(&State::new(i), make_neighbourhood());
+            let state = hillClimb(&problem, 10000, &mut rng);
+            hill_climb_score_mean += state.getScore(false) as f64;
+            let state = simulatedAnnealing(&problem, 10000, 500.0, 10.0, &mut rng);
+            annealing_score_mean += state.getScore(false) as f64;
+        }
+        hill_climb_score_mean /= game_number as f64;
+        annealing_score_mean /= game_number as f64;
+        println!(
+            "{}:\thillClimb {}\tsimulatedAnnealing {}",
+            name, hill_climb_score_mean, annealing_score_mean
+        );
+    }
+}
+
+fn main() {
+    testAiScore(10);
+}
@@ -0,0 +1,413 @@
+use std::time;
+
+use rand;
+use rand::prelude::*;
+
+struct TimeKeeper {
+    start_time_: std::time::Instant,
+    time_threshold_: u64,
+}
+impl TimeKeeper {
+    pub fn new(time_threshold: u64) -> Self {
+        Self {
+            start_time_: time::Instant::now(),
+            time_threshold_: time_threshold,
+        }
+    }
+    // 制限時間のうち経過した割合
+    pub fn progress(&self) -> f64 {
+        self.start_time_.elapsed().as_secs_f64() * 1000.0 / self.time_threshold_ as f64
+    }
+}
+
+const H: i32 = 5;
+const W: i32 = 5;
+const END_TURN: usize = 5;
+const CHARACTER_N: usize = 3;
+type ScoreType = i32;
+const INF: ScoreType = 100000000;
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Copy)]
+struct Coord {
+    y_: i32,
+    x_: i32,
+}
+impl Coord {
+    pub fn new(y_: i32, x_: i32) -> Self {
+        Self { y_, x_ }
+    }
+}
+#[derive(Debug, Default, Clone, Eq, PartialEq, Copy)]
+struct AutoMoveMazeState {
+    points_: [[i32; W as usize]; H as usize],
+    turn_: i32,
+    pub characters_: [Coord; CHARACTER_N],
+    pub game_score_: i32,
+    pub evaluated_score_: ScoreType,
+}
+impl AutoMoveMazeState {
+    const dx: [i32; 4] = [1, -1, 0, 0];
+    const dy: [i32; 4] = [0, 0, 1, -1];
+
+    fn movePlayer(&mut self, character_id: usize) {
+        let character = &mut self.characters_[character_id];
+        let mut best_point = -INF;
+        let mut best_action_index = 0;
+        for action in 0..4 {
+            let ty = character.y_ + Self::dy[action];
+            let tx: i32 = character.x_ + Self::dx[action];
+            if ty >= 0 && ty < H && tx >= 0 && tx < W {
+                let point = self.points_[ty as usize][tx as usize];
+                if point > best_point {
+                    best_point = point;
+                    best_action_index = action;
+                }
+            }
+        }
+        character.y_ += Self::dy[best_action_index];
+        character.x_ += Self::dx[best_action_index];
+    }
+    fn advance(&mut self) {
+        for character_id in 0..CHARACTER_N {
+            self.movePlayer(character_id);
+        }
+        for character in self.characters_.iter() {
+            let point = &mut self.points_[character.y_ as usize][character.x_ as usize];
+            self.game_score_ += *point;
+            *point = 0;
+        }
+        self.turn_ += 1;
+    }
+
+    pub fn new(seed: usize) -> Self {
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(seed as u64);
+        let mut maze_state = AutoMoveMazeState {
+            points_: [[0 as i32; W as usize]; H as usize],
+            turn_: 0,
+            game_score_: 0,
+            evaluated_score_: 0,
+            characters_: [Coord::new(0, 0); CHARACTER_N],
+        };
+        for y in 0..H {
+            for x in 0..W {
+                maze_state.points_[y as usize][x as usize] = rng.gen_range(0..9) + 1;
+            }
+        }
+        maze_state
+    }
+
+    pub fn setCharacter(&mut self, character_id: usize, y: i32, x: i32) {
+        self.characters_[character_id].y_ = y;
+        self.characters_[character_id].x_ = x;
+    }
+
+    pub fn isDone(&self) -> bool {
+        return self.turn_ == END_TURN as i32;
+    }
+    pub fn toString(&self) -> String {
+        let mut ss = String::new();
+        ss += format!("turn:\t{}\n", self.turn_).as_str();
+        ss += format!("score:\t{}\n", self.game_score_).as_str();
+        let mut board_chars = [['.'; W as usize]; H as usize];
+        for h in 0..H {
+            for w in 0..W {
+                let mut is_written = false;
+                for character in self.characters_ {
+                    if character.y_ == h && character.x_ == w {
+                        ss += "@";
+                        is_written = true;
+                        break;
+                    }
+                    board_chars[character.y_ as usize][character.x_ as usize] = '@';
+                }
+                if !is_written {
+                    if self.points_[h as usize][w as usize] > 0 {
+                        ss += self.points_[h as usize][w as usize].to_string().as_str();
+                    } else {
+                        ss += ".";
+                    }
+                }
+            }
+            ss += "\n";
+        }
+        ss
+    }
+
+    pub fn getScore(&self, is_print: bool) -> ScoreType {
+        let mut tmp_state = self.clone();
+        for character in self.characters_ {
+            let point = &mut tmp_state.points_[character.y_ as usize][character.x_ as usize];
+            *point = 0;
+        }
+        while !tmp_state.isDone() {
+            tmp_state.advance();
+            if is_print {
+                println!("{}", tmp_state.toString());
+            }
+        }
+        tmp_state.game_score_
+    }
+}
+
+type State = AutoMoveMazeState;
+// 局所探索で解く問題
+// deltaScoreは近傍解の得点とnow_scoreとの差を返す。差分計算できる問題は上書きして高速化する
+trait LocalSearchProblem {
+    type Solution: Clone;
+    fn initialSolution(&self, rng: &mut rand::rngs::StdRng) -> Self::Solution;
+    fn neighbour(&self, solution: &Self::Solution, rng: &mut rand::rngs::StdRng) -> Self::Solution;
+    fn score(&self, solution: &Self::Solution) -> ScoreType;
+    fn deltaScore(
+        &self,
+        _now_solution: &Self::Solution,
+        now_score: ScoreType,
+        next_solution: &Self::Solution,
+    ) -> ScoreType {
+        self.score(next_solution) - now_score
+    }
+}
+
+// 近傍の作り方
+trait Neighbourhood {
+    fn apply(&self, state: &State, rng: &mut rand::rngs::StdRng) -> State;
+}
+
+// キャラクターを1人選び、盤面全体からランダムに置き直す
+struct Rerandomize;
+impl Neighbourhood for Rerandomize {
+    fn apply(&self, state: &State, rng: &mut rand::rngs::StdRng) -> State {
+        let mut next_state = state.clone();
+        let character_id = rng.gen_range(0..CHARACTER_N);
+        next_state.setCharacter(character_id, rng.gen_range(0..H), rng.gen_range(0..W));
+        next_state
+    }
+}
+
+// キャラクターの初期配置を決める問題
+struct CharacterPlacementProblem {
+    state_: State,
+    neighbourhood_: Box<dyn Neighbourhood>,
+}
+impl CharacterPlacementProblem {
+    pub fn new(state: &State, neighbourhood: Box<dyn Neighbourhood>) -> Self {
+        Self {
+            state_: state.clone(),
+            neighbourhood_: neighbourhood,
+        }
+    }
+}
+impl LocalSearchProblem for CharacterPlacementProblem {
+    type Solution = State;
+    fn initialSolution(&self, rng: &mut rand::rngs::StdRng) -> State {
+        let mut now_state = self.state_.clone();
+        for character_id in 0..CHARACTER_N {
+            now_state.setCharacter(character_id, rng.gen_range(0..H), rng.gen_range(0..W));
+        }
+        now_state
+    }
+    fn neighbour(&self, solution: &State, rng: &mut rand::rngs::StdRng) -> State {
+        self.neighbourhood_.apply(solution, rng)
+    }
+    fn score(&self, solution: &State) -> ScoreType {
+        solution.getScore(false)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum TemperatureSchedule {
+    Linear,
+    Exponential,
+    // 序盤に速く、終盤はゆっくり冷える
+    Logarithmic,
+}
+impl TemperatureSchedule {
+    pub fn name(&self) -> &str {
+        match self {
+            TemperatureSchedule::Linear => "linear",
+            TemperatureSchedule::Exponential => "exponential",
+            TemperatureSchedule::Logarithmic => "logarithmic",
+        }
+    }
+    // progressが0.0のときstart_temp、1.0のときend_tempになる
+    pub fn temperature(&self, start_temp: f64, end_temp: f64, progress: f64) -> f64 {
+        match self {
+            TemperatureSchedule::Linear => start_temp + (end_temp - start_temp) * progress,
+            TemperatureSchedule::Exponential => start_temp * (end_temp / start_temp).powf(progress),
+            TemperatureSchedule::Logarithmic => {
+                let decay = (1.0 + (std::f64::consts::E - 1.0) * progress).ln();
+                start_temp / (1.0 + (start_temp / end_temp - 1.0) * decay)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct AnnealingConfig {
+    schedule_: TemperatureSchedule,
+    start_temp_: f64,
+    end_temp_: f64,
+    time_threshold_: u64,
+    // 制限時間をこの数の区間に分け、区間の始めに温度をstart_temp_に戻す
+    restart_number_: usize,
+    // trueなら区間の始めにそれまでの最良解から再開し、falseなら初期解を作り直す
+    restart_from_best_: bool,
+    // この反復回数ごとに受理率を表示する。0なら表示しない
+    log_interval_: usize,
+}
+impl AnnealingConfig {
+    const DEFAULT: Self = Self {
+        schedule_: TemperatureSchedule::Exponential,
+        start_temp_: 500.0,
+        end_temp_: 10.0,
+        time_threshold_: 10,
+        restart_number_: 1,
+        restart_from_best_: true,
+        log_interval_: 0,
+    };
+}
+
+// 経過時間で温度を決める焼きなまし法
+fn simulatedAnnealing<P: LocalSearchProblem>(
+    problem: &P,
+    config: &AnnealingConfig,
+    rng: &mut rand::rngs::StdRng,
+) -> P::Solution {
+    let time_keeper = TimeKeeper::new(config.time_threshold_);
+    let mut now_solution = problem.initialSolution(rng);
+    let mut now_score = problem.score(&now_solution);
+    let mut best_score = now_score;
+    let mut best_solution = now_solution.clone();
+    let mut segment = 0;
+    let mut iteration = 0;
+    let mut accepted_number = 0;
+    let mut improved_number = 0;
+    loop {
+        let progress = time_keeper.progress();
+        if progress >= 1.0 {
+            break;
+        }
+        let segment_progress = progress * config.restart_number_ as f64;
+        if segment_progress as usize > segment {
+            segment = segment_progress as usize;
+            if config.restart_from_best_ {
+                now_solution = best_solution.clone();
+                now_score = best_score;
+            } else {
+                now_solution = problem.initialSolution(rng);
+                now_score = problem.score(&now_solution);
+            }
+        }
+        let temp = config.schedule_.temperature(
+            config.start_temp_,
+            config.end_temp_,
+            segment_progress.fract(),
+        );
+
+        let next_solution = problem.neighbour(&now_solution, rng);
+        let delta = problem.deltaScore(&now_solution, now_score, &next_solution);
+        if delta > 0 || (delta as f64 / temp).exp() > rng.gen::<f64>() {
+            now_score += delta;
+            now_solution = next_solution;
+            accepted_number += 1;
+            if delta > 0 {
+                improved_number += 1;
+            }
+            if now_score > best_score {
+                best_score = now_score;
+                best_solution = now_solution.clone();
+            }
+        }
+
+        iteration += 1;
+        if config.log_interval_ > 0 && iteration % config.log_interval_ == 0 {
+            println!(
+                "iteration {}\ttemp {:.2}\tscore {}\tbest {}\taccepted {:.3}\timproved {:.3}",
+                iteration,
+                temp,
+                now_score,
+                best_score,
+                accepted_number as f64 / config.log_interval_ as f64,
+                improved_number as f64 / config.log_interval_ as f64
+            );
+            accepted_number = 0;
+            improved_number = 0;
+        }
+    }
+    best_solution
+}
+
+// 02_SimulatedAnnealingのprintOptimalScoresで全探索して求めた、seed 0..100の盤面の最適な得点
+const OPTIMAL_SCORES: [ScoreType; 100] = [
+    97, 97, 85, 102, 97, 103, 97, 88, 88, 93, 91, 112, 100, 92, 106, 100, 96, 100, 90, 92, 93, 92,
+    94, 90, 87, 104, 87, 101, 85, 93, 85, 99, 95, 88, 66, 97, 95, 93, 95, 90, 98, 94, 86, 98, 88,
+    89, 84, 107, 94, 100, 117, 104, 88, 105, 96, 83, 104, 107, 98, 107, 99, 87, 99, 94, 101, 112,
+    92, 85, 102, 87, 103, 86, 85, 89, 90, 89, 106, 91, 96, 99, 93, 108, 84, 113, 76, 96, 111, 97,
+    85, 90, 91, 80, 84, 77, 99, 91, 93, 92, 85, 103,
+];
+
+// 設定ごとに平均得点と、最適解との差の平均、最適解に届いた盤面の数を表示する
+fn testAiScore(configs: &[(String, AnnealingConfig)], game_number: usize) {
+    let optimal_scores = &OPTIMAL_SCORES[..game_number];
+    for (name, config) in configs.iter() {
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0);
+        let mut score_mean = 0.0;
+        let mut gap_mean = 0.0;
+        let mut optimal_number = 0;
+        for i in 0..game_number {
+            let problem = CharacterPlacementProblem::new(&State::new(i), Box::new(Rerandomize));
+            let score = simulatedAnnealing(&problem, config, &mut rng).getScore(false);
+            score_mean += score as f64;
+            gap_mean += (optimal_scores[i] - score) as f64;
+            if score == optimal_scores[i] {
+                optimal_number += 1;
+            }
+        }
+        score_mean /= game_number as f64;
+        gap_mean /= game_number as f64;
+        println!(
+            "Score of {}:\t{}\tgap {}\toptimal {}/{}",
+            name, score_mean, gap_mean, optimal_number, game_number
+        );
+    }
+}
+
+fn main() {
+    let mut configs = Vec::new();
+    for schedule in [
+        TemperatureSchedule::Linear,
+        TemperatureSchedule::Exponential,
+        TemperatureSchedule::Logarithmic,
+    ] {
+        configs.push((
+            schedule.name().to_string(),
+            AnnealingConfig {
+                schedule_: schedule,
+                ..AnnealingConfig::DEFAULT
+            },
+        ));
+    }
+    configs.push((
+        "exponential, 4 reheats".to_string(),
+        AnnealingConfig {
+            restart_number_: 4,
+            ..AnnealingConfig::DEFAULT
+        },
+    ));
+    configs.push((
+        "exponential, 4 restarts".to_string(),
+        AnnealingConfig {
+            restart_number_: 4,
+            restart_from_best_: false,
+            ..AnnealingConfig::DEFAULT
+        },
+    ));
+    testAiScore(&configs, 10);
+
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0);
+    let problem = CharacterPlacementProblem::new(&State::new(0), Box::new(Rerandomize));
+    let config = AnnealingConfig {
+        log_interval_: 2000,
+        ..AnnealingConfig::DEFAULT
+    };
+    simulatedAnnealing(&problem, &config, &mut rng);
+}
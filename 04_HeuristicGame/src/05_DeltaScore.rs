@@ -0,0 +1,909 @@
+// 差分計算の速さは盤面の大きさ・ターン数・キャラクター数で大きく変わるので、
+// 同じコードを本来の問題と大きい問題の両方で動かせるよう、問題の大きさを引数にしたマクロにしている
+macro_rules! deltaScoreProblem {
+    ($h:expr, $w:expr, $end_turn:expr, $character_n:expr) => {
+        use std::cell::RefCell;
+        use std::time;
+
+        use rand;
+        use rand::prelude::*;
+
+        const H: i32 = $h;
+        const W: i32 = $w;
+        const END_TURN: usize = $end_turn;
+        const CHARACTER_N: usize = $character_n;
+        type ScoreType = i32;
+        const INF: ScoreType = 100000000;
+
+        #[derive(Debug, Default, Clone, Eq, PartialEq, Copy)]
+        struct Coord {
+            y_: i32,
+            x_: i32,
+        }
+        impl Coord {
+            pub fn new(y_: i32, x_: i32) -> Self {
+                Self { y_, x_ }
+            }
+        }
+        #[derive(Debug, Clone, Eq, PartialEq, Copy)]
+        struct AutoMoveMazeState {
+            points_: [[i32; W as usize]; H as usize],
+            turn_: i32,
+            pub characters_: [Coord; CHARACTER_N],
+            pub game_score_: i32,
+            pub evaluated_score_: ScoreType,
+        }
+        impl AutoMoveMazeState {
+            const dx: [i32; 4] = [1, -1, 0, 0];
+            const dy: [i32; 4] = [0, 0, 1, -1];
+
+            fn movePlayer(&mut self, character_id: usize) {
+                let character = &mut self.characters_[character_id];
+                let mut best_point = -INF;
+                let mut best_action_index = 0;
+                for action in 0..4 {
+                    let ty = character.y_ + Self::dy[action];
+                    let tx: i32 = character.x_ + Self::dx[action];
+                    if ty >= 0 && ty < H && tx >= 0 && tx < W {
+                        let point = self.points_[ty as usize][tx as usize];
+                        if point > best_point {
+                            best_point = point;
+                            best_action_index = action;
+                        }
+                    }
+                }
+                character.y_ += Self::dy[best_action_index];
+                character.x_ += Self::dx[best_action_index];
+            }
+            fn advance(&mut self) {
+                for character_id in 0..CHARACTER_N {
+                    self.movePlayer(character_id);
+                }
+                for character in self.characters_.iter() {
+                    let point = &mut self.points_[character.y_ as usize][character.x_ as usize];
+                    self.game_score_ += *point;
+                    *point = 0;
+                }
+                self.turn_ += 1;
+            }
+
+            pub fn new(seed: usize) -> Self {
+                let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(seed as u64);
+                let mut maze_state = AutoMoveMazeState {
+                    points_: [[0 as i32; W as usize]; H as usize],
+                    turn_: 0,
+                    game_score_: 0,
+                    evaluated_score_: 0,
+                    characters_: [Coord::new(0, 0); CHARACTER_N],
+                };
+                for y in 0..H {
+                    for x in 0..W {
+                        maze_state.points_[y as usize][x as usize] = rng.gen_range(0..9) + 1;
+                    }
+                }
+                maze_state
+            }
+
+            pub fn setCharacter(&mut self, character_id: usize, y: i32, x: i32) {
+                self.characters_[character_id].y_ = y;
+                self.characters_[character_id].x_ = x;
+            }
+
+            pub fn isDone(&self) -> bool {
+                return self.turn_ == END_TURN as i32;
+            }
+            pub fn toString(&self) -> String {
+                let mut ss = String::new();
+                ss += format!("turn:\t{}\n", self.turn_).as_str();
+                ss += format!("score:\t{}\n", self.game_score_).as_str();
+                let mut board_chars = [['.'; W as usize]; H as usize];
+                for h in 0..H {
+                    for w in 0..W {
+                        let mut is_written = false;
+                        for character in self.characters_ {
+                            if character.y_ == h && character.x_ == w {
+                                ss += "@";
+                                is_written = true;
+                                break;
+                            }
+                            board_chars[character.y_ as usize][character.x_ as usize] = '@';
+                        }
+                        if !is_written {
+                            if self.points_[h as usize][w as usize] > 0 {
+                                ss += self.points_[h as usize][w as usize].to_string().as_str();
+                            } else {
+                                ss += ".";
+                            }
+                        }
+                    }
+                    ss += "\n";
+                }
+                ss
+            }
+
+            pub fn getScore(&self, is_print: bool) -> ScoreType {
+                let mut tmp_state = self.clone();
+                for character in self.characters_ {
+                    let point =
+                        &mut tmp_state.points_[character.y_ as usize][character.x_ as usize];
+                    *point = 0;
+                }
+                while !tmp_state.isDone() {
+                    tmp_state.advance();
+                    if is_print {
+                        println!("{}", tmp_state.toString());
+                    }
+                }
+                tmp_state.game_score_
+            }
+        }
+
+        type State = AutoMoveMazeState;
+        fn randomAction(state: &State) -> State {
+            let mut rng = rand::thread_rng();
+            let mut now_state = state.clone();
+            for character_id in 0..CHARACTER_N {
+                let y = rng.gen_range(0..H);
+                let x = rng.gen_range(0..W);
+                now_state.setCharacter(character_id, y, x);
+            }
+            now_state
+        }
+        // 局所探索で解く問題
+        // deltaScoreは近傍解の得点とnow_scoreとの差を返す。差分計算できる問題は上書きして高速化する
+        trait LocalSearchProblem {
+            type Solution: Clone;
+            fn initialSolution(&self, rng: &mut rand::rngs::StdRng) -> Self::Solution;
+            fn neighbour(
+                &self,
+                solution: &Self::Solution,
+                rng: &mut rand::rngs::StdRng,
+            ) -> Self::Solution;
+            fn score(&self, solution: &Self::Solution) -> ScoreType;
+            fn deltaScore(
+                &self,
+                _now_solution: &Self::Solution,
+                now_score: ScoreType,
+                next_solution: &Self::Solution,
+            ) -> ScoreType {
+                self.score(next_solution) - now_score
+            }
+        }
+
+        // 近傍の作り方
+        trait Neighbourhood {
+            fn apply(&self, state: &State, rng: &mut rand::rngs::StdRng) -> State;
+        }
+
+        // キャラクターを1人選び、上下左右のいずれかに1マス動かす
+        struct MoveOneCell;
+        impl Neighbourhood for MoveOneCell {
+            fn apply(&self, state: &State, rng: &mut rand::rngs::StdRng) -> State {
+                let mut next_state = state.clone();
+                let character_id = rng.gen_range(0..CHARACTER_N);
+                let character = next_state.characters_[character_id];
+                loop {
+                    let action = rng.gen_range(0..4);
+                    let ty = character.y_ + State::dy[action];
+                    let tx = character.x_ + State::dx[action];
+                    if ty >= 0 && ty < H && tx >= 0 && tx < W {
+                        next_state.setCharacter(character_id, ty, tx);
+                        return next_state;
+                    }
+                }
+            }
+        }
+
+        // キャラクターを2人選び、それぞれ上下左右のいずれかに1マス動かす
+        // 1人ずつ動かす近傍では、途中で一度得点が下がる配置を通らないと届かない配置にも1回で移れる
+        struct MoveTwoCharacters;
+        impl Neighbourhood for MoveTwoCharacters {
+            fn apply(&self, state: &State, rng: &mut rand::rngs::StdRng) -> State {
+                let mut next_state = state.clone();
+                let character_id0 = rng.gen_range(0..CHARACTER_N);
+                let character_id1 = (character_id0 + rng.gen_range(1..CHARACTER_N)) % CHARACTER_N;
+                for character_id in [character_id0, character_id1] {
+                    let character = next_state.characters_[character_id];
+                    loop {
+                        let action = rng.gen_range(0..4);
+                        let ty = character.y_ + State::dy[action];
+                        let tx = character.x_ + State::dx[action];
+                        if ty >= 0 && ty < H && tx >= 0 && tx < W {
+                            next_state.setCharacter(character_id, ty, tx);
+                            break;
+                        }
+                    }
+                }
+                next_state
+            }
+        }
+
+        // キャラクターを1人選び、盤面全体からランダムに置き直す
+        struct Rerandomize;
+        impl Neighbourhood for Rerandomize {
+            fn apply(&self, state: &State, rng: &mut rand::rngs::StdRng) -> State {
+                let mut next_state = state.clone();
+                let character_id = rng.gen_range(0..CHARACTER_N);
+                next_state.setCharacter(character_id, rng.gen_range(0..H), rng.gen_range(0..W));
+                next_state
+            }
+        }
+
+        // 重みに比例した確率で近傍を選んで使う
+        struct WeightedNeighbourhood {
+            neighbourhoods_: Vec<(Box<dyn Neighbourhood>, f64)>,
+        }
+        impl WeightedNeighbourhood {
+            pub fn new(neighbourhoods: Vec<(Box<dyn Neighbourhood>, f64)>) -> Self {
+                Self {
+                    neighbourhoods_: neighbourhoods,
+                }
+            }
+        }
+        impl Neighbourhood for WeightedNeighbourhood {
+            fn apply(&self, state: &State, rng: &mut rand::rngs::StdRng) -> State {
+                let weight_sum: f64 = self.neighbourhoods_.iter().map(|(_, weight)| weight).sum();
+                let mut r = rng.gen::<f64>() * weight_sum;
+                for (neighbourhood, weight) in self.neighbourhoods_.iter() {
+                    if r < *weight {
+                        return neighbourhood.apply(state, rng);
+                    }
+                    r -= weight;
+                }
+                self.neighbourhoods_.last().unwrap().0.apply(state, rng)
+            }
+        }
+
+        // キャラクターの初期配置を決める問題
+        struct CharacterPlacementProblem {
+            state_: State,
+            neighbourhood_: Box<dyn Neighbourhood>,
+        }
+        impl CharacterPlacementProblem {
+            pub fn new(state: &State, neighbourhood: Box<dyn Neighbourhood>) -> Self {
+                Self {
+                    state_: state.clone(),
+                    neighbourhood_: neighbourhood,
+                }
+            }
+        }
+        impl LocalSearchProblem for CharacterPlacementProblem {
+            type Solution = State;
+            fn initialSolution(&self, rng: &mut rand::rngs::StdRng) -> State {
+                let mut now_state = self.state_.clone();
+                for character_id in 0..CHARACTER_N {
+                    now_state.setCharacter(character_id, rng.gen_range(0..H), rng.gen_range(0..W));
+                }
+                now_state
+            }
+            fn neighbour(&self, solution: &State, rng: &mut rand::rngs::StdRng) -> State {
+                self.neighbourhood_.apply(solution, rng)
+            }
+            fn score(&self, solution: &State) -> ScoreType {
+                solution.getScore(false)
+            }
+        }
+
+        const CELL_N: usize = (H * W) as usize;
+        const NEVER: usize = usize::MAX;
+
+        fn cellIndex(coord: &Coord) -> usize {
+            (coord.y_ * W + coord.x_) as usize
+        }
+
+        // DeltaScoreEngineがマスごとに持つ情報。ランダムに参照するので1か所にまとめておく
+        #[derive(Debug, Clone)]
+        struct CellInfo {
+            point_: ScoreType,
+            // マスの得点が0になったターン。初期位置は0、誰も訪れないマスはNEVER
+            clear_turn_: usize,
+            base_clear_turn_: usize,
+            // キャラクターがこのマスにいるターン
+            visits_: Vec<usize>,
+            // 基準の軌跡でこのマスを移動先の候補として見る(ターン, キャラクター)
+            // commitで軌跡が変わっても古い要素は消さず、使うときに基準の軌跡と照らし合わせる
+            watchers_: Vec<(usize, usize)>,
+        }
+
+        // キャラクターの位置を変えたときの得点を、影響を受ける部分だけ再シミュレーションして求める
+        // trajectories_にキャラクターごとの各ターンの位置、cells_にマスごとの訪問ターンを持つ。
+        // 位置が変わったキャラクター(diverged)だけを毎ターン動かし直し、
+        // それ以外のキャラクターは見ているマスの得点の有無が変わったターンだけ動きを確かめ直す
+        struct DeltaScoreEngine {
+            trajectories_: [[Coord; END_TURN + 1]; CHARACTER_N],
+            cells_: Vec<CellInfo>,
+            // watchers_の要素数の合計。古い要素が溜まりすぎたら作り直す
+            watcher_n_: usize,
+            // score_は直前にevaluateした配置の得点、base_score_は基準の配置の得点
+            score_: ScoreType,
+            base_score_: ScoreType,
+            // 直前のevaluateの結果を戻さずに持っているか
+            has_candidate_: bool,
+            next_trajectories_: [[Coord; END_TURN + 1]; CHARACTER_N],
+            pending_: Vec<Vec<usize>>,
+            count_log_: Vec<(usize, usize, i32)>,
+            changed_cells_: Vec<usize>,
+            diverged_ids_: Vec<usize>,
+            last_changes_: Vec<(usize, Coord)>,
+        }
+        impl DeltaScoreEngine {
+            pub fn new(state: &State) -> Self {
+                let mut engine = Self {
+                    trajectories_: [[Coord::new(0, 0); END_TURN + 1]; CHARACTER_N],
+                    cells_: Vec::with_capacity(CELL_N),
+                    watcher_n_: 0,
+                    score_: 0,
+                    base_score_: 0,
+                    has_candidate_: false,
+                    next_trajectories_: [[Coord::new(0, 0); END_TURN + 1]; CHARACTER_N],
+                    pending_: vec![Vec::new(); END_TURN + 1],
+                    count_log_: Vec::new(),
+                    changed_cells_: Vec::new(),
+                    diverged_ids_: Vec::new(),
+                    last_changes_: Vec::new(),
+                };
+                for y in 0..H as usize {
+                    for x in 0..W as usize {
+                        engine.cells_.push(CellInfo {
+                            point_: state.points_[y][x],
+                            clear_turn_: NEVER,
+                            base_clear_turn_: NEVER,
+                            visits_: Vec::new(),
+                            watchers_: Vec::new(),
+                        });
+                    }
+                }
+                for character_id in 0..CHARACTER_N {
+                    let character = state.characters_[character_id];
+                    engine.trajectories_[character_id][0] = character;
+                    engine.addVisit(cellIndex(&character), 0, 1, 0);
+                }
+                for turn in 1..=END_TURN {
+                    for character_id in 0..CHARACTER_N {
+                        let now = engine.trajectories_[character_id][turn - 1];
+                        let next = engine.greedyMove(&now, turn);
+                        engine.trajectories_[character_id][turn] = next;
+                        engine.addVisit(cellIndex(&next), turn, 1, turn);
+                        engine.addWatcher(&now, turn, character_id);
+                    }
+                }
+                for cell_info in engine.cells_.iter_mut() {
+                    cell_info.base_clear_turn_ = cell_info.clear_turn_;
+                }
+                engine.base_score_ = engine.score_;
+                engine.next_trajectories_ = engine.trajectories_;
+                engine.count_log_.clear();
+                engine.changed_cells_.clear();
+                engine
+            }
+
+            pub fn score(&self) -> ScoreType {
+                self.base_score_
+            }
+
+            pub fn characters(&self) -> [Coord; CHARACTER_N] {
+                let mut characters = [Coord::new(0, 0); CHARACTER_N];
+                for character_id in 0..CHARACTER_N {
+                    characters[character_id] = self.trajectories_[character_id][0];
+                }
+                characters
+            }
+
+            // 直前にevaluateした配置
+            pub fn lastCandidate(&self) -> [Coord; CHARACTER_N] {
+                let mut characters = self.characters();
+                for (character_id, coord) in self.last_changes_.iter() {
+                    characters[*character_id] = *coord;
+                }
+                characters
+            }
+
+            // キャラクターを動かした後の得点を返す。基準の配置は変えない
+            // 結果は次のevaluateまで持っておき、commitLastでそのまま基準にできるようにする
+            pub fn evaluate(&mut self, changes: &[(usize, Coord)]) -> ScoreType {
+                self.rollback();
+                let score = self.simulate(changes);
+                self.has_candidate_ = true;
+                self.last_changes_.extend_from_slice(changes);
+                score
+            }
+
+            // キャラクターを動かした配置を新しい基準にする
+            pub fn commit(&mut self, changes: &[(usize, Coord)]) {
+                self.evaluate(changes);
+                self.commitLast();
+            }
+
+            // 直前にevaluateした配置を新しい基準にする
+            pub fn commitLast(&mut self) {
+                if !self.has_candidate_ {
+                    return;
+                }
+                for i in 0..self.diverged_ids_.len() {
+                    let character_id = self.diverged_ids_[i];
+                    for turn in 1..=END_TURN {
+                        let from = self.trajectories_[character_id][turn - 1];
+                        let to = self.next_trajectories_[character_id][turn - 1];
+                        if from != to {
+                            self.addWatcher(&to, turn, character_id);
+                        }
+                    }
+                    self.trajectories_[character_id] = self.next_trajectories_[character_id];
+                }
+                if self.watcher_n_ > 2 * CHARACTER_N * END_TURN * 4 {
+                    self.rebuildWatchers();
+                }
+                for &cell in self.changed_cells_.iter() {
+                    self.cells_[cell].base_clear_turn_ = self.cells_[cell].clear_turn_;
+                }
+                self.base_score_ = self.score_;
+                self.has_candidate_ = false;
+                self.last_changes_.clear();
+            }
+
+            // 直前のevaluateで変えた内部の状態を基準の配置に戻す
+            fn rollback(&mut self) {
+                if !self.has_candidate_ {
+                    return;
+                }
+                for &(cell, turn, delta) in self.count_log_.iter().rev() {
+                    if delta > 0 {
+                        Self::removeTurn(&mut self.cells_[cell].visits_, turn);
+                    } else {
+                        self.cells_[cell].visits_.push(turn);
+                    }
+                }
+                for &cell in self.changed_cells_.iter() {
+                    self.cells_[cell].clear_turn_ = self.cells_[cell].base_clear_turn_;
+                }
+                for &character_id in self.diverged_ids_.iter() {
+                    self.next_trajectories_[character_id] = self.trajectories_[character_id];
+                }
+                self.score_ = self.base_score_;
+                self.has_candidate_ = false;
+                self.last_changes_.clear();
+            }
+
+            // turnターン目にcharacterの位置から移動先の候補を見るキャラクターとして登録する
+            fn addWatcher(&mut self, character: &Coord, turn: usize, character_id: usize) {
+                for action in 0..4 {
+                    let ty = character.y_ + State::dy[action];
+                    let tx = character.x_ + State::dx[action];
+                    if ty >= 0 && ty < H && tx >= 0 && tx < W {
+                        self.cells_[(ty * W + tx) as usize]
+                            .watchers_
+                            .push((turn, character_id));
+                        self.watcher_n_ += 1;
+                    }
+                }
+            }
+
+            fn rebuildWatchers(&mut self) {
+                for cell_info in self.cells_.iter_mut() {
+                    cell_info.watchers_.clear();
+                }
+                self.watcher_n_ = 0;
+                for character_id in 0..CHARACTER_N {
+                    for turn in 1..=END_TURN {
+                        let character = self.trajectories_[character_id][turn - 1];
+                        self.addWatcher(&character, turn, character_id);
+                    }
+                }
+            }
+
+            fn contribution(&self, cell: usize, clear_turn: usize) -> ScoreType {
+                if clear_turn == 0 || clear_turn == NEVER {
+                    0
+                } else {
+                    self.cells_[cell].point_
+                }
+            }
+
+            fn removeTurn(visits: &mut Vec<usize>, turn: usize) {
+                let index = visits.iter().position(|&t| t == turn).unwrap();
+                visits.swap_remove(index);
+            }
+
+            // now_turnターン目の処理中にcellへの訪問を増減する
+            fn addVisit(&mut self, cell: usize, turn: usize, delta: i32, now_turn: usize) {
+                self.count_log_.push((cell, turn, delta));
+                let old_clear_turn = self.cells_[cell].clear_turn_;
+                let clear_turn = if delta > 0 {
+                    self.cells_[cell].visits_.push(turn);
+                    old_clear_turn.min(turn)
+                } else {
+                    Self::removeTurn(&mut self.cells_[cell].visits_, turn);
+                    if turn == old_clear_turn {
+                        // 1マスを訪れる回数は少ないので、最小値は毎回数え直す
+                        self.cells_[cell]
+                            .visits_
+                            .iter()
+                            .copied()
+                            .min()
+                            .unwrap_or(NEVER)
+                    } else {
+                        old_clear_turn
+                    }
+                };
+                if clear_turn == old_clear_turn {
+                    return;
+                }
+                self.score_ +=
+                    self.contribution(cell, clear_turn) - self.contribution(cell, old_clear_turn);
+                self.cells_[cell].clear_turn_ = clear_turn;
+                self.changed_cells_.push(cell);
+                // 得点の有無が変わったターンにこのマスを見ていたキャラクターを起こす
+                let low = clear_turn.min(old_clear_turn).max(now_turn);
+                let high = clear_turn.max(old_clear_turn);
+                let y = cell as i32 / W;
+                let x = cell as i32 % W;
+                for &(watch_turn, character_id) in self.cells_[cell].watchers_.iter() {
+                    if watch_turn <= low || watch_turn > high {
+                        continue;
+                    }
+                    let character = &self.trajectories_[character_id][watch_turn - 1];
+                    if (character.y_ - y).abs() + (character.x_ - x).abs() == 1 {
+                        self.pending_[watch_turn].push(character_id);
+                    }
+                }
+            }
+
+            // AutoMoveMazeState::movePlayerと同じ規則で、turnターン目の移動先を返す
+            fn greedyMove(&self, character: &Coord, turn: usize) -> Coord {
+                let mut best_point = -INF;
+                let mut best_action_index = 0;
+                for action in 0..4 {
+                    let ty = character.y_ + State::dy[action];
+                    let tx = character.x_ + State::dx[action];
+                    if ty >= 0 && ty < H && tx >= 0 && tx < W {
+                        let cell_info = &self.cells_[(ty * W + tx) as usize];
+                        let point = if cell_info.clear_turn_ < turn {
+                            0
+                        } else {
+                            cell_info.point_
+                        };
+                        if point > best_point {
+                            best_point = point;
+                            best_action_index = action;
+                        }
+                    }
+                }
+                Coord::new(
+                    character.y_ + State::dy[best_action_index],
+                    character.x_ + State::dx[best_action_index],
+                )
+            }
+
+            fn moveVisit(&mut self, from: &Coord, to: &Coord, turn: usize) {
+                if from != to {
+                    self.addVisit(cellIndex(from), turn, -1, turn);
+                    self.addVisit(cellIndex(to), turn, 1, turn);
+                }
+            }
+
+            // turnターン目の移動をやり直し、基準の軌跡から外れたかを返す
+            fn stepCharacter(&mut self, character_id: usize, turn: usize) -> bool {
+                let now = self.next_trajectories_[character_id][turn - 1];
+                let next = self.greedyMove(&now, turn);
+                self.next_trajectories_[character_id][turn] = next;
+                let base = self.trajectories_[character_id][turn];
+                self.moveVisit(&base, &next, turn);
+                next != base
+            }
+
+            fn simulate(&mut self, changes: &[(usize, Coord)]) -> ScoreType {
+                self.count_log_.clear();
+                self.changed_cells_.clear();
+                self.diverged_ids_.clear();
+                // next_trajectories_は位置が変わったキャラクター以外trajectories_と同じに保つ
+                let mut is_diverged = [false; CHARACTER_N];
+                for &(character_id, coord) in changes.iter() {
+                    is_diverged[character_id] = true;
+                    self.diverged_ids_.push(character_id);
+                    self.next_trajectories_[character_id][0] = coord;
+                }
+                for &(character_id, _) in changes.iter() {
+                    let from = self.trajectories_[character_id][0];
+                    let to = self.next_trajectories_[character_id][0];
+                    self.moveVisit(&from, &to, 0);
+                }
+                let mut processed_turn = [0; CHARACTER_N];
+                for turn in 1..=END_TURN {
+                    // 位置が変わったキャラクターは毎ターン動かし直す
+                    for i in 0..self.diverged_ids_.len() {
+                        let character_id = self.diverged_ids_[i];
+                        processed_turn[character_id] = turn;
+                        self.stepCharacter(character_id, turn);
+                    }
+                    // 起こされたキャラクターは動きが変わったときだけ位置が変わったキャラクターに加える
+                    for i in 0..self.pending_[turn].len() {
+                        let character_id = self.pending_[turn][i];
+                        if processed_turn[character_id] == turn {
+                            continue;
+                        }
+                        processed_turn[character_id] = turn;
+                        if self.stepCharacter(character_id, turn) && !is_diverged[character_id] {
+                            is_diverged[character_id] = true;
+                            self.diverged_ids_.push(character_id);
+                        }
+                    }
+                    self.pending_[turn].clear();
+                }
+                self.score_
+            }
+        }
+
+        fn characterChanges(now_state: &State, next_state: &State) -> Vec<(usize, Coord)> {
+            (0..CHARACTER_N)
+                .filter(|&character_id| {
+                    now_state.characters_[character_id] != next_state.characters_[character_id]
+                })
+                .map(|character_id| (character_id, next_state.characters_[character_id]))
+                .collect()
+        }
+
+        // CharacterPlacementProblemと同じ問題を、DeltaScoreEngineで差分計算して解く
+        struct DeltaCharacterPlacementProblem {
+            base_: CharacterPlacementProblem,
+            engine_: RefCell<DeltaScoreEngine>,
+        }
+        impl DeltaCharacterPlacementProblem {
+            pub fn new(state: &State, neighbourhood: Box<dyn Neighbourhood>) -> Self {
+                Self {
+                    base_: CharacterPlacementProblem::new(state, neighbourhood),
+                    engine_: RefCell::new(DeltaScoreEngine::new(state)),
+                }
+            }
+        }
+        impl LocalSearchProblem for DeltaCharacterPlacementProblem {
+            type Solution = State;
+            fn initialSolution(&self, rng: &mut rand::rngs::StdRng) -> State {
+                self.base_.initialSolution(rng)
+            }
+            fn neighbour(&self, solution: &State, rng: &mut rand::rngs::StdRng) -> State {
+                self.base_.neighbour(solution, rng)
+            }
+            fn score(&self, solution: &State) -> ScoreType {
+                let mut engine = self.engine_.borrow_mut();
+                *engine = DeltaScoreEngine::new(solution);
+                engine.score()
+            }
+            fn deltaScore(
+                &self,
+                now_solution: &State,
+                now_score: ScoreType,
+                next_solution: &State,
+            ) -> ScoreType {
+                let mut engine = self.engine_.borrow_mut();
+                if engine.characters() != now_solution.characters_ {
+                    // 直前の近傍が受理されたときは差分だけ反映する
+                    if engine.lastCandidate() == now_solution.characters_ {
+                        engine.commitLast();
+                    } else {
+                        *engine = DeltaScoreEngine::new(now_solution);
+                    }
+                }
+                engine.evaluate(&characterChanges(now_solution, next_solution)) - now_score
+            }
+        }
+
+        fn hillClimb<P: LocalSearchProblem>(
+            problem: &P,
+            number: usize,
+            rng: &mut rand::rngs::StdRng,
+        ) -> P::Solution {
+            let mut now_solution = problem.initialSolution(rng);
+            let mut now_score = problem.score(&now_solution);
+            for _ in 0..number {
+                let next_solution = problem.neighbour(&now_solution, rng);
+                let delta = problem.deltaScore(&now_solution, now_score, &next_solution);
+                if delta > 0 {
+                    now_score += delta;
+                    now_solution = next_solution;
+                }
+            }
+            now_solution
+        }
+
+        fn simulatedAnnealing<P: LocalSearchProblem>(
+            problem: &P,
+            number: usize,
+            start_temp: f64,
+            end_temp: f64,
+            rng: &mut rand::rngs::StdRng,
+        ) -> P::Solution {
+            let mut now_solution = problem.initialSolution(rng);
+            let mut now_score = problem.score(&now_solution);
+            let mut best_score = now_score;
+            let mut best_solution = now_solution.clone();
+            for i in 0..number {
+                let next_solution = problem.neighbour(&now_solution, rng);
+                let delta = problem.deltaScore(&now_solution, now_score, &next_solution);
+                let temp = start_temp + (end_temp - start_temp) * (i as f64 / number as f64);
+                let probability = (delta as f64 / temp).exp();
+                if delta > 0 || probability > rng.gen::<f64>() {
+                    now_score += delta;
+                    now_solution = next_solution;
+                    if now_score > best_score {
+                        best_score = now_score;
+                        best_solution = now_solution.clone();
+                    }
+                }
+            }
+            best_solution
+        }
+
+        fn mixedNeighbourhood() -> Box<dyn Neighbourhood> {
+            Box::new(WeightedNeighbourhood::new(vec![
+                (Box::new(MoveOneCell), 6.0),
+                (Box::new(MoveTwoCharacters), 1.0),
+                (Box::new(Rerandomize), 3.0),
+            ]))
+        }
+
+        // 差分計算の得点がgetScoreと一致するか確かめる
+        fn testDeltaScore(game_number: usize, step_number: usize) {
+            let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0);
+            let neighbourhood = mixedNeighbourhood();
+            let mut mismatch_number = 0;
+            for i in 0..game_number {
+                let mut now_state = randomAction(&State::new(i));
+                let mut engine = DeltaScoreEngine::new(&now_state);
+                if engine.score() != now_state.getScore(false) {
+                    mismatch_number += 1;
+                }
+                for _ in 0..step_number {
+                    let next_state = neighbourhood.apply(&now_state, &mut rng);
+                    let changes = characterChanges(&now_state, &next_state);
+                    if engine.evaluate(&changes) != next_state.getScore(false) {
+                        mismatch_number += 1;
+                    }
+                    if rng.gen::<bool>() {
+                        engine.commit(&changes);
+                        now_state = next_state;
+                        if engine.score() != now_state.getScore(false) {
+                            mismatch_number += 1;
+                        }
+                    }
+                }
+            }
+            println!(
+                "delta score mismatches:\t{} / {}",
+                mismatch_number,
+                game_number * step_number
+            );
+        }
+
+        // 1秒あたりに評価できる近傍の数を比べる
+        fn testEvaluationSpeed(evaluation_number: usize) {
+            let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0);
+            let neighbourhood = mixedNeighbourhood();
+            let now_state = randomAction(&State::new(0));
+            // 盤面ごと持つと大きすぎるので、近傍はキャラクターの配置だけ持っておく
+            let candidates: Vec<[Coord; CHARACTER_N]> = (0..evaluation_number)
+                .map(|_| neighbourhood.apply(&now_state, &mut rng).characters_)
+                .collect();
+            let mut next_state = now_state;
+
+            let start_time = time::Instant::now();
+            let mut full_sum = 0;
+            for characters in candidates.iter() {
+                next_state.characters_ = *characters;
+                full_sum += next_state.getScore(false);
+            }
+            let full_time = start_time.elapsed().as_secs_f64();
+
+            let start_time = time::Instant::now();
+            let mut engine = DeltaScoreEngine::new(&now_state);
+            let mut delta_sum = 0;
+            for characters in candidates.iter() {
+                next_state.characters_ = *characters;
+                delta_sum += engine.evaluate(&characterChanges(&now_state, &next_state));
+            }
+            let delta_time = start_time.elapsed().as_secs_f64();
+
+            assert_eq!(full_sum, delta_sum);
+            println!(
+                "getScore:\t{:.0} evaluations/sec",
+                evaluation_number as f64 / full_time
+            );
+            println!(
+                "DeltaScoreEngine:\t{:.0} evaluations/sec",
+                evaluation_number as f64 / delta_time
+            );
+        }
+
+        // 同じ乱数で山登り法・焼きなまし法を動かし、結果が同じで速くなっているか確かめる
+        // 受理された近傍はDeltaScoreEngineの基準に反映する必要があるので、受理が多い焼きなまし法の初期は差が縮む
+        fn testAiScore(game_number: usize, number: usize) {
+            for (name, use_annealing) in [("hillClimb", false), ("simulatedAnnealing", true)] {
+                for use_delta in [false, true] {
+                    let mut score_mean = 0.0;
+                    let mut elapsed = 0.0;
+                    for i in 0..game_number {
+                        let mut rng: rand::rngs::StdRng =
+                            rand::SeedableRng::seed_from_u64(i as u64);
+                        let start_time = time::Instant::now();
+                        let state = match (use_annealing, use_delta) {
+                            (false, false) => hillClimb(
+                                &CharacterPlacementProblem::new(
+                                    &State::new(i),
+                                    mixedNeighbourhood(),
+                                ),
+                                number,
+                                &mut rng,
+                            ),
+                            (false, true) => hillClimb(
+                                &DeltaCharacterPlacementProblem::new(
+                                    &State::new(i),
+                                    mixedNeighbourhood(),
+                                ),
+                                number,
+                                &mut rng,
+                            ),
+                            (true, false) => simulatedAnnealing(
+                                &CharacterPlacementProblem::new(
+                                    &State::new(i),
+                                    mixedNeighbourhood(),
+                                ),
+                                number,
+                                500.0,
+                                10.0,
+                                &mut rng,
+                            ),
+                            (true, true) => simulatedAnnealing(
+                                &DeltaCharacterPlacementProblem::new(
+                                    &State::new(i),
+                                    mixedNeighbourhood(),
+                                ),
+                                number,
+                                500.0,
+                                10.0,
+                                &mut rng,
+                            ),
+                        };
+                        elapsed += start_time.elapsed().as_secs_f64();
+                        score_mean += state.getScore(false) as f64;
+                    }
+                    println!(
+                        "{} {}:\tscore {}\t{:.3}s",
+                        name,
+                        if use_delta {
+                            "DeltaScoreEngine"
+                        } else {
+                            "getScore"
+                        },
+                        score_mean / game_number as f64,
+                        elapsed
+                    );
+                }
+            }
+        }
+
+        pub fn run() {
+            testDeltaScore(100, 1000);
+            testEvaluationSpeed(100000);
+            testAiScore(3, 100000);
+        }
+    };
+}
+
+// 01_HillClimb〜04_Annealerと同じ、本来の問題の大きさ
+// シミュレーション全体が5ターン×3人と軽いので、差分計算は管理の手間が上回ってgetScoreより遅くなる
+mod original {
+    deltaScoreProblem!(5, 5, 5, 3);
+}
+
+// 差分計算の効果が分かるよう、他のファイルより大きい盤面にキャラクターを多く置いている。
+// 盤面が狭かったりターン数が多かったりするとキャラクター同士の軌跡が頻繁に交わり、
+// 1人動かすだけでほぼ全員を再シミュレーションすることになって差分計算の意味が薄れる
+mod large {
+    deltaScoreProblem!(100, 100, 50, 50);
+}
+
+fn main() {
+    println!("original problem (5x5, 5 turns, 3 characters)");
+    original::run();
+    println!("large problem (100x100, 50 turns, 50 characters)");
+    large::run();
+}
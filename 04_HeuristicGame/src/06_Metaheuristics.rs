@@ -0,0 +1,590 @@
+use rand;
+use rand::prelude::*;
+
+const H: i32 = 5;
+const W: i32 = 5;
+const END_TURN: usize = 5;
+const CHARACTER_N: usize = 3;
+type ScoreType = i32;
+const INF: ScoreType = 100000000;
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Copy, Hash)]
+struct Coord {
+    y_: i32,
+    x_: i32,
+}
+impl Coord {
+    pub fn new(y_: i32, x_: i32) -> Self {
+        Self { y_, x_ }
+    }
+}
+#[derive(Debug, Default, Clone, Eq, PartialEq, Copy)]
+struct AutoMoveMazeState {
+    points_: [[i32; W as usize]; H as usize],
+    turn_: i32,
+    pub characters_: [Coord; CHARACTER_N],
+    pub game_score_: i32,
+    pub evaluated_score_: ScoreType,
+}
+impl AutoMoveMazeState {
+    const dx: [i32; 4] = [1, -1, 0, 0];
+    const dy: [i32; 4] = [0, 0, 1, -1];
+
+    fn movePlayer(&mut self, character_id: usize) {
+        let character = &mut self.characters_[character_id];
+        let mut best_point = -INF;
+        let mut best_action_index = 0;
+        for action in 0..4 {
+            let ty = character.y_ + Self::dy[action];
+            let tx: i32 = character.x_ + Self::dx[action];
+            if ty >= 0 && ty < H && tx >= 0 && tx < W {
+                let point = self.points_[ty as usize][tx as usize];
+                if point > best_point {
+                    best_point = point;
+                    best_action_index = action;
+                }
+            }
+        }
+        character.y_ += Self::dy[best_action_index];
+        character.x_ += Self::dx[best_action_index];
+    }
+    fn advance(&mut self) {
+        for character_id in 0..CHARACTER_N {
+            self.movePlayer(character_id);
+        }
+        for character in self.characters_.iter() {
+            let point = &mut self.points_[character.y_ as usize][character.x_ as usize];
+            self.game_score_ += *point;
+            *point = 0;
+        }
+        self.turn_ += 1;
+    }
+
+    pub fn new(seed: usize) -> Self {
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(seed as u64);
+        let mut maze_state = AutoMoveMazeState {
+            points_: [[0 as i32; W as usize]; H as usize],
+            turn_: 0,
+            game_score_: 0,
+            evaluated_score_: 0,
+            characters_: [Coord::new(0, 0); CHARACTER_N],
+        };
+        for y in 0..H {
+            for x in 0..W {
+                maze_state.points_[y as usize][x as usize] = rng.gen_range(0..9) + 1;
+            }
+        }
+        maze_state
+    }
+
+    pub fn setCharacter(&mut self, character_id: usize, y: i32, x: i32) {
+        self.characters_[character_id].y_ = y;
+        self.characters_[character_id].x_ = x;
+    }
+
+    pub fn isDone(&self) -> bool {
+        return self.turn_ == END_TURN as i32;
+    }
+    pub fn toString(&self) -> String {
+        let mut ss = String::new();
+        ss += format!("turn:\t{}\n", self.turn_).as_str();
+        ss += format!("score:\t{}\n", self.game_score_).as_str();
+        let mut board_chars = [['.'; W as usize]; H as usize];
+        for h in 0..H {
+            for w in 0..W {
+                let mut is_written = false;
+                for character in self.characters_ {
+                    if character.y_ == h && character.x_ == w {
+                        ss += "@";
+                        is_written = true;
+                        break;
+                    }
+                    board_chars[character.y_ as usize][character.x_ as usize] = '@';
+                }
+                if !is_written {
+                    if self.points_[h as usize][w as usize] > 0 {
+                        ss += self.points_[h as usize][w as usize].to_string().as_str();
+                    } else {
+                        ss += ".";
+                    }
+                }
+            }
+            ss += "\n";
+        }
+        ss
+    }
+
+    pub fn getScore(&self, is_print: bool) -> ScoreType {
+        let mut tmp_state = self.clone();
+        for character in self.characters_ {
+            let point = &mut tmp_state.points_[character.y_ as usize][character.x_ as usize];
+            *point = 0;
+        }
+        while !tmp_state.isDone() {
+            tmp_state.advance();
+            if is_print {
+                println!("{}", tmp_state.toString());
+            }
+        }
+        tmp_state.game_score_
+    }
+}
+
+type State = AutoMoveMazeState;
+// 局所探索で解く問題
+// deltaScoreは近傍解の得点とnow_scoreとの差を返す。差分計算できる問題は上書きして高速化する
+trait LocalSearchProblem {
+    type Solution: Clone;
+    fn initialSolution(&self, rng: &mut rand::rngs::StdRng) -> Self::Solution;
+    fn neighbour(&self, solution: &Self::Solution, rng: &mut rand::rngs::StdRng) -> Self::Solution;
+    fn score(&self, solution: &Self::Solution) -> ScoreType;
+    fn deltaScore(
+        &self,
+        _now_solution: &Self::Solution,
+        now_score: ScoreType,
+        next_solution: &Self::Solution,
+    ) -> ScoreType {
+        self.score(next_solution) - now_score
+    }
+}
+
+// 近傍の作り方
+trait Neighbourhood {
+    fn apply(&self, state: &State, rng: &mut rand::rngs::StdRng) -> State;
+}
+
+// キャラクターを1人選び、上下左右のいずれかに1マス動かす
+struct MoveOneCell;
+impl Neighbourhood for MoveOneCell {
+    fn apply(&self, state: &State, rng: &mut rand::rngs::StdRng) -> State {
+        let mut next_state = state.clone();
+        let character_id = rng.gen_range(0..CHARACTER_N);
+        let character = next_state.characters_[character_id];
+        loop {
+            let action = rng.gen_range(0..4);
+            let ty = character.y_ + State::dy[action];
+            let tx = character.x_ + State::dx[action];
+            if ty >= 0 && ty < H && tx >= 0 && tx < W {
+                next_state.setCharacter(character_id, ty, tx);
+                return next_state;
+            }
+        }
+    }
+}
+
+// キャラクターを2人選び、それぞれ上下左右のいずれかに1マス動かす
+// 1人ずつ動かす近傍では、途中で一度得点が下がる配置を通らないと届かない配置にも1回で移れる
+struct MoveTwoCharacters;
+impl Neighbourhood for MoveTwoCharacters {
+    fn apply(&self, state: &State, rng: &mut rand::rngs::StdRng) -> State {
+        let mut next_state = state.clone();
+        let character_id0 = rng.gen_range(0..CHARACTER_N);
+        let character_id1 = (character_id0 + rng.gen_range(1..CHARACTER_N)) % CHARACTER_N;
+        for character_id in [character_id0, character_id1] {
+            let character = next_state.characters_[character_id];
+            loop {
+                let action = rng.gen_range(0..4);
+                let ty = character.y_ + State::dy[action];
+                let tx = character.x_ + State::dx[action];
+                if ty >= 0 && ty < H && tx >= 0 && tx < W {
+                    next_state.setCharacter(character_id, ty, tx);
+                    break;
+                }
+            }
+        }
+        next_state
+    }
+}
+
+// キャラクターを1人選び、盤面全体からランダムに置き直す
+struct Rerandomize;
+impl Neighbourhood for Rerandomize {
+    fn apply(&self, state: &State, rng: &mut rand::rngs::StdRng) -> State {
+        let mut next_state = state.clone();
+        let character_id = rng.gen_range(0..CHARACTER_N);
+        next_state.setCharacter(character_id, rng.gen_range(0..H), rng.gen_range(0..W));
+        next_state
+    }
+}
+
+// 重みに比例した確率で近傍を選んで使う
+struct WeightedNeighbourhood {
+    neighbourhoods_: Vec<(Box<dyn Neighbourhood>, f64)>,
+}
+impl WeightedNeighbourhood {
+    pub fn new(neighbourhoods: Vec<(Box<dyn Neighbourhood>, f64)>) -> Self {
+        Self {
+            neighbourhoods_: neighbourhoods,
+        }
+    }
+}
+impl Neighbourhood for WeightedNeighbourhood {
+    fn apply(&self, state: &State, rng: &mut rand::rngs::StdRng) -> State {
+        let weight_sum: f64 = self.neighbourhoods_.iter().map(|(_, weight)| weight).sum();
+        let mut r = rng.gen::<f64>() * weight_sum;
+        for (neighbourhood, weight) in self.neighbourhoods_.iter() {
+            if r < *weight {
+                return neighbourhood.apply(state, rng);
+            }
+            r -= weight;
+        }
+        self.neighbourhoods_.last().unwrap().0.apply(state, rng)
+    }
+}
+
+// キャラクターの初期配置を決める問題
+struct CharacterPlacementProblem {
+    state_: State,
+    neighbourhood_: Box<dyn Neighbourhood>,
+}
+impl CharacterPlacementProblem {
+    pub fn new(state: &State, neighbourhood: Box<dyn Neighbourhood>) -> Self {
+        Self {
+            state_: state.clone(),
+            neighbourhood_: neighbourhood,
+        }
+    }
+}
+impl LocalSearchProblem for CharacterPlacementProblem {
+    type Solution = State;
+    fn initialSolution(&self, rng: &mut rand::rngs::StdRng) -> State {
+        let mut now_state = self.state_.clone();
+        for character_id in 0..CHARACTER_N {
+            now_state.setCharacter(character_id, rng.gen_range(0..H), rng.gen_range(0..W));
+        }
+        now_state
+    }
+    fn neighbour(&self, solution: &State, rng: &mut rand::rngs::StdRng) -> State {
+        self.neighbourhood_.apply(solution, rng)
+    }
+    fn score(&self, solution: &State) -> ScoreType {
+        solution.getScore(false)
+    }
+}
+
+fn hillClimb<P: LocalSearchProblem>(
+    problem: &P,
+    number: usize,
+    rng: &mut rand::rngs::StdRng,
+) -> P::Solution {
+    let mut now_solution = problem.initialSolution(rng);
+    let mut now_score = problem.score(&now_solution);
+    for _ in 0..number {
+        let next_solution = problem.neighbour(&now_solution, rng);
+        let delta = problem.deltaScore(&now_solution, now_score, &next_solution);
+        if delta > 0 {
+            now_score += delta;
+            now_solution = next_solution;
+        }
+    }
+    now_solution
+}
+
+fn simulatedAnnealing<P: LocalSearchProblem>(
+    problem: &P,
+    number: usize,
+    start_temp: f64,
+    end_temp: f64,
+    rng: &mut rand::rngs::StdRng,
+) -> P::Solution {
+    let mut now_solution = problem.initialSolution(rng);
+    let mut now_score = problem.score(&now_solution);
+    let mut best_score = now_score;
+    let mut best_solution = now_solution.clone();
+    for i in 0..number {
+        let next_solution = problem.neighbour(&now_solution, rng);
+        let delta = problem.deltaScore(&now_solution, now_score, &next_solution);
+        let temp = start_temp + (end_temp - start_temp) * (i as f64 / number as f64);
+        let probability = (delta as f64 / temp).exp();
+        if delta > 0 || probability > rng.gen::<f64>() {
+            now_score += delta;
+            now_solution = next_solution;
+            if now_score > best_score {
+                best_score = now_score;
+                best_solution = now_solution.clone();
+            }
+        }
+    }
+    best_solution
+}
+
+// タブーサーチで使う問題
+// 近傍に移ったときに解から消えた属性と加わった属性を返す。最近消えた属性を加える近傍はタブーになる
+trait TabuProblem: LocalSearchProblem {
+    type Attribute: Clone + Eq + std::hash::Hash;
+    fn changedAttributes(
+        &self,
+        now_solution: &Self::Solution,
+        next_solution: &Self::Solution,
+    ) -> (Vec<Self::Attribute>, Vec<Self::Attribute>);
+}
+
+// 遺伝的アルゴリズムで使う問題。突然変異にはneighbourを使う
+trait GeneticProblem: LocalSearchProblem {
+    fn crossover(
+        &self,
+        parent0: &Self::Solution,
+        parent1: &Self::Solution,
+        rng: &mut rand::rngs::StdRng,
+    ) -> Self::Solution;
+}
+
+// 属性は(キャラクター, 置いたマス)。離れたばかりのマスへすぐ戻らないようにする
+impl TabuProblem for CharacterPlacementProblem {
+    type Attribute = (usize, Coord);
+    fn changedAttributes(
+        &self,
+        now_solution: &State,
+        next_solution: &State,
+    ) -> (Vec<(usize, Coord)>, Vec<(usize, Coord)>) {
+        let mut removed = Vec::new();
+        let mut added = Vec::new();
+        for character_id in 0..CHARACTER_N {
+            let now = now_solution.characters_[character_id];
+            let next = next_solution.characters_[character_id];
+            if now != next {
+                removed.push((character_id, now));
+                added.push((character_id, next));
+            }
+        }
+        (removed, added)
+    }
+}
+
+// キャラクターごとに、どちらかの親の位置を受け継ぐ(一様交叉)
+impl GeneticProblem for CharacterPlacementProblem {
+    fn crossover(&self, parent0: &State, parent1: &State, rng: &mut rand::rngs::StdRng) -> State {
+        let mut child = parent0.clone();
+        for character_id in 0..CHARACTER_N {
+            if rng.gen::<bool>() {
+                child.characters_[character_id] = parent1.characters_[character_id];
+            }
+        }
+        child
+    }
+}
+
+// 毎回candidate_number個の近傍を作り、タブーでないものの中で最も良い近傍へ悪くなっても移る
+// タブーでも最良解を更新する近傍は受理する(aspiration)。得点計算の回数はおよそnumberになる
+fn tabuSearch<P: TabuProblem>(
+    problem: &P,
+    number: usize,
+    candidate_number: usize,
+    tabu_tenure: usize,
+    rng: &mut rand::rngs::StdRng,
+) -> P::Solution {
+    let mut now_solution = problem.initialSolution(rng);
+    let mut now_score = problem.score(&now_solution);
+    let mut best_score = now_score;
+    let mut best_solution = now_solution.clone();
+    // 属性ごとに、タブーが解けるイテレーション
+    let mut tabu_until: std::collections::HashMap<P::Attribute, usize> =
+        std::collections::HashMap::new();
+    for iteration in 0..number / candidate_number {
+        let mut best_candidate: Option<(P::Solution, ScoreType)> = None;
+        for _ in 0..candidate_number {
+            let next_solution = problem.neighbour(&now_solution, rng);
+            let next_score =
+                now_score + problem.deltaScore(&now_solution, now_score, &next_solution);
+            let (_, added) = problem.changedAttributes(&now_solution, &next_solution);
+            let is_tabu = added.iter().any(|attribute| {
+                tabu_until
+                    .get(attribute)
+                    .map_or(false, |&until| until > iteration)
+            });
+            if is_tabu && next_score <= best_score {
+                continue;
+            }
+            if best_candidate
+                .as_ref()
+                .map_or(true, |(_, score)| next_score > *score)
+            {
+                best_candidate = Some((next_solution, next_score));
+            }
+        }
+        // 全ての近傍がタブーだったときはそのまま留まる
+        if let Some((next_solution, next_score)) = best_candidate {
+            let (removed, _) = problem.changedAttributes(&now_solution, &next_solution);
+            for attribute in removed {
+                tabu_until.insert(attribute, iteration + 1 + tabu_tenure);
+            }
+            now_solution = next_solution;
+            now_score = next_score;
+            if now_score > best_score {
+                best_score = now_score;
+                best_solution = now_solution.clone();
+            }
+        }
+    }
+    best_solution
+}
+
+// Late Acceptance Hill Climbing
+// 今の得点かhistory_lengthイテレーション前の得点以上なら受理する
+fn lateAcceptance<P: LocalSearchProblem>(
+    problem: &P,
+    number: usize,
+    history_length: usize,
+    rng: &mut rand::rngs::StdRng,
+) -> P::Solution {
+    let mut now_solution = problem.initialSolution(rng);
+    let mut now_score = problem.score(&now_solution);
+    let mut best_score = now_score;
+    let mut best_solution = now_solution.clone();
+    let mut history = vec![now_score; history_length];
+    for i in 0..number {
+        let next_solution = problem.neighbour(&now_solution, rng);
+        let delta = problem.deltaScore(&now_solution, now_score, &next_solution);
+        let history_index = i % history_length;
+        if delta >= 0 || now_score + delta >= history[history_index] {
+            now_score += delta;
+            now_solution = next_solution;
+            if now_score > best_score {
+                best_score = now_score;
+                best_solution = now_solution.clone();
+            }
+        }
+        history[history_index] = now_score;
+    }
+    best_solution
+}
+
+#[derive(Debug, Clone, Copy)]
+struct GeneticConfig {
+    population_size_: usize,
+    tournament_size_: usize,
+    // 次の世代にそのまま残す上位の個体数
+    elite_number_: usize,
+    mutation_rate_: f64,
+}
+impl GeneticConfig {
+    const DEFAULT: Self = Self {
+        population_size_: 50,
+        tournament_size_: 3,
+        elite_number_: 2,
+        mutation_rate_: 0.3,
+    };
+}
+
+// トーナメント選択で選んだ2つの親を交叉し、mutation_rate_の確率で近傍へ動かした子を作る
+// 得点計算の回数がおよそnumberになるまで世代を進める
+fn geneticAlgorithm<P: GeneticProblem>(
+    problem: &P,
+    number: usize,
+    config: &GeneticConfig,
+    rng: &mut rand::rngs::StdRng,
+) -> P::Solution {
+    let mut population: Vec<(ScoreType, P::Solution)> = (0..config.population_size_)
+        .map(|_| {
+            let solution = problem.initialSolution(rng);
+            (problem.score(&solution), solution)
+        })
+        .collect();
+    let mut evaluation_number = config.population_size_;
+    while evaluation_number + config.population_size_ - config.elite_number_ <= number {
+        population.sort_by(|a, b| b.0.cmp(&a.0));
+        let mut next_population: Vec<(ScoreType, P::Solution)> =
+            population[..config.elite_number_].to_vec();
+        while next_population.len() < config.population_size_ {
+            let parent0 = tournament(&population, config.tournament_size_, rng);
+            let parent1 = tournament(&population, config.tournament_size_, rng);
+            let mut child = problem.crossover(parent0, parent1, rng);
+            if rng.gen::<f64>() < config.mutation_rate_ {
+                child = problem.neighbour(&child, rng);
+            }
+            next_population.push((problem.score(&child), child));
+            evaluation_number += 1;
+        }
+        population = next_population;
+    }
+    population
+        .into_iter()
+        .max_by_key(|(score, _)| *score)
+        .unwrap()
+        .1
+}
+
+fn tournament<'a, S>(
+    population: &'a [(ScoreType, S)],
+    tournament_size: usize,
+    rng: &mut rand::rngs::StdRng,
+) -> &'a S {
+    let mut best = &population[rng.gen_range(0..population.len())];
+    for _ in 1..tournament_size {
+        let candidate = &population[rng.gen_range(0..population.len())];
+        if candidate.0 > best.0 {
+            best = candidate;
+        }
+    }
+    &best.1
+}
+
+// どの手法も得点計算の回数をEVALUATION_NUMBERにそろえて比べる
+const EVALUATION_NUMBER: usize = 10000;
+
+type AIFunction = fn(&CharacterPlacementProblem, &mut rand::rngs::StdRng) -> State;
+type StringAIPair = (String, AIFunction);
+
+// 02_SimulatedAnnealingのprintOptimalScoresで全探索して求めた、seed 0..100の盤面の最適な得点
+const OPTIMAL_SCORES: [ScoreType; 100] = [
+    97, 97, 85, 102, 97, 103, 97, 88, 88, 93, 91, 112, 100, 92, 106, 100, 96, 100, 90, 92, 93, 92,
+    94, 90, 87, 104, 87, 101, 85, 93, 85, 99, 95, 88, 66, 97, 95, 93, 95, 90, 98, 94, 86, 98, 88,
+    89, 84, 107, 94, 100, 117, 104, 88, 105, 96, 83, 104, 107, 98, 107, 99, 87, 99, 94, 101, 112,
+    92, 85, 102, 87, 103, 86, 85, 89, 90, 89, 106, 91, 96, 99, 93, 108, 84, 113, 76, 96, 111, 97,
+    85, 90, 91, 80, 84, 77, 99, 91, 93, 92, 85, 103,
+];
+
+// 平均得点と、最適解との差の平均、最適解に届いた盤面の数を表示する
+fn testAiScore(ai: &StringAIPair, optimal_scores: &[ScoreType]) {
+    let game_number = optimal_scores.len();
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0);
+    let mut score_mean = 0.0;
+    let mut gap_mean = 0.0;
+    let mut optimal_number = 0;
+    for i in 0..game_number {
+        let problem = CharacterPlacementProblem::new(&State::new(i), mixedNeighbourhood());
+        let score = ai.1(&problem, &mut rng).getScore(false);
+        score_mean += score as f64;
+        gap_mean += (optimal_scores[i] - score) as f64;
+        if score == optimal_scores[i] {
+            optimal_number += 1;
+        }
+    }
+    score_mean /= game_number as f64;
+    gap_mean /= game_number as f64;
+    println!(
+        "Score of {}:\t{}\tgap {}\toptimal {}/{}",
+        ai.0, score_mean, gap_mean, optimal_number, game_number
+    );
+}
+
+fn mixedNeighbourhood() -> Box<dyn Neighbourhood> {
+    Box::new(WeightedNeighbourhood::new(vec![
+        (Box::new(MoveOneCell), 6.0),
+        (Box::new(MoveTwoCharacters), 1.0),
+        (Box::new(Rerandomize), 3.0),
+    ]))
+}
+
+fn main() {
+    let ais: [StringAIPair; 5] = [
+        ("hillClimb".to_string(), |problem, rng| {
+            hillClimb(problem, EVALUATION_NUMBER, rng)
+        }),
+        ("simulatedAnnealing".to_string(), |problem, rng| {
+            simulatedAnnealing(problem, EVALUATION_NUMBER, 500.0, 10.0, rng)
+        }),
+        ("tabuSearch".to_string(), |problem, rng| {
+            tabuSearch(problem, EVALUATION_NUMBER, 10, 5, rng)
+        }),
+        ("lateAcceptance".to_string(), |problem, rng| {
+            lateAcceptance(problem, EVALUATION_NUMBER, 50, rng)
+        }),
+        ("geneticAlgorithm".to_string(), |problem, rng| {
+            geneticAlgorithm(problem, EVALUATION_NUMBER, &GeneticConfig::DEFAULT, rng)
+        }),
+    ];
+    for ai in ais.iter() {
+        testAiScore(ai, &OPTIMAL_SCORES);
+    }
+}
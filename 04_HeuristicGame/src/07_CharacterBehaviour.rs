@@ -0,0 +1,691 @@
+use rand;
+use rand::prelude::*;
+
+const H: i32 = 7;
+const W: i32 = 7;
+const END_TURN: usize = 10;
+const CHARACTER_N: usize = 3;
+type ScoreType = i32;
+const INF: ScoreType = 100000000;
+
+// 行動の番号。0から3はdx, dyの向きで、STAYはその場に留まる
+const RIGHT: usize = 0;
+const LEFT: usize = 1;
+const DOWN: usize = 2;
+const UP: usize = 3;
+const STAY: usize = 4;
+const ACTION_N: usize = 5;
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Copy)]
+struct Coord {
+    y_: i32,
+    x_: i32,
+}
+impl Coord {
+    pub fn new(y_: i32, x_: i32) -> Self {
+        Self { y_, x_ }
+    }
+}
+
+// キャラクターの動き方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Behaviour {
+    // 隣のマスのうち得点が最も高いマスへ動く
+    Greedy,
+    // 自分だけが動くとして、指定した手数で取れる得点の合計が最も高くなる方向へ動く
+    GreedyLookahead(usize),
+    // シード、ターン、キャラクターの番号から決まる乱数で動ける方向を選ぶ
+    Random(u64),
+    // 右手を壁に付けて進む。盤面の端も壁として扱う
+    WallFollowing,
+}
+impl Behaviour {
+    pub fn name(&self) -> String {
+        match self {
+            Behaviour::Greedy => "greedy".to_string(),
+            Behaviour::GreedyLookahead(depth) => format!("lookahead{}", depth),
+            Behaviour::Random(seed) => format!("random{}", seed),
+            Behaviour::WallFollowing => "wallFollowing".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RuleConfig {
+    behaviours_: [Behaviour; CHARACTER_N],
+    // 同点のときに優先する行動の順。元の規則はRIGHT, LEFT, DOWN, UPの順
+    action_order_: [usize; ACTION_N],
+    // trueならSTAYも候補に入れる。falseでも動けるマスがなければ留まる
+    allow_stay_: bool,
+    // 各マスが障害物になる確率
+    wall_ratio_: f64,
+}
+impl RuleConfig {
+    // 00_AutoMoveMazeState.rsと同じ規則
+    const DEFAULT: Self = Self {
+        behaviours_: [Behaviour::Greedy; CHARACTER_N],
+        action_order_: [RIGHT, LEFT, DOWN, UP, STAY],
+        allow_stay_: false,
+        wall_ratio_: 0.0,
+    };
+}
+
+#[derive(Debug, Clone, Copy)]
+struct AutoMoveMazeState {
+    points_: [[i32; W as usize]; H as usize],
+    walls_: [[bool; W as usize]; H as usize],
+    turn_: i32,
+    pub characters_: [Coord; CHARACTER_N],
+    // WallFollowingで使う、キャラクターが最後に動いた向き
+    directions_: [usize; CHARACTER_N],
+    pub game_score_: i32,
+    pub evaluated_score_: ScoreType,
+    rule_: RuleConfig,
+}
+impl AutoMoveMazeState {
+    const dx: [i32; ACTION_N] = [1, -1, 0, 0, 0];
+    const dy: [i32; ACTION_N] = [0, 0, 1, -1, 0];
+    // 時計回り、反時計回りに90度回した向き
+    const clockwise: [usize; 4] = [DOWN, UP, LEFT, RIGHT];
+    const counterClockwise: [usize; 4] = [UP, DOWN, RIGHT, LEFT];
+    const opposite: [usize; 4] = [LEFT, RIGHT, UP, DOWN];
+
+    fn isWalkable(&self, y: i32, x: i32) -> bool {
+        y >= 0 && y < H && x >= 0 && x < W && !self.walls_[y as usize][x as usize]
+    }
+
+    // coordから選べる行動をaction_order_の順に返す
+    fn legalActions(&self, coord: &Coord) -> Vec<usize> {
+        let mut actions = Vec::new();
+        for &action in self.rule_.action_order_.iter() {
+            if action == STAY {
+                if self.rule_.allow_stay_ {
+                    actions.push(action);
+                }
+            } else if self.isWalkable(coord.y_ + Self::dy[action], coord.x_ + Self::dx[action]) {
+                actions.push(action);
+            }
+        }
+        if actions.is_empty() {
+            actions.push(STAY);
+        }
+        actions
+    }
+
+    // coordからdepth手動いたときに取れる得点の合計の最大値。他のキャラクターの動きは考えない
+    fn lookahead(&self, coord: &Coord, depth: usize, visited: &mut Vec<Coord>) -> ScoreType {
+        if depth == 0 {
+            return 0;
+        }
+        let mut best_point = 0;
+        for action in self.legalActions(coord) {
+            let next = Coord::new(coord.y_ + Self::dy[action], coord.x_ + Self::dx[action]);
+            let is_visited = visited.contains(&next);
+            let point = if is_visited {
+                0
+            } else {
+                self.points_[next.y_ as usize][next.x_ as usize]
+            };
+            visited.push(next);
+            best_point = best_point.max(point + self.lookahead(&next, depth - 1, visited));
+            visited.pop();
+        }
+        best_point
+    }
+
+    fn chooseAction(&self, character_id: usize) -> usize {
+        let character = self.characters_[character_id];
+        let actions = self.legalActions(&character);
+        match self.rule_.behaviours_[character_id] {
+            Behaviour::Greedy | Behaviour::GreedyLookahead(_) => {
+                let depth = match self.rule_.behaviours_[character_id] {
+                    Behaviour::GreedyLookahead(depth) => depth.max(1),
+                    _ => 1,
+                };
+                let mut best_point = -INF;
+                let mut best_action = actions[0];
+                for &action in actions.iter() {
+                    let next = Coord::new(
+                        character.y_ + Self::dy[action],
+                        character.x_ + Self::dx[action],
+                    );
+                    let mut visited = vec![character, next];
+                    let point = self.points_[next.y_ as usize][next.x_ as usize]
+                        + self.lookahead(&next, depth - 1, &mut visited);
+                    if point > best_point {
+                        best_point = point;
+                        best_action = action;
+                    }
+                }
+                best_action
+            }
+            Behaviour::Random(seed) => {
+                let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(
+                    seed ^ ((self.turn_ as u64) << 32 | character_id as u64),
+                );
+                actions[rng.gen_range(0..actions.len())]
+            }
+            Behaviour::WallFollowing => {
+                let direction = self.directions_[character_id];
+                for action in [
+                    Self::clockwise[direction],
+                    direction,
+                    Self::counterClockwise[direction],
+                    Self::opposite[direction],
+                ] {
+                    if self.isWalkable(
+                        character.y_ + Self::dy[action],
+                        character.x_ + Self::dx[action],
+                    ) {
+                        return action;
+                    }
+                }
+                STAY
+            }
+        }
+    }
+
+    fn movePlayer(&mut self, character_id: usize) {
+        let action = self.chooseAction(character_id);
+        let character = &mut self.characters_[character_id];
+        character.y_ += Self::dy[action];
+        character.x_ += Self::dx[action];
+        if action != STAY {
+            self.directions_[character_id] = action;
+        }
+    }
+    fn advance(&mut self) {
+        for character_id in 0..CHARACTER_N {
+            self.movePlayer(character_id);
+        }
+        for character in self.characters_.iter() {
+            let point = &mut self.points_[character.y_ as usize][character.x_ as usize];
+            self.game_score_ += *point;
+            *point = 0;
+        }
+        self.turn_ += 1;
+    }
+
+    pub fn new(seed: usize, rule: &RuleConfig) -> Self {
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(seed as u64);
+        let mut maze_state = Self::fromBoard(
+            [[0 as i32; W as usize]; H as usize],
+            [[false; W as usize]; H as usize],
+            rule,
+        );
+        for y in 0..H {
+            for x in 0..W {
+                maze_state.points_[y as usize][x as usize] = rng.gen_range(0..9) + 1;
+            }
+        }
+        // 障害物の有無で得点の乱数がずれないよう、得点を決めてから障害物を置く
+        for y in 0..H {
+            for x in 0..W {
+                if rng.gen::<f64>() < rule.wall_ratio_ {
+                    maze_state.walls_[y as usize][x as usize] = true;
+                    maze_state.points_[y as usize][x as usize] = 0;
+                }
+            }
+        }
+        maze_state
+    }
+
+    // 得点と障害物を指定して盤面を作る。キャラクターは全員(0, 0)に置く
+    pub fn fromBoard(
+        points: [[i32; W as usize]; H as usize],
+        walls: [[bool; W as usize]; H as usize],
+        rule: &RuleConfig,
+    ) -> Self {
+        AutoMoveMazeState {
+            points_: points,
+            walls_: walls,
+            turn_: 0,
+            game_score_: 0,
+            evaluated_score_: 0,
+            characters_: [Coord::new(0, 0); CHARACTER_N],
+            directions_: [RIGHT; CHARACTER_N],
+            rule_: *rule,
+        }
+    }
+
+    // AtCoder形式の入力を読み込む
+    // 1行目にH W T、続くH行に盤面(数字、.、#)を書く。
+    // 続けてCHARACTER_N行のキャラクターの位置y xを書けばその配置で、書かなければ(0, 0)に置く
+    pub fn fromInput(text: &str, rule: &RuleConfig) -> Result<Self, String> {
+        let mut lines = text
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty());
+        let header = lines.next().ok_or("empty input")?;
+        let size = parseNumbers::<i32>(&header.split_whitespace().collect::<Vec<_>>())?;
+        if size != [H, W, END_TURN as i32] {
+            return Err(format!(
+                "H W T must be {} {} {}: {}",
+                H, W, END_TURN, header
+            ));
+        }
+        let rows: Vec<&str> = lines.by_ref().take(H as usize).collect();
+        let (points, walls, characters) = parseGrid(&rows)?;
+        if !characters.is_empty() {
+            return Err("characters must be given after the board".to_string());
+        }
+        let mut state = Self::fromBoard(points, walls, rule);
+        let positions: Vec<&str> = lines.collect();
+        if !positions.is_empty() && positions.len() != CHARACTER_N {
+            return Err(format!(
+                "{} character positions must be given but {} are",
+                CHARACTER_N,
+                positions.len()
+            ));
+        }
+        for (character_id, position) in positions.iter().enumerate() {
+            let position = parseNumbers::<i32>(&position.split_whitespace().collect::<Vec<_>>())?;
+            if position.len() != 2 {
+                return Err("character position must be y x".to_string());
+            }
+            let (y, x) = (position[0], position[1]);
+            if !state.isWalkable(y, x) {
+                return Err(format!(
+                    "character {} ({}, {}) is not on a floor",
+                    character_id, y, x
+                ));
+            }
+            state.setCharacter(character_id, y, x);
+        }
+        Ok(state)
+    }
+
+    pub fn setCharacter(&mut self, character_id: usize, y: i32, x: i32) {
+        self.characters_[character_id].y_ = y;
+        self.characters_[character_id].x_ = x;
+    }
+
+    // 障害物のないマスをランダムに選ぶ
+    pub fn randomEmptyCell(&self, rng: &mut rand::rngs::StdRng) -> Coord {
+        loop {
+            let y = rng.gen_range(0..H);
+            let x = rng.gen_range(0..W);
+            if !self.walls_[y as usize][x as usize] {
+                return Coord::new(y, x);
+            }
+        }
+    }
+
+    pub fn isDone(&self) -> bool {
+        return self.turn_ == END_TURN as i32;
+    }
+    pub fn toString(&self) -> String {
+        let mut ss = String::new();
+        ss += format!("turn:\t{}\n", self.turn_).as_str();
+        ss += format!("score:\t{}\n", self.game_score_).as_str();
+        for h in 0..H {
+            for w in 0..W {
+                if self.walls_[h as usize][w as usize] {
+                    ss += "#";
+                } else if self
+                    .characters_
+                    .iter()
+                    .any(|character| character.y_ == h && character.x_ == w)
+                {
+                    ss += "@";
+                } else if self.points_[h as usize][w as usize] > 0 {
+                    ss += self.points_[h as usize][w as usize].to_string().as_str();
+                } else {
+                    ss += ".";
+                }
+            }
+            ss += "\n";
+        }
+        // @のマスの得点は盤面に出ないので、キャラクターごとに位置とその下の得点を書く
+        for character in self.characters_.iter() {
+            ss += format!(
+                "character:\t{}\t{}\t{}\n",
+                character.y_,
+                character.x_,
+                self.points_[character.y_ as usize][character.x_ as usize]
+            )
+            .as_str();
+        }
+        ss
+    }
+
+    pub fn getScore(&self, is_print: bool) -> ScoreType {
+        let mut tmp_state = self.clone();
+        for character in self.characters_ {
+            let point = &mut tmp_state.points_[character.y_ as usize][character.x_ as usize];
+            *point = 0;
+        }
+        while !tmp_state.isDone() {
+            tmp_state.advance();
+            if is_print {
+                println!("{}", tmp_state.toString());
+            }
+        }
+        tmp_state.game_score_
+    }
+}
+
+fn parseNumbers<T: std::str::FromStr>(words: &[&str]) -> Result<Vec<T>, String> {
+    words
+        .iter()
+        .map(|word| {
+            word.parse::<T>()
+                .map_err(|_| format!("invalid number: {}", word))
+        })
+        .collect()
+}
+
+// 盤面のH行を読み、得点、障害物、キャラクターの位置を返す
+// 数字は得点、.は得点のないマス、#は障害物、@はキャラクターを表す
+fn parseGrid(
+    rows: &[&str],
+) -> Result<
+    (
+        [[i32; W as usize]; H as usize],
+        [[bool; W as usize]; H as usize],
+        Vec<Coord>,
+    ),
+    String,
+> {
+    if rows.len() != H as usize {
+        return Err(format!("board must have {} rows but has {}", H, rows.len()));
+    }
+    let mut points = [[0; W as usize]; H as usize];
+    let mut walls = [[false; W as usize]; H as usize];
+    let mut characters = Vec::new();
+    for (y, row) in rows.iter().enumerate() {
+        let cells: Vec<char> = row.chars().collect();
+        if cells.len() != W as usize {
+            return Err(format!("row {} must have {} cells: {}", y, W, row));
+        }
+        for (x, &cell) in cells.iter().enumerate() {
+            match cell {
+                '0'..='9' => points[y][x] = cell.to_digit(10).unwrap() as i32,
+                '.' => {}
+                '#' => walls[y][x] = true,
+                '@' => characters.push(Coord::new(y as i32, x as i32)),
+                _ => return Err(format!("invalid cell {} at ({}, {})", cell, y, x)),
+            }
+        }
+    }
+    Ok((points, walls, characters))
+}
+
+impl std::str::FromStr for AutoMoveMazeState {
+    type Err = String;
+    // toStringの出力を読み込む。turn:とscore:の行は省略でき、規則はRuleConfig::DEFAULTになる
+    // キャラクターの位置と下の得点はcharacter:の行から読み、盤面の@はその位置と一致しなければならない
+    // character:の行も@もない盤面はキャラクターを置く前の問題として読み、全員を(0, 0)に置く
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut turn = 0;
+        let mut game_score = 0;
+        let mut positions = Vec::new();
+        let mut rows = Vec::new();
+        for line in text.lines().map(|line| line.trim()) {
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(value) = line.strip_prefix("turn:") {
+                turn = parseNumbers::<i32>(&[value.trim()])?[0];
+            } else if let Some(value) = line.strip_prefix("score:") {
+                game_score = parseNumbers::<i32>(&[value.trim()])?[0];
+            } else if let Some(value) = line.strip_prefix("character:") {
+                let position = parseNumbers::<i32>(&value.split_whitespace().collect::<Vec<_>>())?;
+                if position.len() != 3 {
+                    return Err(format!("character must be y x point: {}", line));
+                }
+                positions.push(position);
+            } else {
+                rows.push(line);
+            }
+        }
+        let (points, walls, characters) = parseGrid(&rows)?;
+        let mut state = State::fromBoard(points, walls, &RuleConfig::DEFAULT);
+        state.turn_ = turn;
+        state.game_score_ = game_score;
+        if positions.is_empty() {
+            // @だけでは下のマスの得点が分からないので読めない
+            if !characters.is_empty() {
+                return Err("board with @ must have character: y x point lines".to_string());
+            }
+            return Ok(state);
+        }
+        if positions.len() != CHARACTER_N {
+            return Err(format!(
+                "board must have {} character: lines but has {}",
+                CHARACTER_N,
+                positions.len()
+            ));
+        }
+        for (character_id, position) in positions.iter().enumerate() {
+            let (y, x, point) = (position[0], position[1], position[2]);
+            if !state.isWalkable(y, x) {
+                return Err(format!(
+                    "character {} ({}, {}) is not on a floor",
+                    character_id, y, x
+                ));
+            }
+            if !characters.contains(&Coord::new(y, x)) {
+                return Err(format!(
+                    "character {} ({}, {}) is not on @",
+                    character_id, y, x
+                ));
+            }
+            state.setCharacter(character_id, y, x);
+            state.points_[y as usize][x as usize] = point;
+        }
+        for character in characters.iter() {
+            if !state.characters_.contains(character) {
+                return Err(format!(
+                    "@ at ({}, {}) has no character: line",
+                    character.y_, character.x_
+                ));
+            }
+        }
+        Ok(state)
+    }
+}
+
+type State = AutoMoveMazeState;
+fn randomAction(state: &State, rng: &mut rand::rngs::StdRng) -> State {
+    let mut now_state = state.clone();
+    for character_id in 0..CHARACTER_N {
+        let cell = now_state.randomEmptyCell(rng);
+        now_state.setCharacter(character_id, cell.y_, cell.x_);
+    }
+    now_state
+}
+
+// キャラクターを1人選び、障害物のないマスへ置き直す
+fn transition(state: &State, rng: &mut rand::rngs::StdRng) -> State {
+    let mut next_state = state.clone();
+    let character_id = rng.gen_range(0..CHARACTER_N);
+    let cell = next_state.randomEmptyCell(rng);
+    next_state.setCharacter(character_id, cell.y_, cell.x_);
+    next_state
+}
+
+fn simulatedAnnealing(
+    state: &State,
+    number: usize,
+    start_temp: f64,
+    end_temp: f64,
+    rng: &mut rand::rngs::StdRng,
+) -> State {
+    let mut now_state = randomAction(state, rng);
+    let mut now_score = now_state.getScore(false);
+    let mut best_score = now_score;
+    let mut best_state = now_state.clone();
+    for i in 0..number {
+        let next_state = transition(&now_state, rng);
+        let next_score = next_state.getScore(false);
+        let temp = start_temp + (end_temp - start_temp) * (i as f64 / number as f64);
+        let probability = ((next_score - now_score) as f64 / temp).exp();
+        if next_score > now_score || probability > rng.gen::<f64>() {
+            now_score = next_score;
+            now_state = next_state;
+            if now_score > best_score {
+                best_score = now_score;
+                best_state = now_state.clone();
+            }
+        }
+    }
+    best_state
+}
+
+fn playGame(seed: usize, rule: &RuleConfig) {
+    playFrom(&State::new(seed, rule), seed);
+}
+fn playFrom(state: &State, seed: usize) {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(seed as u64);
+    let state = simulatedAnnealing(state, 10000, 500.0, 10.0, &mut rng);
+    let score = state.getScore(true);
+    println!("Score of simulatedAnnealing: {}", score);
+}
+
+// 問題ファイルを読み込む。1行目が3つの数字ならAtCoder形式、そうでなければtoStringの形式として読む
+fn loadProblem(path: &str, rule: &RuleConfig) -> Result<State, String> {
+    let text = std::fs::read_to_string(path).map_err(|error| error.to_string())?;
+    let first_line = text
+        .lines()
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty())
+        .unwrap_or("");
+    let words: Vec<&str> = first_line.split_whitespace().collect();
+    if words.len() == 3 && parseNumbers::<i32>(&words).is_ok() {
+        State::fromInput(&text, rule)
+    } else {
+        let mut state: State = text.parse()?;
+        state.rule_ = *rule;
+        Ok(state)
+    }
+}
+
+// toStringの出力を読み直して、キャラクターの下の得点も含めて同じ盤面に戻るか確かめる
+fn testParse(game_number: usize, rule: &RuleConfig) {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0);
+    let mut failed_number = 0;
+    for seed in 0..game_number {
+        let state = randomAction(&State::new(seed, rule), &mut rng);
+        match state.toString().parse::<State>() {
+            Ok(parsed)
+                if parsed.toString() == state.toString()
+                    && parsed.points_ == state.points_
+                    && parsed.walls_ == state.walls_
+                    && parsed.characters_ == state.characters_ => {}
+            Ok(_) => {
+                println!("seed {}: parsed board differs", seed);
+                failed_number += 1;
+            }
+            Err(message) => {
+                println!("seed {}: {}", seed, message);
+                failed_number += 1;
+            }
+        }
+    }
+    println!("parsed {} boards, {} failed", game_number, failed_number);
+}
+
+// 規則ごとに、ランダムな配置と焼きなまし法で決めた配置の平均得点を比べる
+fn testAiScore(name: &str, rule: &RuleConfig, game_number: usize) {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0);
+    let mut random_score_mean = 0.0;
+    let mut annealing_score_mean = 0.0;
+    for i in 0..game_number {
+        let state = State::new(i, rule);
+        random_score_mean += randomAction(&state, &mut rng).getScore(false) as f64;
+        annealing_score_mean +=
+            simulatedAnnealing(&state, 10000, 500.0, 10.0, &mut rng).getScore(false) as f64;
+    }
+    random_score_mean /= game_number as f64;
+    annealing_score_mean /= game_number as f64;
+    let behaviours: Vec<String> = rule
+        .behaviours_
+        .iter()
+        .map(|behaviour| behaviour.name())
+        .collect();
+    println!(
+        "{} [{}]:\trandomAction {}\tsimulatedAnnealing {}",
+        name,
+        behaviours.join(","),
+        random_score_mean,
+        annealing_score_mean
+    );
+}
+
+fn main() {
+    let walled = RuleConfig {
+        wall_ratio_: 0.2,
+        ..RuleConfig::DEFAULT
+    };
+    let rules = [
+        ("original", RuleConfig::DEFAULT),
+        ("walls", walled),
+        (
+            "walls, stay",
+            RuleConfig {
+                // 周りの得点が全て0なら、動き回らずに留まる
+                action_order_: [STAY, RIGHT, LEFT, DOWN, UP],
+                allow_stay_: true,
+                ..walled
+            },
+        ),
+        (
+            "walls, UP/DOWN/LEFT/RIGHT order",
+            RuleConfig {
+                action_order_: [UP, DOWN, LEFT, RIGHT, STAY],
+                ..walled
+            },
+        ),
+        (
+            "walls, lookahead",
+            RuleConfig {
+                behaviours_: [Behaviour::GreedyLookahead(3); CHARACTER_N],
+                ..walled
+            },
+        ),
+        (
+            "walls, random",
+            RuleConfig {
+                behaviours_: [
+                    Behaviour::Random(0),
+                    Behaviour::Random(1),
+                    Behaviour::Random(2),
+                ],
+                ..walled
+            },
+        ),
+        (
+            "walls, wall following",
+            RuleConfig {
+                behaviours_: [Behaviour::WallFollowing; CHARACTER_N],
+                ..walled
+            },
+        ),
+        (
+            "walls, mixed",
+            RuleConfig {
+                behaviours_: [
+                    Behaviour::Greedy,
+                    Behaviour::GreedyLookahead(2),
+                    Behaviour::WallFollowing,
+                ],
+                allow_stay_: true,
+                ..walled
+            },
+        ),
+    ];
+    testParse(100, &walled);
+    // 引数に問題ファイルを渡すと、最後の規則でその盤面のキャラクターの配置を探す
+    if let Some(problem_path) = std::env::args().nth(1) {
+        match loadProblem(&problem_path, &rules.last().unwrap().1) {
+            Ok(state) => playFrom(&state, 0),
+            Err(message) => println!("invalid problem {}: {}", problem_path, message),
+        }
+        return;
+    }
+    for (name, rule) in rules.iter() {
+        testAiScore(name, rule, 10);
+    }
+    playGame(0, &rules.last().unwrap().1);
+}
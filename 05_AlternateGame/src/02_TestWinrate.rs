@@ -1,5 +1,3 @@
-use std::os::macos::raw::stat;
-
 use rand::{random, Rng};
 
 const H: i32 = 3;
@@ -1,10 +1,9 @@
-use std::os::macos::raw::stat;
-
 use rand::{distributions::Alphanumeric, random, Rng};
 
 const H: i32 = 3;
 const W: i32 = 3;
 const END_TURN: usize = 4;
+const dstr: [&str; 4] = ["RIGHT", "LEFT", "DOWN", "UP"];
 
 type ScoreType = i32;
 const INF: i32 = 100000000;
@@ -259,6 +258,158 @@ fn alphaBetaAction(state: &State, depth: usize) -> usize {
     best_action
 }
 
+// alphaBetaScoreの探索の様子をGraphvizのDOT形式で記録する
+struct AlphaBetaTrace {
+    dot_: String,
+    node_number_: usize,
+}
+impl AlphaBetaTrace {
+    pub fn new() -> Self {
+        Self {
+            dot_: String::new(),
+            node_number_: 0,
+        }
+    }
+    fn newNode(&mut self) -> usize {
+        let node_id = self.node_number_;
+        self.node_number_ += 1;
+        node_id
+    }
+    fn scoreName(score: ScoreType) -> String {
+        if score >= INF {
+            "INF".to_string()
+        } else if score <= -INF {
+            "-INF".to_string()
+        } else {
+            score.to_string()
+        }
+    }
+    // 評価を終えたノードを書く。枝刈りが起きたノードは赤くする
+    fn writeNode(
+        &mut self,
+        node_id: usize,
+        alpha: ScoreType,
+        beta: ScoreType,
+        score: ScoreType,
+        is_cutoff: bool,
+        is_done: bool,
+    ) {
+        let mut attributes = String::new();
+        if is_cutoff {
+            attributes += ", color=red, fontcolor=red";
+        }
+        if is_done {
+            attributes += ", peripheries=2";
+        }
+        self.dot_ += format!(
+            "  n{} [label=\"alpha={} beta={}\\nscore={}{}\"{}];\n",
+            node_id,
+            Self::scoreName(alpha),
+            Self::scoreName(beta),
+            Self::scoreName(score),
+            if is_cutoff { "\\ncutoff" } else { "" },
+            attributes
+        )
+        .as_str();
+    }
+    fn writeEdge(&mut self, parent_id: usize, child_id: usize, action: usize) {
+        self.dot_ += format!(
+            "  n{} -> n{} [label=\"{}\"];\n",
+            parent_id, child_id, dstr[action]
+        )
+        .as_str();
+    }
+    // 枝刈りで探索しなかった手を灰色の点線で書く
+    fn writePruned(&mut self, parent_id: usize, action: usize) {
+        let node_id = self.newNode();
+        self.dot_ += format!(
+            "  n{} [label=\"pruned\", style=dashed, color=gray, fontcolor=gray];\n",
+            node_id
+        )
+        .as_str();
+        self.dot_ += format!(
+            "  n{} -> n{} [label=\"{}\", style=dashed, color=gray, fontcolor=gray];\n",
+            parent_id, node_id, dstr[action]
+        )
+        .as_str();
+    }
+}
+
+// alphaBetaScoreと同じ探索をしながら探索木を記録する。返り値は評価値とノードの番号
+fn alphaBetaScoreTraced(
+    state: &State,
+    alpha: ScoreType,
+    beta: ScoreType,
+    depth: usize,
+    trace: &mut AlphaBetaTrace,
+) -> (ScoreType, usize) {
+    let node_id = trace.newNode();
+    if state.isDone() || depth == 0 {
+        let score = state.getScore();
+        trace.writeNode(node_id, alpha, beta, score, false, state.isDone());
+        return (score, node_id);
+    }
+    let legal_actions = state.legalActions();
+    if legal_actions.is_empty() {
+        let score = state.getScore();
+        trace.writeNode(node_id, alpha, beta, score, false, false);
+        return (score, node_id);
+    }
+    let mut alpha_local = alpha;
+    for (i, &action) in legal_actions.iter().enumerate() {
+        let mut next_state = state.clone();
+        next_state.advance(action);
+        let (child_score, child_id) =
+            alphaBetaScoreTraced(&next_state, -beta, -alpha_local, depth - 1, trace);
+        trace.writeEdge(node_id, child_id, action);
+        let score = -child_score;
+        if score > alpha_local {
+            alpha_local = score;
+        }
+        if alpha_local >= beta {
+            for &pruned_action in &legal_actions[i + 1..] {
+                trace.writePruned(node_id, pruned_action);
+            }
+            trace.writeNode(node_id, alpha, beta, alpha_local, true, false);
+            return (alpha_local, node_id);
+        }
+    }
+    trace.writeNode(node_id, alpha, beta, alpha_local, false, false);
+    return (alpha_local, node_id);
+}
+
+// alphaBetaActionと同じ探索をして、探索木をDOT形式で返す
+fn alphaBetaDot(state: &State, depth: usize) -> String {
+    let mut trace = AlphaBetaTrace::new();
+    let root_id = trace.newNode();
+    let mut best_action = 0;
+    let mut alpha = -INF;
+    let beta = INF;
+    for action in state.legalActions() {
+        let mut next_state = state.clone();
+        next_state.advance(action);
+        let (child_score, child_id) =
+            alphaBetaScoreTraced(&next_state, -beta, -alpha, depth, &mut trace);
+        trace.writeEdge(root_id, child_id, action);
+        let score = -child_score;
+        if score > alpha {
+            best_action = action;
+            alpha = score;
+        }
+    }
+    trace.dot_ += format!(
+        "  n{} [label=\"root\\nbest={} score={}\"];\n",
+        root_id,
+        dstr[best_action],
+        AlphaBetaTrace::scoreName(alpha)
+    )
+    .as_str();
+    format!(
+        "digraph alphabeta {{\n  node [shape=box, fontname=\"monospace\"];\n{}}}\n",
+        trace.dot_
+    )
+}
+
 fn playGame(seed: usize) {
     let mut state = State::new(seed);
     println!("{}", state.toString());
@@ -339,7 +490,17 @@ fn testFirstPlayerWinRate(ais: [StringAIPair; 2], game_number: usize) {
     );
 }
 
+const TREE_DIRECTORY: &str = "trees";
+
 fn main() {
+    // 初期局面の探索木を書き出す。dot -Tsvg trees/alphabeta.dot -o alphabeta.svg のように画像にできる
+    std::fs::create_dir_all(TREE_DIRECTORY).unwrap();
+    std::fs::write(
+        format!("{}/alphabeta.dot", TREE_DIRECTORY),
+        alphaBetaDot(&State::new(0), END_TURN),
+    )
+    .unwrap();
+
     let f1: AIFunction = |state: &State| return miniMaxAction(state, END_TURN);
     let f0: AIFunction = |state: &State| return alphaBetaAction(state, END_TURN);
     let ais = [
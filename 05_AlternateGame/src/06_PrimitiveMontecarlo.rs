@@ -1,4 +1,4 @@
-use std::{os::macos::raw::stat, time};
+use std::time;
 
 use rand::{distributions::Alphanumeric, random, Rng};
 
@@ -1,4 +1,4 @@
-use std::{os::macos::raw::stat, time};
+use std::time;
 
 use rand::{distributions::Alphanumeric, random, Rng};
 
@@ -246,8 +246,6 @@ fn testFirstPlayerWinRate(ais: [StringAIPair; 2], game_number: usize) {
 }
 pub mod montecalro {
 
-    use std::os::macos::raw::stat;
-
     use crate::randomAction;
     use crate::State;
     use crate::WinningStatus;
@@ -1,4 +1,4 @@
-use std::{os::macos::raw::stat, time};
+use std::time;
 
 use rand::{distributions::Alphanumeric, random, Rng};
 
@@ -21,6 +21,7 @@ impl TimeKeeper {
 const H: i32 = 10;
 const W: i32 = 10;
 const END_TURN: usize = 50;
+const dstr: [&str; 4] = ["RIGHT", "LEFT", "DOWN", "UP"];
 
 type ScoreType = i32;
 const INF: i32 = 100000000;
@@ -209,6 +210,16 @@ fn randomAction(state: &State) -> usize {
     return legal_actions[id as usize];
 }
 
+// 終局していれば、手番のプレイヤーから見た勝敗を返す
+fn winningStatusName(state: &State) -> &'static str {
+    match state.getWinningStatus() {
+        WinningStatus::WIN => "WIN",
+        WinningStatus::LOSE => "LOSE",
+        WinningStatus::DRAW => "DRAW",
+        WinningStatus::NONE => "",
+    }
+}
+
 type AIFunction = fn(&State) -> usize;
 type StringAIPair = (String, AIFunction);
 
@@ -253,7 +264,6 @@ fn testFirstPlayerWinRate(ais: [StringAIPair; 2], game_number: usize) {
 }
 pub mod montecalro {
 
-    use std::os::macos::raw::stat;
 
     use crate::randomAction;
     use crate::State;
@@ -371,6 +381,84 @@ pub mod montecalro {
             &mut self.child_nodes[best_action_index]
         }
     }
+    impl Node {
+        // 探索木をGraphvizのDOT形式で書き出す
+        // 根からの深さがmax_depth以下で、試行回数がmin_n以上のノードだけを出力する
+        pub fn toDot(&self, max_depth: usize, min_n: f64) -> String {
+            let mut ss = String::new();
+            ss += "digraph mcts {\n";
+            ss += "  node [shape=box, fontname=\"monospace\"];\n";
+            let mut node_number = 0;
+            self.writeDot(&mut ss, &mut node_number, "root", 0.0, 0, max_depth, min_n);
+            ss += "}\n";
+            ss
+        }
+        // ノードをssに書き、そのノードの番号を返す。tは兄弟ノードの試行回数の合計
+        fn writeDot(
+            &self,
+            ss: &mut String,
+            node_number: &mut usize,
+            action_name: &str,
+            t: f64,
+            depth: usize,
+            max_depth: usize,
+            min_n: f64,
+        ) -> usize {
+            let node_id = *node_number;
+            *node_number += 1;
+            let mut label = format!("{}\\nn={}", action_name, self.n_);
+            if self.n_ > 0.0 {
+                label += format!("\\nw/n={:.3}", self.w_ / self.n_).as_str();
+                // 親の手番から見た、nextChildNodeが比べる値
+                if depth > 0 {
+                    label += format!(
+                        "\\nucb1={:.3}",
+                        1.0 - self.w_ / self.n_ + C * (2.0 * t.ln() / self.n_).sqrt()
+                    )
+                    .as_str();
+                }
+            }
+            let mut attributes = String::new();
+            if self.state_.isDone() {
+                label += format!("\\n{}", crate::winningStatusName(&self.state_)).as_str();
+                attributes += ", peripheries=2";
+            }
+            *ss += format!("  n{} [label=\"{}\"{}];\n", node_id, label, attributes).as_str();
+            if depth < max_depth {
+                let legal_actions = self.state_.legalActions();
+                let t: f64 = self
+                    .child_nodes
+                    .iter()
+                    .map(|child_node| child_node.n_)
+                    .sum();
+                for (child_node, action) in self.child_nodes.iter().zip(legal_actions) {
+                    if child_node.n_ < min_n {
+                        continue;
+                    }
+                    let child_id = child_node.writeDot(
+                        ss,
+                        node_number,
+                        crate::dstr[action],
+                        t,
+                        depth + 1,
+                        max_depth,
+                        min_n,
+                    );
+                    *ss += format!("  n{} -> n{};\n", node_id, child_id).as_str();
+                }
+            }
+            node_id
+        }
+    }
+    // mctsActionと同じ探索をして、探索木をDOT形式で返す
+    pub fn mctsDot(state: &State, playout_number: usize, max_depth: usize, min_n: f64) -> String {
+        let mut root_node = Node::new(state);
+        root_node.expand();
+        for _ in 0..playout_number {
+            root_node.evaluate();
+        }
+        root_node.toDot(max_depth, min_n)
+    }
     pub fn mctsAction(state: &State, playout_number: usize) -> usize {
         let mut root_node = Node::new(state);
         root_node.expand();
@@ -473,6 +561,79 @@ mod thunder {
         }
     }
 
+    impl Node {
+        // 探索木をGraphvizのDOT形式で書き出す
+        // 根からの深さがmax_depth以下で、試行回数がmin_n以上のノードだけを出力する
+        pub fn toDot(&self, max_depth: usize, min_n: f64) -> String {
+            let mut ss = String::new();
+            ss += "digraph thunder {\n";
+            ss += "  node [shape=box, fontname=\"monospace\"];\n";
+            let mut node_number = 0;
+            self.writeDot(&mut ss, &mut node_number, "root", 0, max_depth, min_n);
+            ss += "}\n";
+            ss
+        }
+        // ノードをssに書き、そのノードの番号を返す
+        fn writeDot(
+            &self,
+            ss: &mut String,
+            node_number: &mut usize,
+            action_name: &str,
+            depth: usize,
+            max_depth: usize,
+            min_n: f64,
+        ) -> usize {
+            let node_id = *node_number;
+            *node_number += 1;
+            let mut label = format!("{}\\nn={}", action_name, self.n_);
+            if self.n_ > 0.0 {
+                label += format!("\\nw/n={:.3}", self.w_ / self.n_).as_str();
+                // 親の手番から見た、nextChildNodeが比べる値
+                if depth > 0 {
+                    label += format!("\\nthunder={:.3}", 1.0 - self.w_ / self.n_).as_str();
+                }
+            }
+            let mut attributes = String::new();
+            if self.state_.isDone() {
+                label += format!("\\n{}", crate::winningStatusName(&self.state_)).as_str();
+                attributes += ", peripheries=2";
+            }
+            *ss += format!("  n{} [label=\"{}\"{}];\n", node_id, label, attributes).as_str();
+            if depth < max_depth {
+                let legal_actions = self.state_.legalActions();
+                for (child_node, action) in self.child_nodes.iter().zip(legal_actions) {
+                    if child_node.n_ < min_n {
+                        continue;
+                    }
+                    let child_id = child_node.writeDot(
+                        ss,
+                        node_number,
+                        crate::dstr[action],
+                        depth + 1,
+                        max_depth,
+                        min_n,
+                    );
+                    *ss += format!("  n{} -> n{};\n", node_id, child_id).as_str();
+                }
+            }
+            node_id
+        }
+    }
+    // thunderSearchActionと同じ探索をして、探索木をDOT形式で返す
+    pub fn thunderSearchDot(
+        state: &State,
+        playout_number: usize,
+        max_depth: usize,
+        min_n: f64,
+    ) -> String {
+        let mut root_node = Node::new(state);
+        root_node.expand();
+        for _ in 0..playout_number {
+            root_node.evaluate();
+        }
+        root_node.toDot(max_depth, min_n)
+    }
+
     pub fn thunderSearchAction(state: &State, playout_number: usize) -> usize {
         let mut root_node = Node::new(state);
         root_node.expand();
@@ -494,7 +655,23 @@ mod thunder {
     }
 }
 
+const TREE_DIRECTORY: &str = "trees";
+
 fn main() {
+    // 初期局面の探索木を書き出す。dot -Tsvg trees/mcts.dot -o mcts.svg のように画像にできる
+    std::fs::create_dir_all(TREE_DIRECTORY).unwrap();
+    let state = State::new(0);
+    std::fs::write(
+        format!("{}/mcts.dot", TREE_DIRECTORY),
+        montecalro::mctsDot(&state, 300, 3, 5.0),
+    )
+    .unwrap();
+    std::fs::write(
+        format!("{}/thunder.dot", TREE_DIRECTORY),
+        thunder::thunderSearchDot(&state, 300, 3, 5.0),
+    )
+    .unwrap();
+
     let f0: AIFunction = |state: &State| return thunder::thunderSearchAction(state, 300);
     let f1: AIFunction = |state: &State| return montecalro::mctsAction(state, 300);
     let ais = [
@@ -0,0 +1,578 @@
+use std::io::{self, Write};
+use std::time;
+
+use rand::Rng;
+
+struct TimeKeeper {
+    start_time_: std::time::Instant,
+    time_threshold_: u64,
+}
+impl TimeKeeper {
+    pub fn new(time_threshold: u64) -> Self {
+        Self {
+            start_time_: time::Instant::now(),
+            time_threshold_: time_threshold,
+        }
+    }
+    pub fn isTimeOver(&self) -> bool {
+        self.start_time_.elapsed() > time::Duration::from_millis(self.time_threshold_)
+    }
+}
+
+const H: i32 = 5;
+const W: i32 = 5;
+const END_TURN: usize = 10;
+const dstr: [&str; 4] = ["RIGHT", "LEFT", "DOWN", "UP"];
+
+type ScoreType = i32;
+const INF: i32 = 100000000;
+
+pub enum WinningStatus {
+    WIN,
+    LOSE,
+    DRAW,
+    NONE,
+}
+#[derive(Debug, Default, Clone, Eq, PartialEq, Copy)]
+struct Character {
+    y_: i32,
+    x_: i32,
+    game_score_: i32,
+}
+impl Character {
+    pub fn new(y_: i32, x_: i32) -> Self {
+        Self {
+            y_: y_,
+            x_: x_,
+            game_score_: 0,
+        }
+    }
+}
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct AlternateMazeState {
+    points_: [[i32; W as usize]; H as usize],
+    turn_: usize,
+    characters_: Vec<Character>,
+}
+
+impl AlternateMazeState {
+    const dx: [i32; 4] = [1, -1, 0, 0];
+    const dy: [i32; 4] = [0, 0, 1, -1];
+    pub fn new(seed: usize) -> Self {
+        let mut state = Self {
+            points_: [[0; W as usize]; H as usize],
+            turn_: 0,
+            characters_: vec![
+                Character::new(H / 2, W / 2 - 1),
+                Character::new(H / 2, W / 2 + 1),
+            ],
+        };
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(seed as u64);
+        for y in 0..H {
+            for x in 0..W {
+                let point = rng.gen_range(0..10);
+                if state.characters_[0].y_ == y && state.characters_[0].x_ == x {
+                    continue;
+                }
+                if state.characters_[1].y_ == y && state.characters_[1].x_ == x {
+                    continue;
+                }
+                state.points_[y as usize][x as usize] = point;
+            }
+        }
+        state
+    }
+    fn isFirstPlayer(&self) -> bool {
+        self.turn_ % 2 == 0
+    }
+
+    pub fn isDone(&self) -> bool {
+        self.turn_ == END_TURN
+    }
+    pub fn advance(&mut self, action: usize) {
+        let character = &mut self.characters_[0];
+        character.x_ += Self::dx[action];
+        character.y_ += Self::dy[action];
+        let point: &mut i32 = &mut self.points_[character.y_ as usize][character.x_ as usize];
+        if *point > 0 {
+            character.game_score_ += *point;
+            *point = 0;
+        }
+        self.turn_ += 1;
+        self.characters_.swap(0, 1);
+    }
+    pub fn legalActions(&self) -> Vec<usize> {
+        let mut actions = Vec::new();
+        let character = &self.characters_[0];
+        for action in 0..4 {
+            let ty = character.y_ + Self::dy[action];
+            let tx = character.x_ + Self::dx[action];
+            if ty >= 0 && ty < H && tx >= 0 && tx < W {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+    pub fn getWinningStatus(&self) -> WinningStatus {
+        if self.isDone() {
+            if self.characters_[0].game_score_ > self.characters_[1].game_score_ {
+                return WinningStatus::WIN;
+            } else if self.characters_[0].game_score_ < self.characters_[1].game_score_ {
+                return WinningStatus::LOSE;
+            } else {
+                return WinningStatus::DRAW;
+            }
+        }
+        WinningStatus::NONE
+    }
+    pub fn getScore(&self) -> ScoreType {
+        return self.characters_[0].game_score_ - self.characters_[1].game_score_;
+    }
+    pub fn getFirstPlayerScoreForWinRate(&self) -> f64 {
+        match self.getWinningStatus() {
+            WinningStatus::WIN => {
+                if self.isFirstPlayer() {
+                    return 1.0;
+                } else {
+                    return 0.0;
+                }
+            }
+            WinningStatus::LOSE => {
+                if self.isFirstPlayer() {
+                    return 0.0;
+                } else {
+                    return 1.0;
+                }
+            }
+            _ => return 0.5,
+        }
+    }
+
+    pub fn toString(&self) -> String {
+        let mut ss = String::new();
+        ss += format!("turn:\t{}\n", self.turn_).as_str();
+        for player_id in 0..self.characters_.len() {
+            let mut actual_player_id = player_id;
+            if self.turn_ % 2 == 1 {
+                actual_player_id = (player_id + 1) % 2;
+            }
+            let chara = &self.characters_[actual_player_id as usize];
+            ss += format!(
+                "score({})\t {}\ty:{} x:{}\n",
+                player_id, chara.game_score_, chara.y_, chara.x_
+            )
+            .as_str();
+        }
+        for h in 0..H {
+            for w in 0..W {
+                let mut is_written = false;
+                for player_id in 0..self.characters_.len() {
+                    let mut actual_player_id = player_id;
+                    if self.turn_ % 2 == 1 {
+                        actual_player_id = (player_id + 1) % 2;
+                    }
+                    let character = &self.characters_[player_id as usize];
+                    if character.y_ == h && character.x_ == w {
+                        if actual_player_id == 0 {
+                            ss += "A";
+                        } else {
+                            ss += "B";
+                        }
+                        is_written = true;
+                    }
+                }
+                if !is_written {
+                    if self.points_[h as usize][w as usize] > 0 {
+                        ss += format!("{}", self.points_[h as usize][w as usize]).as_str();
+                    } else {
+                        ss += ".";
+                    }
+                }
+            }
+            ss += "\n";
+        }
+
+        ss
+    }
+}
+
+type State = AlternateMazeState;
+fn randomAction(state: &State) -> usize {
+    let mut rng = rand::thread_rng();
+    let legal_actions = state.legalActions();
+    let id = rng.gen_range(0..legal_actions.len());
+    return legal_actions[id as usize];
+}
+
+fn alphaBetaScore(
+    state: &State,
+    alpha: ScoreType,
+    beta: ScoreType,
+    depth: usize,
+    time_keeper: &TimeKeeper,
+) -> ScoreType {
+    if time_keeper.isTimeOver() {
+        return 0;
+    }
+    if state.isDone() || depth == 0 {
+        return state.getScore();
+    }
+    let legal_actions = state.legalActions();
+    if legal_actions.is_empty() {
+        return state.getScore();
+    }
+    let mut alpha_local = alpha;
+    for action in legal_actions {
+        let mut next_state = state.clone();
+        next_state.advance(action);
+        let score = -alphaBetaScore(&next_state, -beta, -alpha_local, depth - 1, time_keeper);
+        if time_keeper.isTimeOver() {
+            return 0;
+        }
+        if score > alpha_local {
+            alpha_local = score;
+        }
+        if alpha_local >= beta {
+            return alpha_local;
+        }
+    }
+    return alpha_local;
+}
+
+fn alphaBetaActionWithTimeThreshold(
+    state: &State,
+    depth: usize,
+    time_keeper: &TimeKeeper,
+) -> usize {
+    let mut best_action = 0;
+    let mut alpha = -INF;
+    let mut beta = INF;
+    for action in state.legalActions() {
+        let mut next_state = state.clone();
+        next_state.advance(action);
+        let score = -alphaBetaScore(&next_state, -beta, -alpha, depth, time_keeper);
+        if time_keeper.isTimeOver() {
+            return 0;
+        }
+        if score > alpha {
+            best_action = action;
+            alpha = score;
+        }
+    }
+    best_action
+}
+
+fn iterativeDeepeningAction(state: &State, time_threshold: u64) -> usize {
+    let time_keeper = TimeKeeper::new(time_threshold);
+    // 深さ1の探索も時間内に終わらなかったときは、合法手のうち最初のものを指す
+    let mut best_action = state.legalActions()[0];
+    for depth in 1..1000 {
+        let action = alphaBetaActionWithTimeThreshold(state, depth, &time_keeper);
+        if time_keeper.isTimeOver() {
+            break;
+        }
+        best_action = action;
+    }
+    best_action
+}
+
+pub mod montecalro {
+
+    use crate::randomAction;
+    use crate::State;
+    use crate::WinningStatus;
+    use crate::INF;
+    fn playout(state: &mut State) -> f64 {
+        match state.getWinningStatus() {
+            WinningStatus::WIN => return 1.0,
+            WinningStatus::LOSE => return 0.0,
+            WinningStatus::DRAW => return 0.5,
+            _ => {
+                state.advance(randomAction(state));
+                return 1.0 - playout(state);
+            }
+        }
+    }
+    const C: f64 = 1.0;
+    const EXPAND_THRESHOLD: usize = 10;
+    struct Node {
+        state_: State,
+        w_: f64,
+        pub child_nodes: Vec<Self>,
+        pub n_: f64,
+    }
+    impl Node {
+        pub fn new(state: &State) -> Self {
+            Self {
+                state_: state.clone(),
+                w_: 0.0,
+                child_nodes: Vec::new(),
+                n_: 0.0,
+            }
+        }
+        pub fn evaluate(&mut self) -> f64 {
+            if self.state_.isDone() {
+                let mut value = 0.5;
+                match self.state_.getWinningStatus() {
+                    WinningStatus::WIN => value = 1.0,
+                    WinningStatus::LOSE => value = 0.0,
+                    _ => value = 0.5,
+                }
+                self.w_ += value;
+                self.n_ += 1.0;
+                return value;
+            }
+            if self.child_nodes.is_empty() {
+                let mut state_copy = self.state_.clone();
+                let value = playout(&mut state_copy);
+                self.w_ += value;
+                self.n_ += 1.0;
+                if self.n_ == EXPAND_THRESHOLD as f64 {
+                    self.expand();
+                }
+                return value;
+            } else {
+                let value = 1.0 - self.nextChildNode().evaluate();
+                self.w_ += value;
+                self.n_ += 1.0;
+                return value;
+            }
+        }
+
+        fn expand(&mut self) {
+            let legal_actions = self.state_.legalActions();
+            self.child_nodes.clear();
+            for action in legal_actions {
+                let mut add_state = Self::new(&self.state_);
+                add_state.state_.advance(action);
+                self.child_nodes.push(add_state);
+            }
+        }
+        fn nextChildNode(&mut self) -> &mut Node {
+            for i in 0..self.child_nodes.len() {
+                if self.child_nodes[i].n_ == 0.0 {
+                    return &mut self.child_nodes[i];
+                }
+            }
+            let mut t = 0.0;
+            for child_node in self.child_nodes.iter_mut() {
+                t += child_node.n_;
+            }
+            let mut best_value = -INF as f64;
+            let mut best_action_index = 0;
+            for i in 0..self.child_nodes.len() {
+                let child_node = &self.child_nodes[i];
+                let ucb1_value = 1.0 - child_node.w_ / child_node.n_
+                    + (C as f64) * (2.0 * t.ln() / child_node.n_).sqrt();
+                if ucb1_value > best_value {
+                    best_action_index = i;
+                    best_value = ucb1_value;
+                }
+            }
+            &mut self.child_nodes[best_action_index]
+        }
+    }
+    pub fn mctsAction(state: &State, playout_number: usize) -> usize {
+        let mut root_node = Node::new(state);
+        root_node.expand();
+        for i in 0..playout_number {
+            root_node.evaluate();
+        }
+        let legal_actions = state.legalActions();
+        let mut best_action_searched_number = -1.0;
+        let mut best_action_index = 0;
+        assert_eq!(legal_actions.len(), root_node.child_nodes.len());
+        for i in 0..legal_actions.len() {
+            let n = root_node.child_nodes[i].n_;
+            if n > best_action_searched_number {
+                best_action_index = i;
+                best_action_searched_number = n;
+            }
+        }
+        legal_actions[best_action_index]
+    }
+}
+
+// 人間の入力を行動に変換する。R/L/D/U、RIGHTなどの名前、矢印キーを受け付ける
+// 端末は行単位で入力を渡すので、矢印キーの後にEnterを押してもらう
+fn parseHumanAction(input: &str) -> Result<usize, String> {
+    let input = input.trim();
+    match input {
+        "\x1b[C" => return Ok(0),
+        "\x1b[D" => return Ok(1),
+        "\x1b[B" => return Ok(2),
+        "\x1b[A" => return Ok(3),
+        _ => {}
+    }
+    let name = input.to_uppercase();
+    for action in 0..dstr.len() {
+        if name == dstr[action] || name == dstr[action][..1] {
+            return Ok(action);
+        }
+    }
+    Err(format!(
+        "\"{}\" is not an action. use R/L/D/U or arrow keys",
+        input.escape_debug()
+    ))
+}
+
+// 合法手が入力されるまで標準入力から読み続ける。qか入力の終わりで中断する
+fn humanAction(legal_actions: &[usize]) -> Result<usize, String> {
+    let names: Vec<&str> = legal_actions.iter().map(|&action| dstr[action]).collect();
+    loop {
+        print!("legal actions: {} > ", names.join(" "));
+        io::stdout().flush().map_err(|e| e.to_string())?;
+        let mut line = String::new();
+        if io::stdin()
+            .read_line(&mut line)
+            .map_err(|e| e.to_string())?
+            == 0
+        {
+            return Err("input closed".to_string());
+        }
+        if line.trim() == "q" {
+            return Err("quit".to_string());
+        }
+        match parseHumanAction(&line) {
+            Ok(action) if legal_actions.contains(&action) => return Ok(action),
+            Ok(action) => println!("{} is not a legal action", dstr[action]),
+            Err(e) => println!("{}", e),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+impl Difficulty {
+    pub fn fromName(name: &str) -> Result<Self, String> {
+        match name {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(format!("unknown difficulty: {}", name)),
+        }
+    }
+}
+
+// 人間の相手をするAI。値はプレイアウト数または思考時間(ms)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Opponent {
+    Mcts(usize),
+    IterativeDeepening(u64),
+}
+impl Opponent {
+    pub fn fromName(name: &str, difficulty: Difficulty) -> Result<Self, String> {
+        match name {
+            "mcts" => Ok(Opponent::Mcts(match difficulty {
+                Difficulty::Easy => 10,
+                Difficulty::Normal => 100,
+                Difficulty::Hard => 1000,
+            })),
+            "iterativeDeepening" => Ok(Opponent::IterativeDeepening(match difficulty {
+                Difficulty::Easy => 1,
+                Difficulty::Normal => 10,
+                Difficulty::Hard => 100,
+            })),
+            _ => Err(format!("unknown AI: {}", name)),
+        }
+    }
+    pub fn name(&self) -> String {
+        match self {
+            Opponent::Mcts(playout_number) => format!("mctsAction {}", playout_number),
+            Opponent::IterativeDeepening(time_threshold) => {
+                format!("iterativeDeepeningAction {}", time_threshold)
+            }
+        }
+    }
+    pub fn action(&self, state: &State) -> usize {
+        match *self {
+            Opponent::Mcts(playout_number) => montecalro::mctsAction(state, playout_number),
+            Opponent::IterativeDeepening(time_threshold) => {
+                iterativeDeepeningAction(state, time_threshold)
+            }
+        }
+    }
+}
+
+// human_player_idが0なら人間が先手(A)、1なら後手(B)
+fn playHumanGame(opponent: Opponent, human_player_id: usize, seed: usize) -> Result<(), String> {
+    let mut state = State::new(seed);
+    println!(
+        "you: {} ({}p), opponent: {}",
+        ["A", "B"][human_player_id],
+        human_player_id + 1,
+        opponent.name()
+    );
+    println!("{}", state.toString());
+    while !state.isDone() {
+        let player_id = state.turn_ % 2;
+        let action = if player_id == human_player_id {
+            println!(
+                "{}p (you) ------------------------------------",
+                player_id + 1
+            );
+            humanAction(&state.legalActions())?
+        } else {
+            println!(
+                "{}p ({}) ------------------------------------",
+                player_id + 1,
+                opponent.name()
+            );
+            opponent.action(&state)
+        };
+        println!("action {}", dstr[action]);
+        state.advance(action);
+        println!("{}", state.toString());
+    }
+    let first_player_score = state.getFirstPlayerScoreForWinRate();
+    if first_player_score == 0.5 {
+        println!("DRAW");
+    } else {
+        let winner = if first_player_score == 1.0 { 0 } else { 1 };
+        println!("winner: {}p", winner + 1);
+        if winner == human_player_id {
+            println!("you win!");
+        } else {
+            println!("you lose...");
+        }
+    }
+    Ok(())
+}
+
+const USAGE: &str =
+    "usage: 14_HumanPlay [mcts|iterativeDeepening] [easy|normal|hard] [1p|2p] [seed]";
+
+// 引数から相手のAI、人間の手番、盤面のseedを読む
+fn parseArgs(args: &[String]) -> Result<(Opponent, usize, usize), String> {
+    let arg = |i: usize, default: &str| args.get(i).cloned().unwrap_or(default.to_string());
+    let difficulty = Difficulty::fromName(&arg(2, "normal"))?;
+    let opponent = Opponent::fromName(&arg(1, "mcts"), difficulty)?;
+    let human_player_id = match arg(3, "1p").as_str() {
+        "1p" => 0,
+        "2p" => 1,
+        side => return Err(format!("unknown side: {}", side)),
+    };
+    let seed = arg(4, "0")
+        .parse::<usize>()
+        .map_err(|e| format!("invalid seed: {}", e))?;
+    Ok((opponent, human_player_id, seed))
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    match parseArgs(&args) {
+        Ok((opponent, human_player_id, seed)) => {
+            if let Err(e) = playHumanGame(opponent, human_player_id, seed) {
+                println!("{}", e);
+            }
+        }
+        Err(e) => {
+            println!("{}", e);
+            println!("{}", USAGE);
+        }
+    }
+}
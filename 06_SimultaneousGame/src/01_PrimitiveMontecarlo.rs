@@ -1,5 +1,3 @@
-use std::os::macos::raw::stat;
-
 use montecalro::primitiveMontecarloAction;
 use rand::{distributions::Standard, Rng};
 
@@ -1,5 +1,3 @@
-use std::os::macos::raw::stat;
-
 use montecalro::primitiveMontecarloAction;
 use rand::{distributions::Standard, Rng};
 
@@ -406,72 +404,91 @@ pub mod montecalro {
     }
 }
 
-#[derive(Debug, Default, Clone, Eq, PartialEq)]
-pub struct AlternateMazeState {
-    points_: [[i32; W as usize]; H as usize],
-    turn_: usize,
-    characters_: Vec<Character>,
+// 同時手番ゲームとして最低限必要な操作
+pub trait SimultaneousGame: Clone {
+    fn isDone(&self) -> bool;
+    fn advance(&mut self, action0: usize, action1: usize);
+    fn legalActions(&self, player_id: usize) -> Vec<usize>;
+    fn getWinningStatus(&self) -> WinningStatus;
 }
 
-impl AlternateMazeState {
-    const dx: [i32; 4] = [1, -1, 0, 0];
-    const dy: [i32; 4] = [0, 0, 1, -1];
-    const END_TURN_: usize = END_TURN * 2;
-    pub fn new(base_state: &SimultaneousMazeState, player_id: usize) -> Self {
-        let state = Self {
-            points_: base_state.points_,
-            turn_: base_state.turn_ * 2,
-            characters_: if player_id == 0 {
-                base_state.characters_.clone()
-            } else {
-                vec![base_state.characters_[1], base_state.characters_[0]]
-            },
-        };
-        state
+impl SimultaneousGame for SimultaneousMazeState {
+    fn isDone(&self) -> bool {
+        SimultaneousMazeState::isDone(self)
+    }
+    fn advance(&mut self, action0: usize, action1: usize) {
+        SimultaneousMazeState::advance(self, action0, action1)
+    }
+    fn legalActions(&self, player_id: usize) -> Vec<usize> {
+        SimultaneousMazeState::legalActions(self, player_id)
+    }
+    fn getWinningStatus(&self) -> WinningStatus {
+        SimultaneousMazeState::getWinningStatus(self)
+    }
+}
+
+// 同時手番ゲームを交互手番ゲームとして扱うためのアダプタ
+// player_idが先に行動を決め、その行動は相手が行動を決めるまで元のゲームに適用せずに保留しておく
+// 2人の行動が揃った時点で元のゲームのadvanceを呼ぶので、得点の扱いなどは元のルールのままになる
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct AlternateAdapter<G: SimultaneousGame> {
+    base_state_: G,
+    player_id_: usize,
+    pending_action_: Option<usize>,
+}
+
+impl<G: SimultaneousGame> AlternateAdapter<G> {
+    pub fn new(base_state: &G, player_id: usize) -> Self {
+        Self {
+            base_state_: base_state.clone(),
+            player_id_: player_id,
+            pending_action_: None,
+        }
+    }
+
+    // 次に行動するプレイヤーの、元のゲームでのid
+    pub fn currentPlayer(&self) -> usize {
+        if self.pending_action_.is_none() {
+            self.player_id_
+        } else {
+            (self.player_id_ + 1) % 2
+        }
     }
 
     pub fn isDone(&self) -> bool {
-        self.turn_ == Self::END_TURN_
+        self.base_state_.isDone()
     }
     pub fn advance(&mut self, action: usize) {
-        let character = &mut self.characters_[0];
-        character.x_ += Self::dx[action];
-        character.y_ += Self::dy[action];
-        let point: &mut i32 = &mut self.points_[character.y_ as usize][character.x_ as usize];
-        if *point > 0 {
-            character.game_score_ += *point;
-            *point = 0;
+        match self.pending_action_.take() {
+            None => self.pending_action_ = Some(action),
+            Some(first_action) => {
+                if self.player_id_ == 0 {
+                    self.base_state_.advance(first_action, action);
+                } else {
+                    self.base_state_.advance(action, first_action);
+                }
+            }
         }
-        self.turn_ += 1;
-        self.characters_.swap(0, 1);
     }
+    // 保留中の先手の行動は後手の合法手に影響しない
     pub fn legalActions(&self) -> Vec<usize> {
-        let mut actions = Vec::new();
-        let character = &self.characters_[0];
-        for action in 0..4 {
-            let ty = character.y_ + Self::dy[action];
-            let tx = character.x_ + Self::dx[action];
-            if ty >= 0 && ty < H && tx >= 0 && tx < W {
-                actions.push(action);
-            }
-        }
-        actions
+        self.base_state_.legalActions(self.currentPlayer())
     }
+    // 交互手番ゲームと同じく、手番のプレイヤーから見た勝敗を返す
     pub fn getWinningStatus(&self) -> WinningStatus {
-        if self.isDone() {
-            if self.characters_[0].game_score_ > self.characters_[1].game_score_ {
-                return WinningStatus::FISRT;
-            } else if self.characters_[0].game_score_ < self.characters_[1].game_score_ {
-                return WinningStatus::SECOND;
-            } else {
-                return WinningStatus::DRAW;
-            }
+        let status = self.base_state_.getWinningStatus();
+        if self.currentPlayer() == 0 {
+            return status;
+        }
+        match status {
+            WinningStatus::FISRT => WinningStatus::SECOND,
+            WinningStatus::SECOND => WinningStatus::FISRT,
+            _ => status,
         }
-        WinningStatus::NONE
     }
 }
 
-type AlternateState = AlternateMazeState;
+type AlternateState = AlternateAdapter<SimultaneousMazeState>;
 
 pub mod altanate_montecalro {
     use crate::AlternateState;
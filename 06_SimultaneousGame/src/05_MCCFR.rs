@@ -0,0 +1,856 @@
+use rand::Rng;
+
+const H: i32 = 3;
+const W: i32 = 3;
+const END_TURN: usize = 4;
+const dstr: [&str; 4] = ["RIGHT", "LEFT", "DOWN", "UP"];
+
+pub enum WinningStatus {
+    FISRT,
+    SECOND,
+    DRAW,
+    NONE,
+}
+
+type ScoreType = i32;
+const INF: i32 = 100000000;
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Copy)]
+struct Character {
+    y_: i32,
+    x_: i32,
+    game_score_: i32,
+}
+impl Character {
+    pub fn new(y_: i32, x_: i32) -> Self {
+        Self {
+            y_: y_,
+            x_: x_,
+            game_score_: 0,
+        }
+    }
+}
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct SimultaneousMazeState {
+    points_: [[i32; W as usize]; H as usize],
+    turn_: usize,
+    characters_: Vec<Character>,
+}
+
+impl SimultaneousMazeState {
+    const dx: [i32; 4] = [1, -1, 0, 0];
+    const dy: [i32; 4] = [0, 0, 1, -1];
+    pub fn new(seed: usize) -> Self {
+        let mut state = Self {
+            points_: [[0; W as usize]; H as usize],
+            turn_: 0,
+            characters_: vec![
+                Character::new(H / 2, W / 2 - 1),
+                Character::new(H / 2, W / 2 + 1),
+            ],
+        };
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(seed as u64);
+        for y in 0..H {
+            for x in 0..W {
+                let point = rng.gen_range(0..10);
+                if state.characters_[0].y_ == y && state.characters_[0].x_ == x {
+                    continue;
+                }
+                if state.characters_[1].y_ == y && state.characters_[1].x_ == x {
+                    continue;
+                }
+                let mut ty = y;
+                let mut tx = x;
+                state.points_[ty as usize][tx as usize] = point;
+                tx = W - 1 - x;
+                state.points_[ty as usize][tx as usize] = point;
+            }
+        }
+        state
+    }
+
+    pub fn getWinningStatus(&self) -> WinningStatus {
+        if self.isDone() {
+            if self.characters_[0].game_score_ > self.characters_[1].game_score_ {
+                return WinningStatus::FISRT;
+            } else if self.characters_[0].game_score_ < self.characters_[1].game_score_ {
+                return WinningStatus::SECOND;
+            } else {
+                return WinningStatus::DRAW;
+            }
+        } else {
+            return WinningStatus::NONE;
+        }
+    }
+
+    pub fn isDone(&self) -> bool {
+        self.turn_ == END_TURN
+    }
+    pub fn advance(&mut self, action0: usize, action1: usize) {
+        {
+            let character = &mut self.characters_[0];
+            let action = action0;
+            character.x_ += Self::dx[action];
+            character.y_ += Self::dy[action];
+            let point: &mut i32 = &mut self.points_[character.y_ as usize][character.x_ as usize];
+            if *point > 0 {
+                character.game_score_ += *point;
+            }
+        }
+        {
+            let character = &mut self.characters_[1];
+            let action = action1;
+            character.x_ += Self::dx[action];
+            character.y_ += Self::dy[action];
+            let point: &mut i32 = &mut self.points_[character.y_ as usize][character.x_ as usize];
+            if *point > 0 {
+                character.game_score_ += *point;
+            }
+        }
+        for character_id in 0..self.characters_.len() {
+            let character = &self.characters_[character_id];
+            self.points_[character.y_ as usize][character.x_ as usize] = 0;
+        }
+
+        self.turn_ += 1;
+    }
+    pub fn legalActions(&self, player_id: usize) -> Vec<usize> {
+        let mut actions = Vec::new();
+        let character = &self.characters_[player_id];
+        for action in 0..4 {
+            let ty = character.y_ + Self::dy[action];
+            let tx = character.x_ + Self::dx[action];
+            if ty >= 0 && ty < H && tx >= 0 && tx < W {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+
+    pub fn getFirstPlayerScoreForWinRate(&self) -> f64 {
+        match self.getWinningStatus() {
+            WinningStatus::FISRT => return 1.0,
+            WinningStatus::SECOND => return 0.0,
+            _ => return 0.5,
+        }
+    }
+
+    pub fn getScore(&self) -> ScoreType {
+        return self.characters_[0].game_score_ - self.characters_[1].game_score_;
+    }
+    pub fn getScoreRate(&self) -> f64 {
+        if self.characters_[0].game_score_ + self.characters_[1].game_score_ == 0 {
+            return 0.0;
+        }
+        return self.characters_[0].game_score_ as f64
+            / (self.characters_[0].game_score_ + self.characters_[1].game_score_) as f64;
+    }
+
+    pub fn toString(&self) -> String {
+        let mut ss = String::new();
+        ss += format!("turn:\t{}\n", self.turn_).as_str();
+        for player_id in 0..self.characters_.len() {
+            let chara = &self.characters_[player_id];
+            ss += format!("score({})\t {}\n", player_id, chara.game_score_).as_str();
+        }
+        for h in 0..H {
+            for w in 0..W {
+                let mut is_written = false;
+                for player_id in 0..self.characters_.len() {
+                    let character = &self.characters_[player_id as usize];
+                    if character.y_ == h && character.x_ == w {
+                        if player_id == 0 {
+                            ss += "A";
+                        } else {
+                            ss += "B";
+                        }
+                        is_written = true;
+                    }
+                }
+                if !is_written {
+                    if self.points_[h as usize][w as usize] > 0 {
+                        ss += format!("{}", self.points_[h as usize][w as usize]).as_str();
+                    } else {
+                        ss += ".";
+                    }
+                }
+            }
+            ss += "\n";
+        }
+
+        ss
+    }
+}
+
+type State = SimultaneousMazeState;
+fn randomAction(state: &State, player_id: usize) -> usize {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0);
+    let legal_actions = state.legalActions(player_id);
+    let id = rng.gen_range(0..legal_actions.len());
+    return legal_actions[id as usize];
+}
+
+pub mod montecalro {
+    use rand::Rng;
+
+    use crate::randomAction;
+    use crate::State;
+    use crate::WinningStatus;
+    use crate::INF;
+    fn playout(state: &mut State) -> f64 {
+        match state.getWinningStatus() {
+            WinningStatus::FISRT => return 1.0,
+            WinningStatus::SECOND => return 0.0,
+            WinningStatus::DRAW => return 0.5,
+            _ => {
+                state.advance(randomAction(state, 0), randomAction(state, 1));
+                return playout(state);
+            }
+        }
+    }
+    pub fn primitiveMontecarloAction(
+        state: &State,
+        player_id: usize,
+        playout_number: usize,
+    ) -> usize {
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0);
+
+        let my_legal_actions = state.legalActions(player_id);
+        let opp_legal_actions = state.legalActions((player_id + 1) % 2);
+
+        let mut best_action_index = 0;
+        let mut best_value = -INF as f64;
+        for i in 0..my_legal_actions.len() {
+            let mut value = 0.0;
+            for j in 0..playout_number {
+                let mut next_state = state.clone();
+                if player_id == 0 {
+                    next_state.advance(
+                        my_legal_actions[i],
+                        opp_legal_actions[rng.gen_range(0..opp_legal_actions.len())],
+                    )
+                } else {
+                    next_state.advance(
+                        opp_legal_actions[rng.gen_range(0..opp_legal_actions.len())],
+                        my_legal_actions[i],
+                    )
+                }
+                let player0_win_rate = playout(&mut next_state);
+                let win_rate = if player_id == 0 {
+                    player0_win_rate
+                } else {
+                    1.0 - player0_win_rate
+                };
+                value += win_rate;
+            }
+            if value > best_value {
+                best_value = value;
+                best_action_index = i;
+            }
+        }
+        my_legal_actions[best_action_index]
+    }
+
+    const C: f64 = 1.0;
+    const EXPAND_THRESHOLD: usize = 5;
+    struct Node {
+        state_: State,
+        w_: f64,
+        pub child_nodeses_: Vec<Vec<Self>>,
+        pub n_: f64,
+    }
+    impl Node {
+        pub fn new(state: &State) -> Self {
+            Self {
+                state_: state.clone(),
+                w_: 0.0,
+                child_nodeses_: Vec::new(),
+                n_: 0.0,
+            }
+        }
+        pub fn evaluate(&mut self) -> f64 {
+            if self.state_.isDone() {
+                let mut value = 0.5;
+                match self.state_.getWinningStatus() {
+                    WinningStatus::FISRT => value = 1.0,
+                    WinningStatus::SECOND => value = 0.0,
+                    _ => value = 0.5,
+                }
+                self.w_ += value;
+                self.n_ += 1.0;
+                return value;
+            }
+            if self.child_nodeses_.is_empty() {
+                let mut state_copy = self.state_.clone();
+                let value = playout(&mut state_copy);
+                self.w_ += value;
+                self.n_ += 1.0;
+                if self.n_ == EXPAND_THRESHOLD as f64 {
+                    self.expand();
+                }
+                return value;
+            } else {
+                let value = self.nextChildNode().evaluate();
+                self.w_ += value;
+                self.n_ += 1.0;
+                return value;
+            }
+        }
+
+        fn expand(&mut self) {
+            let legal_actions0 = self.state_.legalActions(0);
+            let legal_actions1 = self.state_.legalActions(1);
+            self.child_nodeses_.clear();
+            for action0 in legal_actions0 {
+                let mut add_nodes = Vec::new();
+                for action1 in legal_actions1.iter() {
+                    let mut add_state = Self::new(&self.state_);
+                    add_state.state_.advance(action0, *action1);
+                    add_nodes.push(add_state);
+                }
+                self.child_nodeses_.push(add_nodes);
+            }
+        }
+        fn nextChildNode(&mut self) -> &mut Node {
+            for i in 0..self.child_nodeses_.len() {
+                for j in 0..self.child_nodeses_[i].len() {
+                    if self.child_nodeses_[i][j].n_ == 0.0 {
+                        return &mut self.child_nodeses_[i][j];
+                    }
+                }
+            }
+
+            let mut t = 0.0;
+            for i in 0..self.child_nodeses_.len() {
+                for j in 0..self.child_nodeses_[i].len() {
+                    t += self.child_nodeses_[i][j].n_;
+                }
+            }
+
+            let mut best_value = -INF as f64;
+            let mut best_is = [0, 0];
+            for i in 0..self.child_nodeses_.len() {
+                let child_nodes = &self.child_nodeses_[i];
+                let mut w = 0.0;
+                let mut n = 0.0;
+                for j in 0..child_nodes.len() {
+                    w += child_nodes[j].w_;
+                    n += child_nodes[j].n_;
+                }
+                let ucb1_value = w / n + (C as f64) * (2.0 * t.ln() / n).sqrt();
+                if ucb1_value > best_value {
+                    best_is[0] = i;
+                    best_value = ucb1_value;
+                }
+            }
+            for j in 0..self.child_nodeses_[0].len() {
+                let mut w = 0.0;
+                let mut n = 0.0;
+                for i in 0..self.child_nodeses_.len() {
+                    let child_node = &self.child_nodeses_[i][j];
+                    w += child_node.w_;
+                    n += child_node.n_;
+                }
+                w = 1.0 - w;
+                let ucb1_value = w / n + (C as f64) * (2.0 * t.ln() / n).sqrt();
+                if ucb1_value > best_value {
+                    best_is[1] = j;
+                    best_value = ucb1_value;
+                }
+            }
+            best_value = -INF as f64;
+            &mut self.child_nodeses_[best_is[0]][best_is[1]]
+        }
+    }
+    pub fn ductAction(state: &State, player_id: usize, playout_number: usize) -> usize {
+        let mut root_node = Node::new(state);
+        root_node.expand();
+        for i in 0..playout_number {
+            root_node.evaluate();
+        }
+        let legal_actions = state.legalActions(player_id);
+        let i_size = root_node.child_nodeses_.len();
+        let j_size = root_node.child_nodeses_[0].len();
+        if player_id == 0 {
+            let mut best_action_searched_number = -1.0;
+            let mut best_action_index = 0;
+            for i in 0..i_size {
+                let mut n = 0.0;
+                for j in 0..j_size {
+                    n += root_node.child_nodeses_[i][j].n_;
+                }
+                if n > best_action_searched_number {
+                    best_action_index = i;
+                    best_action_searched_number = n;
+                }
+            }
+            return legal_actions[best_action_index];
+        } else {
+            let mut best_action_searched_number = -1.0;
+            let mut best_j = 0;
+            for j in 0..j_size {
+                let mut n = 0.0;
+                for i in 0..i_size {
+                    n += root_node.child_nodeses_[i][j].n_;
+                }
+                if n > best_action_searched_number {
+                    best_j = j;
+                    best_action_searched_number = n;
+                }
+            }
+            return legal_actions[best_j];
+        }
+    }
+}
+
+// 同時手番ゲームとして最低限必要な操作
+pub trait SimultaneousGame: Clone {
+    fn isDone(&self) -> bool;
+    fn advance(&mut self, action0: usize, action1: usize);
+    fn legalActions(&self, player_id: usize) -> Vec<usize>;
+    fn getWinningStatus(&self) -> WinningStatus;
+}
+
+impl SimultaneousGame for SimultaneousMazeState {
+    fn isDone(&self) -> bool {
+        SimultaneousMazeState::isDone(self)
+    }
+    fn advance(&mut self, action0: usize, action1: usize) {
+        SimultaneousMazeState::advance(self, action0, action1)
+    }
+    fn legalActions(&self, player_id: usize) -> Vec<usize> {
+        SimultaneousMazeState::legalActions(self, player_id)
+    }
+    fn getWinningStatus(&self) -> WinningStatus {
+        SimultaneousMazeState::getWinningStatus(self)
+    }
+}
+
+// 同時手番ゲームを交互手番ゲームとして扱うためのアダプタ
+// player_idが先に行動を決め、その行動は相手が行動を決めるまで元のゲームに適用せずに保留しておく
+// 2人の行動が揃った時点で元のゲームのadvanceを呼ぶので、得点の扱いなどは元のルールのままになる
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct AlternateAdapter<G: SimultaneousGame> {
+    base_state_: G,
+    player_id_: usize,
+    pending_action_: Option<usize>,
+}
+
+impl<G: SimultaneousGame> AlternateAdapter<G> {
+    pub fn new(base_state: &G, player_id: usize) -> Self {
+        Self {
+            base_state_: base_state.clone(),
+            player_id_: player_id,
+            pending_action_: None,
+        }
+    }
+
+    // 次に行動するプレイヤーの、元のゲームでのid
+    pub fn currentPlayer(&self) -> usize {
+        if self.pending_action_.is_none() {
+            self.player_id_
+        } else {
+            (self.player_id_ + 1) % 2
+        }
+    }
+
+    pub fn isDone(&self) -> bool {
+        self.base_state_.isDone()
+    }
+    pub fn advance(&mut self, action: usize) {
+        match self.pending_action_.take() {
+            None => self.pending_action_ = Some(action),
+            Some(first_action) => {
+                if self.player_id_ == 0 {
+                    self.base_state_.advance(first_action, action);
+                } else {
+                    self.base_state_.advance(action, first_action);
+                }
+            }
+        }
+    }
+    // 保留中の先手の行動は後手の合法手に影響しない
+    pub fn legalActions(&self) -> Vec<usize> {
+        self.base_state_.legalActions(self.currentPlayer())
+    }
+    // 交互手番ゲームと同じく、手番のプレイヤーから見た勝敗を返す
+    pub fn getWinningStatus(&self) -> WinningStatus {
+        let status = self.base_state_.getWinningStatus();
+        if self.currentPlayer() == 0 {
+            return status;
+        }
+        match status {
+            WinningStatus::FISRT => WinningStatus::SECOND,
+            WinningStatus::SECOND => WinningStatus::FISRT,
+            _ => status,
+        }
+    }
+}
+
+type AlternateState = AlternateAdapter<SimultaneousMazeState>;
+
+pub mod altanate_montecalro {
+    use crate::AlternateState;
+    use crate::State;
+    use crate::WinningStatus;
+    use crate::INF;
+    use rand::Rng;
+
+    fn randomAction(state: &AlternateState) -> usize {
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0);
+        let legal_actions = state.legalActions();
+        let id = rng.gen_range(0..legal_actions.len());
+        return legal_actions[id as usize];
+    }
+
+    fn playout(state: &mut AlternateState) -> f64 {
+        match state.getWinningStatus() {
+            WinningStatus::FISRT => return 1.0,
+            WinningStatus::SECOND => return 0.0,
+            WinningStatus::DRAW => return 0.5,
+            _ => {
+                state.advance(randomAction(state));
+                return 1.0 - playout(state);
+            }
+        }
+    }
+
+    const C: f64 = 1.0;
+    const EXPAND_THRESHOLD: usize = 10;
+    struct Node {
+        state_: AlternateState,
+        w_: f64,
+        pub child_nodes: Vec<Self>,
+        pub n_: f64,
+    }
+    impl Node {
+        pub fn new(state: &AlternateState) -> Self {
+            Self {
+                state_: state.clone(),
+                w_: 0.0,
+                child_nodes: Vec::new(),
+                n_: 0.0,
+            }
+        }
+        pub fn evaluate(&mut self) -> f64 {
+            if self.state_.isDone() {
+                let mut value = 0.5;
+                match self.state_.getWinningStatus() {
+                    WinningStatus::FISRT => value = 1.0,
+                    WinningStatus::SECOND => value = 0.0,
+                    _ => value = 0.5,
+                }
+                self.w_ += value;
+                self.n_ += 1.0;
+                return value;
+            }
+            if self.child_nodes.is_empty() {
+                let mut state_copy = self.state_.clone();
+                let value = playout(&mut state_copy);
+                self.w_ += value;
+                self.n_ += 1.0;
+                if self.n_ == EXPAND_THRESHOLD as f64 {
+                    self.expand();
+                }
+                return value;
+            } else {
+                let value = 1.0 - self.nextChildNode().evaluate();
+                self.w_ += value;
+                self.n_ += 1.0;
+                return value;
+            }
+        }
+
+        fn expand(&mut self) {
+            let legal_actions = self.state_.legalActions();
+            self.child_nodes.clear();
+            for action in legal_actions {
+                let mut add_state = Self::new(&self.state_);
+                add_state.state_.advance(action);
+                self.child_nodes.push(add_state);
+            }
+        }
+        fn nextChildNode(&mut self) -> &mut Node {
+            for i in 0..self.child_nodes.len() {
+                if self.child_nodes[i].n_ == 0.0 {
+                    return &mut self.child_nodes[i];
+                }
+            }
+            let mut t = 0.0;
+            for child_node in self.child_nodes.iter_mut() {
+                t += child_node.n_;
+            }
+            let mut best_value = -INF as f64;
+            let mut best_action_index = 0;
+            for i in 0..self.child_nodes.len() {
+                let child_node = &self.child_nodes[i];
+                let ucb1_value = 1.0 - child_node.w_ / child_node.n_
+                    + (C as f64) * (2.0 * t.ln() / child_node.n_).sqrt();
+                if ucb1_value > best_value {
+                    best_action_index = i;
+                    best_value = ucb1_value;
+                }
+            }
+            &mut self.child_nodes[best_action_index]
+        }
+    }
+    pub fn mctsAction(base_state: &State, player_id: usize, playout_number: usize) -> usize {
+        let state = AlternateState::new(base_state, player_id);
+        let mut root_node = Node::new(&state);
+        root_node.expand();
+        for i in 0..playout_number {
+            root_node.evaluate();
+        }
+        let legal_actions = state.legalActions();
+        let mut best_action_searched_number = -1.0;
+        let mut best_action_index = 0;
+        assert_eq!(legal_actions.len(), root_node.child_nodes.len());
+        for i in 0..legal_actions.len() {
+            let n = root_node.child_nodes[i].n_;
+            if n > best_action_searched_number {
+                best_action_index = i;
+                best_action_searched_number = n;
+            }
+        }
+        legal_actions[best_action_index]
+    }
+}
+
+pub mod cfr {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use lazy_static::lazy_static;
+    use rand::Rng;
+
+    use crate::State;
+    use crate::WinningStatus;
+
+    // 同時手番ゲームでは盤面は両者に見えていて、見えないのは相手の今回の行動だけなので
+    // (局面, プレイヤー)をそのまま情報集合として扱う
+    struct InfoSet {
+        regret_sum_: Vec<f64>,
+        strategy_sum_: Vec<f64>,
+    }
+    impl InfoSet {
+        fn new(action_number: usize) -> Self {
+            Self {
+                regret_sum_: vec![0.0; action_number],
+                strategy_sum_: vec![0.0; action_number],
+            }
+        }
+        // Regret Matching
+        fn strategy(&self) -> Vec<f64> {
+            let mut strategy: Vec<f64> = self.regret_sum_.iter().map(|r| r.max(0.0)).collect();
+            let sum: f64 = strategy.iter().sum();
+            let action_number = strategy.len();
+            for p in strategy.iter_mut() {
+                *p = if sum > 0.0 {
+                    *p / sum
+                } else {
+                    1.0 / action_number as f64
+                };
+            }
+            strategy
+        }
+        fn averageStrategy(&self) -> Vec<f64> {
+            let sum: f64 = self.strategy_sum_.iter().sum();
+            let action_number = self.strategy_sum_.len();
+            self.strategy_sum_
+                .iter()
+                .map(|s| {
+                    if sum > 0.0 {
+                        s / sum
+                    } else {
+                        1.0 / action_number as f64
+                    }
+                })
+                .collect()
+        }
+    }
+
+    fn sampleAction(strategy: &Vec<f64>, rng: &mut rand::rngs::StdRng) -> usize {
+        let r: f64 = rng.gen();
+        let mut sum = 0.0;
+        for i in 0..strategy.len() {
+            sum += strategy[i];
+            if r < sum {
+                return i;
+            }
+        }
+        strategy.len() - 1
+    }
+
+    // External Sampling MCCFR
+    pub struct MCCFR {
+        info_sets_: HashMap<(State, usize), InfoSet>,
+        rng_: rand::rngs::StdRng,
+    }
+    impl MCCFR {
+        pub fn new(seed: u64) -> Self {
+            Self {
+                info_sets_: HashMap::new(),
+                rng_: rand::SeedableRng::seed_from_u64(seed),
+            }
+        }
+
+        fn strategy(&mut self, state: &State, player_id: usize) -> Vec<f64> {
+            let action_number = state.legalActions(player_id).len();
+            self.info_sets_
+                .entry((state.clone(), player_id))
+                .or_insert_with(|| InfoSet::new(action_number))
+                .strategy()
+        }
+
+        // traverserの行動はすべて展開し、相手の行動は現在の戦略からひとつだけサンプリングする
+        // 戻り値はtraverserから見た勝率
+        fn traverse(&mut self, state: &State, traverser: usize) -> f64 {
+            if state.isDone() {
+                let value = match state.getWinningStatus() {
+                    WinningStatus::FISRT => 1.0,
+                    WinningStatus::SECOND => 0.0,
+                    _ => 0.5,
+                };
+                return if traverser == 0 { value } else { 1.0 - value };
+            }
+            let opponent = (traverser + 1) % 2;
+            let my_legal_actions = state.legalActions(traverser);
+            let opp_legal_actions = state.legalActions(opponent);
+            let strategy = self.strategy(state, traverser);
+            let opp_strategy = self.strategy(state, opponent);
+            {
+                let opp_info_set = self.info_sets_.get_mut(&(state.clone(), opponent)).unwrap();
+                for i in 0..opp_strategy.len() {
+                    opp_info_set.strategy_sum_[i] += opp_strategy[i];
+                }
+            }
+            let opp_action = opp_legal_actions[sampleAction(&opp_strategy, &mut self.rng_)];
+
+            let mut values = vec![0.0; my_legal_actions.len()];
+            let mut node_value = 0.0;
+            for i in 0..my_legal_actions.len() {
+                let mut next_state = state.clone();
+                if traverser == 0 {
+                    next_state.advance(my_legal_actions[i], opp_action);
+                } else {
+                    next_state.advance(opp_action, my_legal_actions[i]);
+                }
+                values[i] = self.traverse(&next_state, traverser);
+                node_value += strategy[i] * values[i];
+            }
+            let info_set = self
+                .info_sets_
+                .get_mut(&(state.clone(), traverser))
+                .unwrap();
+            for i in 0..values.len() {
+                info_set.regret_sum_[i] += values[i] - node_value;
+            }
+            node_value
+        }
+
+        pub fn train(&mut self, state: &State, iteration_number: usize) {
+            for _ in 0..iteration_number {
+                for traverser in 0..2 {
+                    self.traverse(state, traverser);
+                }
+            }
+        }
+
+        // 学習済みの平均戦略。legalActions(player_id)の順に並ぶ
+        pub fn averageStrategy(&self, state: &State, player_id: usize) -> Option<Vec<f64>> {
+            self.info_sets_
+                .get(&(state.clone(), player_id))
+                .map(|info_set| info_set.averageStrategy())
+        }
+
+        pub fn infoSetNumber(&self) -> usize {
+            self.info_sets_.len()
+        }
+
+        // 平均戦略に従って行動をサンプリングする
+        pub fn sampleAction(&mut self, state: &State, player_id: usize) -> Option<usize> {
+            let strategy = self.averageStrategy(state, player_id)?;
+            let index = sampleAction(&strategy, &mut self.rng_);
+            Some(state.legalActions(player_id)[index])
+        }
+    }
+
+    lazy_static! {
+        static ref trainer: Mutex<MCCFR> = Mutex::new(MCCFR::new(0));
+    }
+
+    // 学習済みの平均戦略を引くだけのエージェント
+    // 未学習の局面を渡されたときはその局面を根としてiteration_number回学習してから引く
+    pub fn cfrAction(state: &State, player_id: usize, iteration_number: usize) -> usize {
+        let mut trainer_ = trainer.lock().unwrap();
+        if trainer_.averageStrategy(state, player_id).is_none() {
+            trainer_.train(state, iteration_number);
+        }
+        trainer_.sampleAction(state, player_id).unwrap()
+    }
+}
+
+type AIFunction = fn(&State, usize) -> usize;
+type StringAIPair = (String, AIFunction);
+
+fn testFirstPlayerWinRate(ais: [StringAIPair; 2], game_number: usize) {
+    let mut first_player_win_rate = 0.0;
+    for i in 0..game_number {
+        let mut best_state = State::new(i);
+        let mut state = best_state.clone();
+        let first_ai = &ais[0];
+        let second_ai = &ais[1];
+        loop {
+            state.advance(first_ai.1(&state, 0), second_ai.1(&state, 1));
+            if state.isDone() {
+                break;
+            }
+        }
+        let mut win_rate_point = state.getFirstPlayerScoreForWinRate();
+
+        if win_rate_point >= 0.0 {
+            state.toString();
+        }
+        first_player_win_rate += win_rate_point;
+        println!("i {} w {} ", i, first_player_win_rate / (i + 1) as f64);
+    }
+    first_player_win_rate /= (game_number) as f64;
+    println!(
+        "Winning rate of {} to {} :\t {}",
+        ais[0].0, ais[1].0, first_player_win_rate
+    );
+}
+fn playGame(ais: [StringAIPair; 2], seed: usize) {
+    let mut state = State::new(seed);
+    println!("{}", state.toString());
+    while !state.isDone() {
+        let actions = vec![ais[0].1(&state, 0), ais[1].1(&state, 1)];
+        println!("actions {} {}", dstr[actions[0]], dstr[actions[1]]);
+        state.advance(actions[0], actions[1]);
+        println!("{}", state.toString());
+    }
+}
+
+fn main() {
+    let f0: AIFunction =
+        |state: &State, player_id: usize| return cfr::cfrAction(state, player_id, 1000);
+    let f1: AIFunction =
+        |state: &State, player_id: usize| return montecalro::ductAction(state, player_id, 50);
+    let f2: AIFunction = |state: &State, player_id: usize| {
+        return altanate_montecalro::mctsAction(state, player_id, 50);
+    };
+    testFirstPlayerWinRate(
+        [
+            ("cfrAction".to_string(), f0),
+            ("ductAction".to_string(), f1),
+        ],
+        100,
+    );
+    testFirstPlayerWinRate(
+        [
+            ("cfrAction".to_string(), f0),
+            ("mctsAction".to_string(), f2),
+        ],
+        100,
+    );
+}
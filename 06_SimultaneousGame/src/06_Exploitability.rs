@@ -0,0 +1,978 @@
+use rand::Rng;
+
+const H: i32 = 3;
+const W: i32 = 3;
+const END_TURN: usize = 4;
+const dstr: [&str; 4] = ["RIGHT", "LEFT", "DOWN", "UP"];
+
+pub enum WinningStatus {
+    FISRT,
+    SECOND,
+    DRAW,
+    NONE,
+}
+
+type ScoreType = i32;
+const INF: i32 = 100000000;
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Copy)]
+struct Character {
+    y_: i32,
+    x_: i32,
+    game_score_: i32,
+}
+impl Character {
+    pub fn new(y_: i32, x_: i32) -> Self {
+        Self {
+            y_: y_,
+            x_: x_,
+            game_score_: 0,
+        }
+    }
+}
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct SimultaneousMazeState {
+    points_: [[i32; W as usize]; H as usize],
+    turn_: usize,
+    characters_: Vec<Character>,
+}
+
+impl SimultaneousMazeState {
+    const dx: [i32; 4] = [1, -1, 0, 0];
+    const dy: [i32; 4] = [0, 0, 1, -1];
+    pub fn new(seed: usize) -> Self {
+        let mut state = Self {
+            points_: [[0; W as usize]; H as usize],
+            turn_: 0,
+            characters_: vec![
+                Character::new(H / 2, W / 2 - 1),
+                Character::new(H / 2, W / 2 + 1),
+            ],
+        };
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(seed as u64);
+        for y in 0..H {
+            for x in 0..W {
+                let point = rng.gen_range(0..10);
+                if state.characters_[0].y_ == y && state.characters_[0].x_ == x {
+                    continue;
+                }
+                if state.characters_[1].y_ == y && state.characters_[1].x_ == x {
+                    continue;
+                }
+                let mut ty = y;
+                let mut tx = x;
+                state.points_[ty as usize][tx as usize] = point;
+                tx = W - 1 - x;
+                state.points_[ty as usize][tx as usize] = point;
+            }
+        }
+        state
+    }
+
+    pub fn getWinningStatus(&self) -> WinningStatus {
+        if self.isDone() {
+            if self.characters_[0].game_score_ > self.characters_[1].game_score_ {
+                return WinningStatus::FISRT;
+            } else if self.characters_[0].game_score_ < self.characters_[1].game_score_ {
+                return WinningStatus::SECOND;
+            } else {
+                return WinningStatus::DRAW;
+            }
+        } else {
+            return WinningStatus::NONE;
+        }
+    }
+
+    pub fn isDone(&self) -> bool {
+        self.turn_ == END_TURN
+    }
+    pub fn advance(&mut self, action0: usize, action1: usize) {
+        {
+            let character = &mut self.characters_[0];
+            let action = action0;
+            character.x_ += Self::dx[action];
+            character.y_ += Self::dy[action];
+            let point: &mut i32 = &mut self.points_[character.y_ as usize][character.x_ as usize];
+            if *point > 0 {
+                character.game_score_ += *point;
+            }
+        }
+        {
+            let character = &mut self.characters_[1];
+            let action = action1;
+            character.x_ += Self::dx[action];
+            character.y_ += Self::dy[action];
+            let point: &mut i32 = &mut self.points_[character.y_ as usize][character.x_ as usize];
+            if *point > 0 {
+                character.game_score_ += *point;
+            }
+        }
+        for character_id in 0..self.characters_.len() {
+            let character = &self.characters_[character_id];
+            self.points_[character.y_ as usize][character.x_ as usize] = 0;
+        }
+
+        self.turn_ += 1;
+    }
+    pub fn legalActions(&self, player_id: usize) -> Vec<usize> {
+        let mut actions = Vec::new();
+        let character = &self.characters_[player_id];
+        for action in 0..4 {
+            let ty = character.y_ + Self::dy[action];
+            let tx = character.x_ + Self::dx[action];
+            if ty >= 0 && ty < H && tx >= 0 && tx < W {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+
+    pub fn getFirstPlayerScoreForWinRate(&self) -> f64 {
+        match self.getWinningStatus() {
+            WinningStatus::FISRT => return 1.0,
+            WinningStatus::SECOND => return 0.0,
+            _ => return 0.5,
+        }
+    }
+
+    pub fn getScore(&self) -> ScoreType {
+        return self.characters_[0].game_score_ - self.characters_[1].game_score_;
+    }
+    pub fn getScoreRate(&self) -> f64 {
+        if self.characters_[0].game_score_ + self.characters_[1].game_score_ == 0 {
+            return 0.0;
+        }
+        return self.characters_[0].game_score_ as f64
+            / (self.characters_[0].game_score_ + self.characters_[1].game_score_) as f64;
+    }
+
+    pub fn toString(&self) -> String {
+        let mut ss = String::new();
+        ss += format!("turn:\t{}\n", self.turn_).as_str();
+        for player_id in 0..self.characters_.len() {
+            let chara = &self.characters_[player_id];
+            ss += format!("score({})\t {}\n", player_id, chara.game_score_).as_str();
+        }
+        for h in 0..H {
+            for w in 0..W {
+                let mut is_written = false;
+                for player_id in 0..self.characters_.len() {
+                    let character = &self.characters_[player_id as usize];
+                    if character.y_ == h && character.x_ == w {
+                        if player_id == 0 {
+                            ss += "A";
+                        } else {
+                            ss += "B";
+                        }
+                        is_written = true;
+                    }
+                }
+                if !is_written {
+                    if self.points_[h as usize][w as usize] > 0 {
+                        ss += format!("{}", self.points_[h as usize][w as usize]).as_str();
+                    } else {
+                        ss += ".";
+                    }
+                }
+            }
+            ss += "\n";
+        }
+
+        ss
+    }
+}
+
+type State = SimultaneousMazeState;
+fn randomAction(state: &State, player_id: usize) -> usize {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0);
+    let legal_actions = state.legalActions(player_id);
+    let id = rng.gen_range(0..legal_actions.len());
+    return legal_actions[id as usize];
+}
+
+pub mod montecalro {
+    use rand::Rng;
+
+    use crate::randomAction;
+    use crate::State;
+    use crate::WinningStatus;
+    use crate::INF;
+    fn playout(state: &mut State) -> f64 {
+        match state.getWinningStatus() {
+            WinningStatus::FISRT => return 1.0,
+            WinningStatus::SECOND => return 0.0,
+            WinningStatus::DRAW => return 0.5,
+            _ => {
+                state.advance(randomAction(state, 0), randomAction(state, 1));
+                return playout(state);
+            }
+        }
+    }
+    pub fn primitiveMontecarloAction(
+        state: &State,
+        player_id: usize,
+        playout_number: usize,
+    ) -> usize {
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0);
+
+        let my_legal_actions = state.legalActions(player_id);
+        let opp_legal_actions = state.legalActions((player_id + 1) % 2);
+
+        let mut best_action_index = 0;
+        let mut best_value = -INF as f64;
+        for i in 0..my_legal_actions.len() {
+            let mut value = 0.0;
+            for j in 0..playout_number {
+                let mut next_state = state.clone();
+                if player_id == 0 {
+                    next_state.advance(
+                        my_legal_actions[i],
+                        opp_legal_actions[rng.gen_range(0..opp_legal_actions.len())],
+                    )
+                } else {
+                    next_state.advance(
+                        opp_legal_actions[rng.gen_range(0..opp_legal_actions.len())],
+                        my_legal_actions[i],
+                    )
+                }
+                let player0_win_rate = playout(&mut next_state);
+                let win_rate = if player_id == 0 {
+                    player0_win_rate
+                } else {
+                    1.0 - player0_win_rate
+                };
+                value += win_rate;
+            }
+            if value > best_value {
+                best_value = value;
+                best_action_index = i;
+            }
+        }
+        my_legal_actions[best_action_index]
+    }
+
+    const C: f64 = 1.0;
+    const EXPAND_THRESHOLD: usize = 5;
+    struct Node {
+        state_: State,
+        w_: f64,
+        pub child_nodeses_: Vec<Vec<Self>>,
+        pub n_: f64,
+    }
+    impl Node {
+        pub fn new(state: &State) -> Self {
+            Self {
+                state_: state.clone(),
+                w_: 0.0,
+                child_nodeses_: Vec::new(),
+                n_: 0.0,
+            }
+        }
+        pub fn evaluate(&mut self) -> f64 {
+            if self.state_.isDone() {
+                let mut value = 0.5;
+                match self.state_.getWinningStatus() {
+                    WinningStatus::FISRT => value = 1.0,
+                    WinningStatus::SECOND => value = 0.0,
+                    _ => value = 0.5,
+                }
+                self.w_ += value;
+                self.n_ += 1.0;
+                return value;
+            }
+            if self.child_nodeses_.is_empty() {
+                let mut state_copy = self.state_.clone();
+                let value = playout(&mut state_copy);
+                self.w_ += value;
+                self.n_ += 1.0;
+                if self.n_ == EXPAND_THRESHOLD as f64 {
+                    self.expand();
+                }
+                return value;
+            } else {
+                let value = self.nextChildNode().evaluate();
+                self.w_ += value;
+                self.n_ += 1.0;
+                return value;
+            }
+        }
+
+        fn expand(&mut self) {
+            let legal_actions0 = self.state_.legalActions(0);
+            let legal_actions1 = self.state_.legalActions(1);
+            self.child_nodeses_.clear();
+            for action0 in legal_actions0 {
+                let mut add_nodes = Vec::new();
+                for action1 in legal_actions1.iter() {
+                    let mut add_state = Self::new(&self.state_);
+                    add_state.state_.advance(action0, *action1);
+                    add_nodes.push(add_state);
+                }
+                self.child_nodeses_.push(add_nodes);
+            }
+        }
+        fn nextChildNode(&mut self) -> &mut Node {
+            for i in 0..self.child_nodeses_.len() {
+                for j in 0..self.child_nodeses_[i].len() {
+                    if self.child_nodeses_[i][j].n_ == 0.0 {
+                        return &mut self.child_nodeses_[i][j];
+                    }
+                }
+            }
+
+            let mut t = 0.0;
+            for i in 0..self.child_nodeses_.len() {
+                for j in 0..self.child_nodeses_[i].len() {
+                    t += self.child_nodeses_[i][j].n_;
+                }
+            }
+
+            let mut best_value = -INF as f64;
+            let mut best_is = [0, 0];
+            for i in 0..self.child_nodeses_.len() {
+                let child_nodes = &self.child_nodeses_[i];
+                let mut w = 0.0;
+                let mut n = 0.0;
+                for j in 0..child_nodes.len() {
+                    w += child_nodes[j].w_;
+                    n += child_nodes[j].n_;
+                }
+                let ucb1_value = w / n + (C as f64) * (2.0 * t.ln() / n).sqrt();
+                if ucb1_value > best_value {
+                    best_is[0] = i;
+                    best_value = ucb1_value;
+                }
+            }
+            for j in 0..self.child_nodeses_[0].len() {
+                let mut w = 0.0;
+                let mut n = 0.0;
+                for i in 0..self.child_nodeses_.len() {
+                    let child_node = &self.child_nodeses_[i][j];
+                    w += child_node.w_;
+                    n += child_node.n_;
+                }
+                w = 1.0 - w;
+                let ucb1_value = w / n + (C as f64) * (2.0 * t.ln() / n).sqrt();
+                if ucb1_value > best_value {
+                    best_is[1] = j;
+                    best_value = ucb1_value;
+                }
+            }
+            best_value = -INF as f64;
+            &mut self.child_nodeses_[best_is[0]][best_is[1]]
+        }
+    }
+    pub fn ductAction(state: &State, player_id: usize, playout_number: usize) -> usize {
+        let mut root_node = Node::new(state);
+        root_node.expand();
+        for i in 0..playout_number {
+            root_node.evaluate();
+        }
+        let legal_actions = state.legalActions(player_id);
+        let i_size = root_node.child_nodeses_.len();
+        let j_size = root_node.child_nodeses_[0].len();
+        if player_id == 0 {
+            let mut best_action_searched_number = -1.0;
+            let mut best_action_index = 0;
+            for i in 0..i_size {
+                let mut n = 0.0;
+                for j in 0..j_size {
+                    n += root_node.child_nodeses_[i][j].n_;
+                }
+                if n > best_action_searched_number {
+                    best_action_index = i;
+                    best_action_searched_number = n;
+                }
+            }
+            return legal_actions[best_action_index];
+        } else {
+            let mut best_action_searched_number = -1.0;
+            let mut best_j = 0;
+            for j in 0..j_size {
+                let mut n = 0.0;
+                for i in 0..i_size {
+                    n += root_node.child_nodeses_[i][j].n_;
+                }
+                if n > best_action_searched_number {
+                    best_j = j;
+                    best_action_searched_number = n;
+                }
+            }
+            return legal_actions[best_j];
+        }
+    }
+}
+
+// 同時手番ゲームとして最低限必要な操作
+pub trait SimultaneousGame: Clone {
+    fn isDone(&self) -> bool;
+    fn advance(&mut self, action0: usize, action1: usize);
+    fn legalActions(&self, player_id: usize) -> Vec<usize>;
+    fn getWinningStatus(&self) -> WinningStatus;
+}
+
+impl SimultaneousGame for SimultaneousMazeState {
+    fn isDone(&self) -> bool {
+        SimultaneousMazeState::isDone(self)
+    }
+    fn advance(&mut self, action0: usize, action1: usize) {
+        SimultaneousMazeState::advance(self, action0, action1)
+    }
+    fn legalActions(&self, player_id: usize) -> Vec<usize> {
+        SimultaneousMazeState::legalActions(self, player_id)
+    }
+    fn getWinningStatus(&self) -> WinningStatus {
+        SimultaneousMazeState::getWinningStatus(self)
+    }
+}
+
+// 同時手番ゲームを交互手番ゲームとして扱うためのアダプタ
+// player_idが先に行動を決め、その行動は相手が行動を決めるまで元のゲームに適用せずに保留しておく
+// 2人の行動が揃った時点で元のゲームのadvanceを呼ぶので、得点の扱いなどは元のルールのままになる
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct AlternateAdapter<G: SimultaneousGame> {
+    base_state_: G,
+    player_id_: usize,
+    pending_action_: Option<usize>,
+}
+
+impl<G: SimultaneousGame> AlternateAdapter<G> {
+    pub fn new(base_state: &G, player_id: usize) -> Self {
+        Self {
+            base_state_: base_state.clone(),
+            player_id_: player_id,
+            pending_action_: None,
+        }
+    }
+
+    // 次に行動するプレイヤーの、元のゲームでのid
+    pub fn currentPlayer(&self) -> usize {
+        if self.pending_action_.is_none() {
+            self.player_id_
+        } else {
+            (self.player_id_ + 1) % 2
+        }
+    }
+
+    pub fn isDone(&self) -> bool {
+        self.base_state_.isDone()
+    }
+    pub fn advance(&mut self, action: usize) {
+        match self.pending_action_.take() {
+            None => self.pending_action_ = Some(action),
+            Some(first_action) => {
+                if self.player_id_ == 0 {
+                    self.base_state_.advance(first_action, action);
+                } else {
+                    self.base_state_.advance(action, first_action);
+                }
+            }
+        }
+    }
+    // 保留中の先手の行動は後手の合法手に影響しない
+    pub fn legalActions(&self) -> Vec<usize> {
+        self.base_state_.legalActions(self.currentPlayer())
+    }
+    // 交互手番ゲームと同じく、手番のプレイヤーから見た勝敗を返す
+    pub fn getWinningStatus(&self) -> WinningStatus {
+        let status = self.base_state_.getWinningStatus();
+        if self.currentPlayer() == 0 {
+            return status;
+        }
+        match status {
+            WinningStatus::FISRT => WinningStatus::SECOND,
+            WinningStatus::SECOND => WinningStatus::FISRT,
+            _ => status,
+        }
+    }
+}
+
+type AlternateState = AlternateAdapter<SimultaneousMazeState>;
+
+pub mod altanate_montecalro {
+    use crate::AlternateState;
+    use crate::State;
+    use crate::WinningStatus;
+    use crate::INF;
+    use rand::Rng;
+
+    fn randomAction(state: &AlternateState) -> usize {
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0);
+        let legal_actions = state.legalActions();
+        let id = rng.gen_range(0..legal_actions.len());
+        return legal_actions[id as usize];
+    }
+
+    fn playout(state: &mut AlternateState) -> f64 {
+        match state.getWinningStatus() {
+            WinningStatus::FISRT => return 1.0,
+            WinningStatus::SECOND => return 0.0,
+            WinningStatus::DRAW => return 0.5,
+            _ => {
+                state.advance(randomAction(state));
+                return 1.0 - playout(state);
+            }
+        }
+    }
+
+    const C: f64 = 1.0;
+    const EXPAND_THRESHOLD: usize = 10;
+    struct Node {
+        state_: AlternateState,
+        w_: f64,
+        pub child_nodes: Vec<Self>,
+        pub n_: f64,
+    }
+    impl Node {
+        pub fn new(state: &AlternateState) -> Self {
+            Self {
+                state_: state.clone(),
+                w_: 0.0,
+                child_nodes: Vec::new(),
+                n_: 0.0,
+            }
+        }
+        pub fn evaluate(&mut self) -> f64 {
+            if self.state_.isDone() {
+                let mut value = 0.5;
+                match self.state_.getWinningStatus() {
+                    WinningStatus::FISRT => value = 1.0,
+                    WinningStatus::SECOND => value = 0.0,
+                    _ => value = 0.5,
+                }
+                self.w_ += value;
+                self.n_ += 1.0;
+                return value;
+            }
+            if self.child_nodes.is_empty() {
+                let mut state_copy = self.state_.clone();
+                let value = playout(&mut state_copy);
+                self.w_ += value;
+                self.n_ += 1.0;
+                if self.n_ == EXPAND_THRESHOLD as f64 {
+                    self.expand();
+                }
+                return value;
+            } else {
+                let value = 1.0 - self.nextChildNode().evaluate();
+                self.w_ += value;
+                self.n_ += 1.0;
+                return value;
+            }
+        }
+
+        fn expand(&mut self) {
+            let legal_actions = self.state_.legalActions();
+            self.child_nodes.clear();
+            for action in legal_actions {
+                let mut add_state = Self::new(&self.state_);
+                add_state.state_.advance(action);
+                self.child_nodes.push(add_state);
+            }
+        }
+        fn nextChildNode(&mut self) -> &mut Node {
+            for i in 0..self.child_nodes.len() {
+                if self.child_nodes[i].n_ == 0.0 {
+                    return &mut self.child_nodes[i];
+                }
+            }
+            let mut t = 0.0;
+            for child_node in self.child_nodes.iter_mut() {
+                t += child_node.n_;
+            }
+            let mut best_value = -INF as f64;
+            let mut best_action_index = 0;
+            for i in 0..self.child_nodes.len() {
+                let child_node = &self.child_nodes[i];
+                let ucb1_value = 1.0 - child_node.w_ / child_node.n_
+                    + (C as f64) * (2.0 * t.ln() / child_node.n_).sqrt();
+                if ucb1_value > best_value {
+                    best_action_index = i;
+                    best_value = ucb1_value;
+                }
+            }
+            &mut self.child_nodes[best_action_index]
+        }
+    }
+    pub fn mctsAction(base_state: &State, player_id: usize, playout_number: usize) -> usize {
+        let state = AlternateState::new(base_state, player_id);
+        let mut root_node = Node::new(&state);
+        root_node.expand();
+        for i in 0..playout_number {
+            root_node.evaluate();
+        }
+        let legal_actions = state.legalActions();
+        let mut best_action_searched_number = -1.0;
+        let mut best_action_index = 0;
+        assert_eq!(legal_actions.len(), root_node.child_nodes.len());
+        for i in 0..legal_actions.len() {
+            let n = root_node.child_nodes[i].n_;
+            if n > best_action_searched_number {
+                best_action_index = i;
+                best_action_searched_number = n;
+            }
+        }
+        legal_actions[best_action_index]
+    }
+}
+
+pub mod nash {
+    use std::collections::HashMap;
+
+    use crate::State;
+    use crate::WinningStatus;
+
+    const EPS: f64 = 1e-9;
+
+    // 行プレイヤー(player0)の利得行列を持つゼロ和行列ゲームを線形計画法(単体法)で解く
+    // 戻り値は(ゲームの値, player0の混合戦略, player1の混合戦略)
+    pub fn solveMatrixGame(matrix: &Vec<Vec<f64>>) -> (f64, Vec<f64>, Vec<f64>) {
+        let m = matrix.len();
+        let n = matrix[0].len();
+        let mut min_value = f64::MAX;
+        for row in matrix.iter() {
+            for value in row.iter() {
+                min_value = min_value.min(*value);
+            }
+        }
+        // 全要素を正にしておくとゲームの値も正になり、max sum(y) s.t. Ay <= 1 に帰着できる
+        let shift = 1.0 - min_value;
+
+        let rhs = n + m;
+        let mut tableau = vec![vec![0.0; n + m + 1]; m + 1];
+        for i in 0..m {
+            for j in 0..n {
+                tableau[i][j] = matrix[i][j] + shift;
+            }
+            tableau[i][n + i] = 1.0;
+            tableau[i][rhs] = 1.0;
+        }
+        for j in 0..n {
+            tableau[m][j] = -1.0;
+        }
+        let mut basis: Vec<usize> = (n..n + m).collect();
+
+        loop {
+            // 巡回を避けるためBlandの規則で入れる列と出す行を選ぶ
+            let mut pivot_col = None;
+            for j in 0..n + m {
+                if tableau[m][j] < -EPS {
+                    pivot_col = Some(j);
+                    break;
+                }
+            }
+            let pivot_col = match pivot_col {
+                Some(j) => j,
+                None => break,
+            };
+            let mut pivot_row = None;
+            let mut best_ratio = f64::MAX;
+            for i in 0..m {
+                if tableau[i][pivot_col] > EPS {
+                    let ratio = tableau[i][rhs] / tableau[i][pivot_col];
+                    let is_better = match pivot_row {
+                        None => true,
+                        Some(r) => {
+                            ratio < best_ratio - EPS
+                                || (ratio < best_ratio + EPS && basis[i] < basis[r])
+                        }
+                    };
+                    if is_better {
+                        pivot_row = Some(i);
+                        best_ratio = ratio;
+                    }
+                }
+            }
+            // 利得が正なのでこの問題は有界であり、pivot_rowは必ず見つかる
+            let pivot_row = pivot_row.unwrap();
+
+            let pivot = tableau[pivot_row][pivot_col];
+            for j in 0..=rhs {
+                tableau[pivot_row][j] /= pivot;
+            }
+            for i in 0..=m {
+                if i == pivot_row {
+                    continue;
+                }
+                let factor = tableau[i][pivot_col];
+                if factor.abs() < EPS {
+                    continue;
+                }
+                for j in 0..=rhs {
+                    tableau[i][j] -= factor * tableau[pivot_row][j];
+                }
+            }
+            basis[pivot_row] = pivot_col;
+        }
+
+        let shifted_value = 1.0 / tableau[m][rhs];
+        let mut strategy0 = vec![0.0; m];
+        for i in 0..m {
+            strategy0[i] = tableau[m][n + i] * shifted_value;
+        }
+        let mut strategy1 = vec![0.0; n];
+        for i in 0..m {
+            if basis[i] < n {
+                strategy1[basis[i]] = tableau[i][rhs] * shifted_value;
+            }
+        }
+        (shifted_value - shift, strategy0, strategy1)
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct Equilibrium {
+        pub value_: f64,
+        pub strategies_: [Vec<f64>; 2],
+        pub matrix_: Vec<Vec<f64>>,
+    }
+
+    // 後退帰納法で到達可能な全局面のナッシュ均衡を求める
+    // 値はすべてplayer0の勝率(勝ち1.0、引き分け0.5、負け0.0)の期待値
+    pub struct NashSolver {
+        table_: HashMap<State, Equilibrium>,
+    }
+    impl NashSolver {
+        pub fn new(state: &State) -> Self {
+            let mut solver = Self {
+                table_: HashMap::new(),
+            };
+            solver.solve(state);
+            solver
+        }
+
+        fn solve(&mut self, state: &State) -> f64 {
+            if state.isDone() {
+                match state.getWinningStatus() {
+                    WinningStatus::FISRT => return 1.0,
+                    WinningStatus::SECOND => return 0.0,
+                    _ => return 0.5,
+                }
+            }
+            if let Some(equilibrium) = self.table_.get(state) {
+                return equilibrium.value_;
+            }
+            let legal_actions0 = state.legalActions(0);
+            let legal_actions1 = state.legalActions(1);
+            let mut matrix = vec![vec![0.0; legal_actions1.len()]; legal_actions0.len()];
+            for i in 0..legal_actions0.len() {
+                for j in 0..legal_actions1.len() {
+                    let mut next_state = state.clone();
+                    next_state.advance(legal_actions0[i], legal_actions1[j]);
+                    matrix[i][j] = self.solve(&next_state);
+                }
+            }
+            let (value, strategy0, strategy1) = solveMatrixGame(&matrix);
+            self.table_.insert(
+                state.clone(),
+                Equilibrium {
+                    value_: value,
+                    strategies_: [strategy0, strategy1],
+                    matrix_: matrix,
+                },
+            );
+            value
+        }
+
+        pub fn get(&self, state: &State) -> Option<&Equilibrium> {
+            self.table_.get(state)
+        }
+
+        pub fn states(&self) -> impl Iterator<Item = &State> {
+            self.table_.keys()
+        }
+
+        pub fn stateNumber(&self) -> usize {
+            self.table_.len()
+        }
+
+        // player_idから見たゲームの値
+        pub fn value(&self, state: &State, player_id: usize) -> f64 {
+            let value = self.table_[state].value_;
+            if player_id == 0 {
+                value
+            } else {
+                1.0 - value
+            }
+        }
+
+        // 相手が均衡戦略を取るときに、actionを選ぶことでゲームの値から失う勝率
+        // 均衡戦略の台に含まれる行動なら0になる
+        pub fn actionLoss(&self, state: &State, player_id: usize, action: usize) -> f64 {
+            let equilibrium = &self.table_[state];
+            let index = state
+                .legalActions(player_id)
+                .iter()
+                .position(|&a| a == action)
+                .unwrap();
+            let opp_strategy = &equilibrium.strategies_[(player_id + 1) % 2];
+            let mut expected = 0.0;
+            for k in 0..opp_strategy.len() {
+                expected += if player_id == 0 {
+                    opp_strategy[k] * equilibrium.matrix_[index][k]
+                } else {
+                    opp_strategy[k] * (1.0 - equilibrium.matrix_[k][index])
+                };
+            }
+            (self.value(state, player_id) - expected).max(0.0)
+        }
+    }
+}
+
+pub mod exploitability {
+    use std::collections::HashMap;
+
+    use crate::nash::NashSolver;
+    use crate::AIFunction;
+    use crate::State;
+    use crate::WinningStatus;
+
+    // 各局面でaiをsample_number回呼び出した経験分布を方策とみなす
+    // 決定的なAIなら1回で十分で、確率的なAIは回数を増やすほど正確になる
+    fn agentPolicy(
+        ai: AIFunction,
+        state: &State,
+        player_id: usize,
+        sample_number: usize,
+    ) -> Vec<f64> {
+        let legal_actions = state.legalActions(player_id);
+        let mut policy = vec![0.0; legal_actions.len()];
+        for _ in 0..sample_number {
+            let action = ai(state, player_id);
+            let index = legal_actions.iter().position(|&a| a == action).unwrap();
+            policy[index] += 1.0 / sample_number as f64;
+        }
+        policy
+    }
+
+    // agent_id番目のプレイヤーをaiに固定したときの、相手の最善応答を全探索で求める
+    pub struct BestResponse {
+        ai_: AIFunction,
+        agent_id_: usize,
+        sample_number_: usize,
+        table_: HashMap<State, (f64, usize)>,
+    }
+    impl BestResponse {
+        pub fn new(ai: AIFunction, agent_id: usize, sample_number: usize) -> Self {
+            Self {
+                ai_: ai,
+                agent_id_: agent_id,
+                sample_number_: sample_number,
+                table_: HashMap::new(),
+            }
+        }
+
+        // 最善応答側から見た勝率
+        pub fn value(&mut self, state: &State) -> f64 {
+            let responder_id = (self.agent_id_ + 1) % 2;
+            if state.isDone() {
+                let value = match state.getWinningStatus() {
+                    WinningStatus::FISRT => 1.0,
+                    WinningStatus::SECOND => 0.0,
+                    _ => 0.5,
+                };
+                return if responder_id == 0 {
+                    value
+                } else {
+                    1.0 - value
+                };
+            }
+            if let Some(&(value, _)) = self.table_.get(state) {
+                return value;
+            }
+            let agent_legal_actions = state.legalActions(self.agent_id_);
+            let policy = agentPolicy(self.ai_, state, self.agent_id_, self.sample_number_);
+            let my_legal_actions = state.legalActions(responder_id);
+            let mut best_value = -1.0;
+            let mut best_action = my_legal_actions[0];
+            for &my_action in my_legal_actions.iter() {
+                let mut value = 0.0;
+                for k in 0..agent_legal_actions.len() {
+                    if policy[k] == 0.0 {
+                        continue;
+                    }
+                    let mut next_state = state.clone();
+                    if responder_id == 0 {
+                        next_state.advance(my_action, agent_legal_actions[k]);
+                    } else {
+                        next_state.advance(agent_legal_actions[k], my_action);
+                    }
+                    value += policy[k] * self.value(&next_state);
+                }
+                if value > best_value {
+                    best_value = value;
+                    best_action = my_action;
+                }
+            }
+            self.table_.insert(state.clone(), (best_value, best_action));
+            best_value
+        }
+
+        pub fn bestAction(&mut self, state: &State) -> usize {
+            self.value(state);
+            self.table_[state].1
+        }
+    }
+
+    // aiがplayer0、player1を担当したときそれぞれの可搾取量
+    // 最善応答が得る勝率から、均衡で保証される勝率を引いたもので、均衡戦略なら0になる
+    pub fn exploitability(ai: AIFunction, state: &State, sample_number: usize) -> [f64; 2] {
+        let solver = NashSolver::new(state);
+        let mut result = [0.0; 2];
+        for agent_id in 0..2 {
+            let responder_id = (agent_id + 1) % 2;
+            let mut best_response = BestResponse::new(ai, agent_id, sample_number);
+            result[agent_id] = best_response.value(state) - solver.value(state, responder_id);
+        }
+        result
+    }
+}
+
+type AIFunction = fn(&State, usize) -> usize;
+type StringAIPair = (String, AIFunction);
+
+fn testExploitability(ais: &[StringAIPair], game_number: usize, sample_number: usize) {
+    let mut total = vec![[0.0; 2]; ais.len()];
+    for i in 0..game_number {
+        let state = State::new(i);
+        println!("seed {}", i);
+        for (ai_index, ai) in ais.iter().enumerate() {
+            let result = exploitability::exploitability(ai.1, &state, sample_number);
+            println!(
+                "  {}\t first {:.3} second {:.3}",
+                ai.0, result[0], result[1]
+            );
+            total[ai_index][0] += result[0];
+            total[ai_index][1] += result[1];
+        }
+    }
+    for (ai_index, ai) in ais.iter().enumerate() {
+        let first = total[ai_index][0] / game_number as f64;
+        let second = total[ai_index][1] / game_number as f64;
+        println!(
+            "Exploitability of {} :\t first {} second {} mean {}",
+            ai.0,
+            first,
+            second,
+            (first + second) / 2.0
+        );
+    }
+}
+
+fn main() {
+    let f0: AIFunction = |state: &State, player_id: usize| {
+        return montecalro::primitiveMontecarloAction(state, player_id, 50);
+    };
+    let f1: AIFunction =
+        |state: &State, player_id: usize| return montecalro::ductAction(state, player_id, 50);
+    let f2: AIFunction = |state: &State, player_id: usize| {
+        return altanate_montecalro::mctsAction(state, player_id, 50);
+    };
+    let f3: AIFunction = |state: &State, player_id: usize| return randomAction(state, player_id);
+    let ais = [
+        ("primitiveMontecarloAction".to_string(), f0),
+        ("ductAction".to_string(), f1),
+        ("mctsAction".to_string(), f2),
+        ("randomAction".to_string(), f3),
+    ];
+    testExploitability(&ais, 10, 1);
+}
@@ -0,0 +1,1011 @@
+use std::io::{self, BufRead, Write};
+use std::{thread, time};
+
+use rand::Rng;
+
+const H: i32 = 3;
+const W: i32 = 3;
+const END_TURN: usize = 4;
+const dstr: [&str; 4] = ["RIGHT", "LEFT", "DOWN", "UP"];
+
+type ScoreType = i32;
+
+// 端末の表示に使うANSIエスケープシーケンス
+const RESET: &str = "\x1b[0m";
+const CLEAR: &str = "\x1b[2J\x1b[H";
+const PLAYER_COLORS: [&str; 2] = ["\x1b[1;31m", "\x1b[1;34m"];
+
+// 得点が大きいマスほど目立つ色で表示する
+fn pointColor(point: i32) -> &'static str {
+    match point {
+        1..=3 => "\x1b[32m",
+        4..=6 => "\x1b[36m",
+        _ => "\x1b[1;35m",
+    }
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Copy)]
+struct Character {
+    y_: i32,
+    x_: i32,
+    game_score_: i32,
+}
+impl Character {
+    pub fn new(y_: i32, x_: i32) -> Self {
+        Self {
+            y_: y_,
+            x_: x_,
+            game_score_: 0,
+        }
+    }
+}
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct SimultaneousMazeState {
+    points_: [[i32; W as usize]; H as usize],
+    turn_: usize,
+    characters_: Vec<Character>,
+}
+
+impl SimultaneousMazeState {
+    const dx: [i32; 4] = [1, -1, 0, 0];
+    const dy: [i32; 4] = [0, 0, 1, -1];
+    pub fn new(seed: usize) -> Self {
+        let mut state = Self {
+            points_: [[0; W as usize]; H as usize],
+            turn_: 0,
+            characters_: vec![
+                Character::new(H / 2, W / 2 - 1),
+                Character::new(H / 2, W / 2 + 1),
+            ],
+        };
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(seed as u64);
+        for y in 0..H {
+            for x in 0..W {
+                let point = rng.gen_range(0..10);
+                if state.characters_[0].y_ == y && state.characters_[0].x_ == x {
+                    continue;
+                }
+                if state.characters_[1].y_ == y && state.characters_[1].x_ == x {
+                    continue;
+                }
+                let mut ty = y;
+                let mut tx = x;
+                state.points_[ty as usize][tx as usize] = point;
+                tx = W - 1 - x;
+                state.points_[ty as usize][tx as usize] = point;
+            }
+        }
+        state
+    }
+
+    pub fn isDone(&self) -> bool {
+        self.turn_ == END_TURN
+    }
+    pub fn advance(&mut self, action0: usize, action1: usize) {
+        {
+            let character = &mut self.characters_[0];
+            let action = action0;
+            character.x_ += Self::dx[action];
+            character.y_ += Self::dy[action];
+            let point: &mut i32 = &mut self.points_[character.y_ as usize][character.x_ as usize];
+            if *point > 0 {
+                character.game_score_ += *point;
+            }
+        }
+        {
+            let character = &mut self.characters_[1];
+            let action = action1;
+            character.x_ += Self::dx[action];
+            character.y_ += Self::dy[action];
+            let point: &mut i32 = &mut self.points_[character.y_ as usize][character.x_ as usize];
+            if *point > 0 {
+                character.game_score_ += *point;
+            }
+        }
+        for character_id in 0..self.characters_.len() {
+            let character = &self.characters_[character_id];
+            self.points_[character.y_ as usize][character.x_ as usize] = 0;
+        }
+
+        self.turn_ += 1;
+    }
+    pub fn legalActions(&self, player_id: usize) -> Vec<usize> {
+        let mut actions = Vec::new();
+        let character = &self.characters_[player_id];
+        for action in 0..4 {
+            let ty = character.y_ + Self::dy[action];
+            let tx = character.x_ + Self::dx[action];
+            if ty >= 0 && ty < H && tx >= 0 && tx < W {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+
+    pub fn getScore(&self) -> ScoreType {
+        return self.characters_[0].game_score_ - self.characters_[1].game_score_;
+    }
+    pub fn getScoreRate(&self) -> f64 {
+        if self.characters_[0].game_score_ + self.characters_[1].game_score_ == 0 {
+            return 0.0;
+        }
+        return self.characters_[0].game_score_ as f64
+            / (self.characters_[0].game_score_ + self.characters_[1].game_score_) as f64;
+    }
+
+    pub fn toString(&self) -> String {
+        let mut ss = String::new();
+        ss += format!("turn:\t{}\n", self.turn_).as_str();
+        for player_id in 0..self.characters_.len() {
+            let chara = &self.characters_[player_id];
+            ss += format!("score({})\t {}\n", player_id, chara.game_score_).as_str();
+        }
+        for h in 0..H {
+            for w in 0..W {
+                let mut is_written = false;
+                for player_id in 0..self.characters_.len() {
+                    let character = &self.characters_[player_id as usize];
+                    if character.y_ == h && character.x_ == w {
+                        if player_id == 0 {
+                            ss += "A";
+                        } else {
+                            ss += "B";
+                        }
+                        is_written = true;
+                    }
+                }
+                if !is_written {
+                    if self.points_[h as usize][w as usize] > 0 {
+                        ss += format!("{}", self.points_[h as usize][w as usize]).as_str();
+                    } else {
+                        ss += ".";
+                    }
+                }
+            }
+            ss += "\n";
+        }
+
+        ss
+    }
+
+    // toStringと同じ盤面に色を付けて返す
+    pub fn toColorString(&self) -> String {
+        let mut ss = String::new();
+        ss += format!("turn:\t{}\n", self.turn_).as_str();
+        for player_id in 0..self.characters_.len() {
+            let chara = &self.characters_[player_id];
+            ss += format!(
+                "{}score({})\t {}{}\n",
+                PLAYER_COLORS[player_id], player_id, chara.game_score_, RESET
+            )
+            .as_str();
+        }
+        for h in 0..H {
+            for w in 0..W {
+                let mut is_written = false;
+                for player_id in 0..self.characters_.len() {
+                    let character = &self.characters_[player_id as usize];
+                    // 2人が同じマスにいるときは、盤面の幅がずれないようAだけを表示する
+                    if character.y_ == h && character.x_ == w && !is_written {
+                        let name = if player_id == 0 { "A" } else { "B" };
+                        ss += format!("{}{}{}", PLAYER_COLORS[player_id], name, RESET).as_str();
+                        is_written = true;
+                    }
+                }
+                if !is_written {
+                    let point = self.points_[h as usize][w as usize];
+                    if point > 0 {
+                        ss += format!("{}{}{}", pointColor(point), point, RESET).as_str();
+                    } else {
+                        ss += ".";
+                    }
+                }
+            }
+            ss += "\n";
+        }
+
+        ss
+    }
+}
+
+type State = SimultaneousMazeState;
+fn randomAction(state: &State, player_id: usize) -> usize {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0);
+    let legal_actions = state.legalActions(player_id);
+    let id = rng.gen_range(0..legal_actions.len());
+    return legal_actions[id as usize];
+}
+
+type AIFunction = fn(&State, usize) -> usize;
+type StringAIPair = (String, AIFunction);
+
+// 棋譜の形式のバージョン。形式を変えたら上げる
+const RECORD_VERSION: usize = 2;
+const RECORD_DIRECTORY: &str = "records";
+
+fn parseNumbers<T: std::str::FromStr>(words: &[&str]) -> Result<Vec<T>, String> {
+    words
+        .iter()
+        .map(|word| {
+            word.parse::<T>()
+                .map_err(|_| format!("invalid number: {}", word))
+        })
+        .collect()
+}
+fn parseAction(word: &str) -> Result<usize, String> {
+    dstr.iter()
+        .position(|name| *name == word)
+        .ok_or(format!("invalid action: {}", word))
+}
+fn gridToString(grid: &[[i32; W as usize]; H as usize]) -> String {
+    let mut ss = String::new();
+    for row in grid.iter() {
+        let values: Vec<String> = row.iter().map(|value| value.to_string()).collect();
+        ss += values.join(" ").as_str();
+        ss += "\n";
+    }
+    ss
+}
+
+// 棋譜を1行ずつ読む。各行は「キー 値...」の形で、空行と#で始まる行は読み飛ばす
+struct RecordReader<'a> {
+    lines_: std::str::Lines<'a>,
+}
+impl<'a> RecordReader<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            lines_: text.lines(),
+        }
+    }
+    pub fn readWords(&mut self) -> Result<Vec<&'a str>, String> {
+        loop {
+            match self.lines_.next() {
+                None => return Err("unexpected end of record".to_string()),
+                Some(line) => {
+                    let line = line.trim();
+                    if !line.is_empty() && !line.starts_with('#') {
+                        return Ok(line.split_whitespace().collect());
+                    }
+                }
+            }
+        }
+    }
+    // keyで始まる行を読み、残りの値を返す
+    pub fn read(&mut self, key: &str) -> Result<Vec<&'a str>, String> {
+        let words = self.readWords()?;
+        if words[0] != key {
+            return Err(format!("expected {} but found {}", key, words[0]));
+        }
+        Ok(words[1..].to_vec())
+    }
+    // keyの行の値が1つの数字であるときに、その数字を返す
+    pub fn readNumber<T: std::str::FromStr>(&mut self, key: &str) -> Result<T, String> {
+        let mut values = parseNumbers::<T>(&self.read(key)?)?;
+        if values.len() != 1 {
+            return Err(format!("{} must have exactly one value", key));
+        }
+        Ok(values.pop().unwrap())
+    }
+    // keyの行に続くH行W列の数字を読む
+    pub fn readGrid(&mut self, key: &str) -> Result<[[i32; W as usize]; H as usize], String> {
+        self.read(key)?;
+        let mut grid = [[0; W as usize]; H as usize];
+        for y in 0..H as usize {
+            let values = parseNumbers::<i32>(&self.readWords()?)?;
+            if values.len() != W as usize {
+                return Err(format!("row {} of {} must have {} values", y, key, W));
+            }
+            grid[y].copy_from_slice(&values);
+        }
+        Ok(grid)
+    }
+}
+
+// 盤面のH行を読み、得点と2人のキャラクターの位置を返す
+// 数字は得点、.は得点のないマス、AとBはキャラクターを表す
+fn parseGrid(
+    rows: &[&str],
+) -> Result<([[i32; W as usize]; H as usize], [Option<(i32, i32)>; 2]), String> {
+    if rows.len() != H as usize {
+        return Err(format!("board must have {} rows but has {}", H, rows.len()));
+    }
+    let mut points = [[0; W as usize]; H as usize];
+    let mut characters = [None; 2];
+    for (y, row) in rows.iter().enumerate() {
+        let cells: Vec<char> = row.chars().collect();
+        let mut x = 0;
+        let mut i = 0;
+        while i < cells.len() {
+            if x >= W {
+                return Err(format!("row {} must have {} cells: {}", y, W, row));
+            }
+            match cells[i] {
+                '0'..='9' => points[y][x as usize] = cells[i].to_digit(10).unwrap() as i32,
+                '.' => {}
+                'A' | 'B' => {
+                    // 2人が同じマスにいると、toStringはそのマスにABのように2文字書く
+                    let mut names = vec![cells[i]];
+                    if cells.len() > W as usize
+                        && i + 1 < cells.len()
+                        && (cells[i + 1] == 'A' || cells[i + 1] == 'B')
+                        && cells[i + 1] != cells[i]
+                    {
+                        i += 1;
+                        names.push(cells[i]);
+                    }
+                    for name in names {
+                        let player_id = if name == 'A' { 0 } else { 1 };
+                        if characters[player_id].is_some() {
+                            return Err(format!("board has more than one {}", name));
+                        }
+                        characters[player_id] = Some((y as i32, x));
+                    }
+                }
+                cell => return Err(format!("invalid cell {} at ({}, {})", cell, y, x)),
+            }
+            i += 1;
+            x += 1;
+        }
+        if x != W {
+            return Err(format!("row {} must have {} cells: {}", y, W, row));
+        }
+    }
+    Ok((points, characters))
+}
+
+impl std::str::FromStr for SimultaneousMazeState {
+    type Err = String;
+    // toStringの出力を読み込む。turn:とscore(プレイヤー番号)の行は省略できる
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut state = State::default();
+        let mut scores = [0; 2];
+        let mut rows = Vec::new();
+        for line in text.lines().map(|line| line.trim()) {
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(value) = line.strip_prefix("turn:") {
+                state.turn_ = parseNumbers::<usize>(&[value.trim()])?[0];
+            } else if let Some(value) = line.strip_prefix("score(") {
+                let words: Vec<&str> = value
+                    .split(|c: char| c == ')' || c.is_whitespace())
+                    .filter(|word| !word.is_empty())
+                    .collect();
+                if words.len() < 2 {
+                    return Err(format!("invalid score: {}", line));
+                }
+                let player_id = parseNumbers::<usize>(&words[..1])?[0];
+                if player_id >= 2 {
+                    return Err(format!("invalid player: {}", line));
+                }
+                scores[player_id] = parseNumbers::<i32>(&words[1..2])?[0];
+            } else {
+                rows.push(line);
+            }
+        }
+        let (points, characters) = parseGrid(&rows)?;
+        state.points_ = points;
+        for player_id in 0..2 {
+            let (y, x) = characters[player_id]
+                .ok_or(format!("board must have {}", ["A", "B"][player_id]))?;
+            let mut character = Character::new(y, x);
+            character.game_score_ = scores[player_id];
+            state.characters_.push(character);
+        }
+        Ok(state)
+    }
+}
+impl SimultaneousMazeState {
+    // AtCoder形式の入力を読み込む
+    // 1行目にH W T、続くH行に盤面(数字と.)、最後の2行に先手と後手のキャラクターの位置y xを書く
+    pub fn fromInput(text: &str) -> Result<Self, String> {
+        let mut lines = text
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty());
+        let header = lines.next().ok_or("empty input")?;
+        let size = parseNumbers::<usize>(&header.split_whitespace().collect::<Vec<_>>())?;
+        if size != [H as usize, W as usize, END_TURN] {
+            return Err(format!(
+                "H W T must be {} {} {}: {}",
+                H, W, END_TURN, header
+            ));
+        }
+        let rows: Vec<&str> = lines.by_ref().take(H as usize).collect();
+        let (points, characters) = parseGrid(&rows)?;
+        if characters.iter().any(|character| character.is_some()) {
+            return Err("characters must be given after the board".to_string());
+        }
+        let mut state = State::default();
+        state.points_ = points;
+        for player_id in 0..2 {
+            let position = lines.next().ok_or("missing character position")?;
+            let position = parseNumbers::<i32>(&position.split_whitespace().collect::<Vec<_>>())?;
+            if position.len() != 2 {
+                return Err("character position must be y x".to_string());
+            }
+            let (y, x) = (position[0], position[1]);
+            if y < 0 || y >= H || x < 0 || x >= W {
+                return Err(format!(
+                    "character {} ({}, {}) is out of the board",
+                    player_id, y, x
+                ));
+            }
+            // キャラクターの初期位置の得点は取れないので0にしておく
+            state.points_[y as usize][x as usize] = 0;
+            state.characters_.push(Character::new(y, x));
+        }
+        Ok(state)
+    }
+}
+
+// 問題ファイルを読み込む。1行目が3つの数字ならAtCoder形式、そうでなければtoStringの形式として読む
+fn loadProblem(path: &str) -> Result<State, String> {
+    let text = std::fs::read_to_string(path).map_err(|error| error.to_string())?;
+    let first_line = text
+        .lines()
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty())
+        .unwrap_or("");
+    let words: Vec<&str> = first_line.split_whitespace().collect();
+    if words.len() == 3 && parseNumbers::<i32>(&words).is_ok() {
+        State::fromInput(&text)
+    } else {
+        text.parse()
+    }
+}
+
+// 1試合分の記録。初期盤面と2人の行動列から、各ターンの盤面を再生して持つ
+struct Replay {
+    pub seed_: usize,
+    pub actions_: Vec<[usize; 2]>,
+    // 2人がそれぞれ各手を決めるのにかかった時間(ミリ秒)
+    pub thinking_times_: Vec<[f64; 2]>,
+    states_: Vec<State>,
+}
+impl Replay {
+    pub fn new(seed: usize) -> Self {
+        Self::fromState(seed, State::new(seed))
+    }
+    pub fn fromState(seed: usize, state: State) -> Self {
+        Self {
+            seed_: seed,
+            actions_: Vec::new(),
+            thinking_times_: Vec::new(),
+            states_: vec![state],
+        }
+    }
+    // 記録したシードと行動列から試合を再生する
+    pub fn fromActions(seed: usize, actions: &[[usize; 2]]) -> Self {
+        let mut replay = Self::new(seed);
+        for &action in actions {
+            replay.push(action, [0.0; 2]);
+        }
+        replay
+    }
+    pub fn push(&mut self, actions: [usize; 2], thinking_times: [f64; 2]) {
+        let mut next_state = self.lastState().clone();
+        next_state.advance(actions[0], actions[1]);
+        self.actions_.push(actions);
+        self.thinking_times_.push(thinking_times);
+        self.states_.push(next_state);
+    }
+    pub fn lastTurn(&self) -> usize {
+        self.actions_.len()
+    }
+    pub fn lastState(&self) -> &State {
+        self.states_.last().unwrap()
+    }
+    // 終局していれば勝者を返す
+    pub fn winner(&self) -> &'static str {
+        let score = self.lastState().getScore();
+        if score > 0 {
+            "1p"
+        } else if score < 0 {
+            "2p"
+        } else {
+            "DRAW"
+        }
+    }
+    // turnの盤面を、そのターンに至った2人の行動と一緒に表示用の文字列にする
+    pub fn render(&self, turn: usize) -> String {
+        let mut ss = String::new();
+        ss += format!("seed:\t{}\t({}/{})\n", self.seed_, turn, self.lastTurn()).as_str();
+        if turn > 0 {
+            let actions = self.actions_[turn - 1];
+            let thinking_times = self.thinking_times_[turn - 1];
+            ss += format!(
+                "actions\t{}{}{} ({:.3}ms) {}{}{} ({:.3}ms)\n",
+                PLAYER_COLORS[0],
+                dstr[actions[0]],
+                RESET,
+                thinking_times[0],
+                PLAYER_COLORS[1],
+                dstr[actions[1]],
+                RESET,
+                thinking_times[1]
+            )
+            .as_str();
+        } else {
+            ss += "actions\t-\n";
+        }
+        ss += self.states_[turn].toColorString().as_str();
+        if self.states_[turn].isDone() {
+            ss += format!("winner: {}\n", self.winner()).as_str();
+        }
+        ss
+    }
+
+    // 棋譜を文字列にする。シードだけでなく初期盤面も書くので、盤面の作り方が変わっても再生できる
+    // 途中の盤面から始めた試合も再生できるよう、初期盤面のターン数と得点も書く
+    // キャラクターは「character y x 得点」の形で書く
+    pub fn toRecord(&self) -> String {
+        let initial_state = &self.states_[0];
+        let mut ss = String::new();
+        ss += format!("version\t{}\n", RECORD_VERSION).as_str();
+        ss += "game\tSimultaneousMazeState\n";
+        ss += format!("board\t{}\t{}\t{}\n", H, W, END_TURN).as_str();
+        ss += format!("seed\t{}\n", self.seed_).as_str();
+        ss += format!("turn\t{}\n", initial_state.turn_).as_str();
+        for character in initial_state.characters_.iter() {
+            ss += format!(
+                "character\t{}\t{}\t{}\n",
+                character.y_, character.x_, character.game_score_
+            )
+            .as_str();
+        }
+        ss += "points\n";
+        ss += gridToString(&initial_state.points_).as_str();
+        // 1行に1ターン分、2人の行動と考えた時間を書く
+        ss += format!("actions\t{}\n", self.lastTurn()).as_str();
+        for (actions, thinking_times) in self.actions_.iter().zip(self.thinking_times_.iter()) {
+            ss += format!(
+                "{}\t{}\t{:.3}\t{:.3}\n",
+                dstr[actions[0]], dstr[actions[1]], thinking_times[0], thinking_times[1]
+            )
+            .as_str();
+        }
+        let characters = &self.lastState().characters_;
+        ss += format!(
+            "result\t{}\t{}\t{}\n",
+            characters[0].game_score_,
+            characters[1].game_score_,
+            self.winner()
+        )
+        .as_str();
+        ss
+    }
+    // 棋譜を読んで初期盤面から再生し、行動が合法で結果が書かれた得点と一致するか確かめる
+    pub fn fromRecord(text: &str) -> Result<Self, String> {
+        let mut reader = RecordReader::new(text);
+        let version = reader.readNumber::<usize>("version")?;
+        if version != RECORD_VERSION {
+            return Err(format!("unsupported version: {}", version));
+        }
+        let game = reader.read("game")?;
+        if game != ["SimultaneousMazeState"] {
+            return Err(format!("unsupported game: {}", game.join(" ")));
+        }
+        let board = parseNumbers::<usize>(&reader.read("board")?)?;
+        if board != [H as usize, W as usize, END_TURN] {
+            return Err(format!(
+                "board {:?} does not match {} {} {}",
+                board, H, W, END_TURN
+            ));
+        }
+        let seed = reader.readNumber::<usize>("seed")?;
+        let mut state = State::default();
+        state.turn_ = reader.readNumber::<usize>("turn")?;
+        if state.turn_ > END_TURN {
+            return Err(format!("turn {} is after the end of the game", state.turn_));
+        }
+        for player_id in 0..2 {
+            let values = parseNumbers::<i32>(&reader.read("character")?)?;
+            if values.len() != 3 {
+                return Err("character must have y, x and score".to_string());
+            }
+            let (y, x) = (values[0], values[1]);
+            if y < 0 || y >= H || x < 0 || x >= W {
+                return Err(format!(
+                    "character {} ({}, {}) is out of the board",
+                    player_id, y, x
+                ));
+            }
+            let mut character = Character::new(y, x);
+            character.game_score_ = values[2];
+            state.characters_.push(character);
+        }
+        state.points_ = reader.readGrid("points")?;
+
+        let mut replay = Self::fromState(seed, state);
+        let action_number = reader.readNumber::<usize>("actions")?;
+        for turn in 0..action_number {
+            let words = reader.readWords()?;
+            if words.len() != 4 {
+                return Err(format!(
+                    "actions of turn {} must have two names and two times",
+                    turn
+                ));
+            }
+            let actions = [parseAction(words[0])?, parseAction(words[1])?];
+            let thinking_times = parseNumbers::<f64>(&words[2..])?;
+            if replay.lastState().isDone() {
+                return Err(format!("action after the game ended at turn {}", turn));
+            }
+            for player_id in 0..2 {
+                if !replay
+                    .lastState()
+                    .legalActions(player_id)
+                    .contains(&actions[player_id])
+                {
+                    return Err(format!(
+                        "illegal action {} of player {} at turn {}",
+                        words[player_id], player_id, turn
+                    ));
+                }
+            }
+            replay.push(actions, [thinking_times[0], thinking_times[1]]);
+        }
+        let result = reader.read("result")?;
+        let characters = &replay.lastState().characters_;
+        let expected = [
+            characters[0].game_score_.to_string(),
+            characters[1].game_score_.to_string(),
+            replay.winner().to_string(),
+        ];
+        if result != expected {
+            return Err(format!(
+                "result {} does not match replayed result {}",
+                result.join(" "),
+                expected.join(" ")
+            ));
+        }
+        Ok(replay)
+    }
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        std::fs::write(path, self.toRecord()).map_err(|error| error.to_string())
+    }
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|error| error.to_string())?;
+        Self::fromRecord(&text)
+    }
+}
+
+// SVGで1マスを描く大きさ(ピクセル)
+const CELL_SIZE: i32 = 48;
+const SVG_PLAYER_COLORS: [&str; 2] = ["#d62728", "#1f77b4"];
+
+impl Replay {
+    // turnでの各キャラクターの位置(y, x)をプレイヤーの番号順に返す
+    fn playerPositions(&self, turn: usize) -> Vec<(i32, i32)> {
+        self.states_[turn]
+            .characters_
+            .iter()
+            .map(|character| (character.y_, character.x_))
+            .collect()
+    }
+    // turnの盤面の説明。HTMLでは盤面の上に表示する
+    fn caption(&self, turn: usize) -> String {
+        let characters = &self.states_[turn].characters_;
+        let actions = if turn > 0 {
+            let actions = self.actions_[turn - 1];
+            format!("{} {}", dstr[actions[0]], dstr[actions[1]])
+        } else {
+            "-".to_string()
+        };
+        format!(
+            "turn {}/{}&emsp;score(0) {}&emsp;score(1) {}&emsp;actions {}",
+            turn,
+            self.lastTurn(),
+            characters[0].game_score_,
+            characters[1].game_score_,
+            actions
+        )
+    }
+    // turnの盤面をSVGにする。得点が大きいマスほど濃く塗り、turnまでの移動の軌跡を線で描く
+    pub fn toSvg(&self, turn: usize) -> String {
+        let state = &self.states_[turn];
+        let mut ss = String::new();
+        ss += format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n",
+            W * CELL_SIZE,
+            H * CELL_SIZE
+        )
+        .as_str();
+        for y in 0..H {
+            for x in 0..W {
+                let point = state.points_[y as usize][x as usize];
+                let fill = format!("rgba(255, 160, 0, {:.2})", point as f64 / 9.0);
+                ss += format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"#cccccc\"/>\n",
+                    x * CELL_SIZE,
+                    y * CELL_SIZE,
+                    CELL_SIZE,
+                    CELL_SIZE,
+                    fill
+                )
+                .as_str();
+                if point > 0 {
+                    ss += format!(
+                        "<text x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>\n",
+                        x * CELL_SIZE + CELL_SIZE / 2,
+                        y * CELL_SIZE + CELL_SIZE / 2,
+                        CELL_SIZE / 3,
+                        point
+                    )
+                    .as_str();
+                }
+            }
+        }
+        let player_number = SVG_PLAYER_COLORS.len();
+        for player_id in 0..player_number {
+            // 2人の軌跡が重ならないよう、プレイヤーごとに少しずらして描く
+            let offset = (2 * player_id as i32 + 1 - player_number as i32) * CELL_SIZE / 10;
+            let path: Vec<String> = (0..=turn)
+                .map(|t| {
+                    let (y, x) = self.playerPositions(t)[player_id];
+                    format!(
+                        "{},{}",
+                        x * CELL_SIZE + CELL_SIZE / 2 + offset,
+                        y * CELL_SIZE + CELL_SIZE / 2 + offset
+                    )
+                })
+                .collect();
+            ss += format!(
+                "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"4\" stroke-opacity=\"0.7\"/>\n",
+                path.join(" "),
+                SVG_PLAYER_COLORS[player_id]
+            )
+            .as_str();
+            let (y, x) = self.playerPositions(turn)[player_id];
+            ss += format!(
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>\n",
+                x * CELL_SIZE + CELL_SIZE / 2 + offset,
+                y * CELL_SIZE + CELL_SIZE / 2 + offset,
+                CELL_SIZE / 5,
+                SVG_PLAYER_COLORS[player_id]
+            )
+            .as_str();
+        }
+        ss += "</svg>\n";
+        ss
+    }
+    // 全ターンのSVGと表示するターンを選ぶスライダーを1つのHTMLにまとめる
+    // 外部のスクリプトやスタイルシートを読まないので、このファイルだけで開ける
+    pub fn toHtml(&self) -> String {
+        let mut ss = String::new();
+        ss += "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n";
+        ss += format!("<title>SimultaneousMazeState seed {}</title>\n", self.seed_).as_str();
+        ss += "</head>\n<body>\n";
+        ss += format!(
+            "<input type=\"range\" id=\"turn\" min=\"0\" max=\"{}\" value=\"0\">\n",
+            self.lastTurn()
+        )
+        .as_str();
+        for turn in 0..=self.lastTurn() {
+            ss += "<div class=\"frame\">\n";
+            ss += format!("<p>{}</p>\n", self.caption(turn)).as_str();
+            ss += self.toSvg(turn).as_str();
+            ss += "</div>\n";
+        }
+        ss += r#"<script>
+const slider = document.getElementById("turn");
+function show() {
+  document.querySelectorAll(".frame").forEach((frame, turn) => {
+    frame.style.display = turn == slider.value ? "block" : "none";
+  });
+}
+slider.addEventListener("input", show);
+show();
+</script>
+</body>
+</html>
+"#;
+        ss
+    }
+    pub fn saveSvg(&self, path: &str, turn: usize) -> Result<(), String> {
+        std::fs::write(path, self.toSvg(turn)).map_err(|error| error.to_string())
+    }
+    pub fn saveHtml(&self, path: &str) -> Result<(), String> {
+        std::fs::write(path, self.toHtml()).map_err(|error| error.to_string())
+    }
+}
+
+// 記録した試合を端末で1ターンずつ表示する
+// Enterかn: 1手進む、p: 1手戻る、g <turn>: 指定したターンへ移動、a <ms>: ms間隔で最後まで自動再生、q: 終了
+struct ReplayViewer<'a> {
+    replay_: &'a Replay,
+    turn_: usize,
+}
+impl<'a> ReplayViewer<'a> {
+    pub fn new(replay: &'a Replay) -> Self {
+        Self {
+            replay_: replay,
+            turn_: 0,
+        }
+    }
+    fn draw(&self) {
+        print!("{}{}", CLEAR, self.replay_.render(self.turn_));
+        println!("[Enter/n] next  [p] prev  [g <turn>] jump  [a <ms>] auto  [q] quit");
+        io::stdout().flush().unwrap();
+    }
+    pub fn autoPlay(&mut self, interval: u64) {
+        while self.turn_ < self.replay_.lastTurn() {
+            thread::sleep(time::Duration::from_millis(interval));
+            self.turn_ += 1;
+            self.draw();
+        }
+    }
+    pub fn run(&mut self) {
+        self.draw();
+        for line in io::stdin().lock().lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+            let mut words = line.split_whitespace();
+            let command = words.next();
+            let argument = words.next().and_then(|word| word.parse::<u64>().ok());
+            match command {
+                None | Some("n") => self.turn_ = (self.turn_ + 1).min(self.replay_.lastTurn()),
+                Some("p") => self.turn_ = self.turn_.saturating_sub(1),
+                Some("g") => {
+                    if let Some(turn) = argument {
+                        self.turn_ = (turn as usize).min(self.replay_.lastTurn());
+                    }
+                }
+                Some("a") => self.autoPlay(argument.unwrap_or(500)),
+                Some("q") => break,
+                _ => {}
+            }
+            self.draw();
+        }
+    }
+}
+
+fn playGame(ais: &[StringAIPair; 2], seed: usize) -> Replay {
+    playFrom(ais, Replay::new(seed))
+}
+fn playFrom(ais: &[StringAIPair; 2], mut replay: Replay) -> Replay {
+    while !replay.lastState().isDone() {
+        let state = replay.lastState();
+        let mut actions = [0; 2];
+        let mut thinking_times = [0.0; 2];
+        for player_id in 0..2 {
+            let start_time = time::Instant::now();
+            actions[player_id] = ais[player_id].1(state, player_id);
+            thinking_times[player_id] = start_time.elapsed().as_secs_f64() * 1000.0;
+        }
+        replay.push(actions, thinking_times);
+    }
+    replay
+}
+
+// toStringの出力を読み直して、同じ盤面に戻るか確かめる
+fn testParse(game_number: usize) {
+    let mut failed_number = 0;
+    for seed in 0..game_number {
+        let mut state = State::new(seed);
+        // 途中の盤面も確かめるため、シードの数だけ適当に進める
+        for _ in 0..seed % (END_TURN + 1) {
+            let action = state.legalActions(0)[0];
+            state.advance(action, state.legalActions(1)[0]);
+        }
+        match state.toString().parse::<State>() {
+            Ok(parsed) if parsed == state => {}
+            Ok(_) => {
+                println!("seed {}: parsed board differs", seed);
+                failed_number += 1;
+            }
+            Err(message) => {
+                println!("seed {}: {}", seed, message);
+                failed_number += 1;
+            }
+        }
+    }
+    println!("parsed {} boards, {} failed", game_number, failed_number);
+}
+// シードと行動列だけから再生し直して、同じ試合に戻るか確かめる
+fn testActions(ais: &[StringAIPair; 2], game_number: usize) {
+    let mut failed_number = 0;
+    for seed in 0..game_number {
+        let replay = playFrom(ais, Replay::new(seed));
+        if Replay::fromActions(seed, &replay.actions_).states_ != replay.states_ {
+            println!("seed {}: replayed game differs", seed);
+            failed_number += 1;
+        }
+    }
+    println!("replayed {} games, {} failed", game_number, failed_number);
+}
+
+// 途中の盤面から始めた試合も含めて、棋譜を書いて読み直すと同じ試合に戻るか確かめる
+fn testRecord(ais: &[StringAIPair; 2], game_number: usize) {
+    let mut failed_number = 0;
+    for seed in 0..game_number {
+        let mut state = State::new(seed);
+        for _ in 0..seed % END_TURN {
+            let action = state.legalActions(0)[0];
+            state.advance(action, state.legalActions(1)[0]);
+        }
+        let replay = playFrom(ais, Replay::fromState(seed, state));
+        match Replay::fromRecord(&replay.toRecord()) {
+            Ok(loaded)
+                if loaded.states_ == replay.states_ && loaded.actions_ == replay.actions_ => {}
+            Ok(_) => {
+                println!("seed {}: loaded game differs", seed);
+                failed_number += 1;
+            }
+            Err(message) => {
+                println!("seed {}: {}", seed, message);
+                failed_number += 1;
+            }
+        }
+    }
+    println!("loaded {} records, {} failed", game_number, failed_number);
+}
+
+// 各試合の棋譜をRECORD_DIRECTORYに保存しながら、先手の勝率を求める
+fn testFirstPlayerWinRate(ais: &[StringAIPair; 2], game_number: usize) {
+    std::fs::create_dir_all(RECORD_DIRECTORY).unwrap();
+    let mut first_player_win_rate = 0.0;
+    for i in 0..game_number {
+        let replay = playGame(ais, i);
+        let path = format!("{}/SimultaneousMazeState_{}.txt", RECORD_DIRECTORY, i);
+        replay.save(&path).unwrap();
+        first_player_win_rate += match replay.winner() {
+            "1p" => 1.0,
+            "2p" => 0.0,
+            _ => 0.5,
+        };
+    }
+    first_player_win_rate /= game_number as f64;
+    println!(
+        "Winning rate of {} to {}:\t{}",
+        ais[0].0, ais[1].0, first_player_win_rate
+    );
+}
+
+fn main() {
+    testParse(100);
+    let f0: AIFunction = |state: &State, seed: usize| return randomAction(state, seed);
+    let f1: AIFunction = |state: &State, seed: usize| return randomAction(state, seed);
+    let ais = [
+        ("randomAction".to_string(), f0),
+        ("randomAction".to_string(), f1),
+    ];
+    testRecord(&ais, 100);
+    testActions(&ais, 100);
+    // 引数に問題ファイルを渡すとその盤面で1試合、渡さなければシードを変えて10試合遊ぶ
+    let path = match std::env::args().nth(1) {
+        Some(problem_path) => match loadProblem(&problem_path) {
+            Ok(state) => {
+                std::fs::create_dir_all(RECORD_DIRECTORY).unwrap();
+                let path = format!("{}/SimultaneousMazeState_problem.txt", RECORD_DIRECTORY);
+                // 問題ファイルの盤面にはシードがないので0として記録する
+                playFrom(&ais, Replay::fromState(0, state))
+                    .save(&path)
+                    .unwrap();
+                path
+            }
+            Err(message) => {
+                println!("invalid problem {}: {}", problem_path, message);
+                return;
+            }
+        },
+        None => {
+            testFirstPlayerWinRate(&ais, 10);
+            format!("{}/SimultaneousMazeState_0.txt", RECORD_DIRECTORY)
+        }
+    };
+    match Replay::load(&path) {
+        Ok(replay) => {
+            let html_path = path.replace(".txt", ".html");
+            replay.saveHtml(&html_path).unwrap();
+            replay
+                .saveSvg(&path.replace(".txt", ".svg"), replay.lastTurn())
+                .unwrap();
+            ReplayViewer::new(&replay).run();
+        }
+        Err(message) => println!("invalid record {}: {}", path, message),
+    }
+}
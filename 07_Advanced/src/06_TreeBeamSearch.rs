@@ -0,0 +1,607 @@
+use std::collections::{BinaryHeap, HashSet, VecDeque};
+use std::time;
+
+use rand;
+use rand::prelude::*;
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct Coord {
+    y_: i32,
+    x_: i32,
+}
+impl Coord {
+    pub fn new(y_: i32, x_: i32) -> Self {
+        Self { y_, x_ }
+    }
+}
+
+struct TimeKeeper {
+    start_time_: std::time::Instant,
+    time_threshold_: u64,
+}
+impl TimeKeeper {
+    pub fn new(time_threshold: u64) -> Self {
+        Self {
+            start_time_: time::Instant::now(),
+            time_threshold_: time_threshold,
+        }
+    }
+    pub fn isTimeOver(&self) -> bool {
+        self.start_time_.elapsed() > time::Duration::from_millis(self.time_threshold_)
+    }
+}
+
+type ScoreType = i32;
+const INF: ScoreType = 10000000;
+
+const H: i32 = 7;
+const W: i32 = 7;
+const END_TURN: i32 = 49;
+mod zobrist_hash {
+    use crate::H;
+    use crate::W;
+    use lazy_static::lazy_static;
+    use rand;
+    use rand::prelude::*;
+
+    lazy_static! {
+        pub static ref points: [[[u64; 10]; W as usize]; H as usize] = {
+            let mut mt_init_hash: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0 as u64);
+            let mut points_ = [[[0 as u64; 10]; W as usize]; H as usize];
+            for y in 0..H as usize {
+                for x in 0..W as usize {
+                    for p in 1..10 {
+                        points_[y][x][p] = mt_init_hash.gen();
+                    }
+                }
+            }
+            points_
+        };
+        pub static ref character: [[u64; W as usize]; H as usize] = {
+            let mut mt_init_hash: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0 as u64);
+            let mut character_ = [[0; W as usize]; H as usize];
+            for y in 0..H as usize {
+                for x in 0..W as usize {
+                    character_[y][x] = mt_init_hash.gen();
+                }
+            }
+            character_
+        };
+    }
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct DistanceCoord {
+    y_: i32,
+    x_: i32,
+    distance_: i32,
+}
+impl DistanceCoord {
+    pub fn new(y: i32, x: i32, distance: i32) -> Self {
+        Self {
+            y_: y,
+            x_: x,
+            distance_: distance,
+        }
+    }
+    pub fn newFromCoord(coord: &Coord) -> Self {
+        Self {
+            y_: coord.y_,
+            x_: coord.x_,
+            distance_: 0,
+        }
+    }
+}
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct WallMazeState {
+    points_: [[i32; W as usize]; H as usize],
+    walls_: [[i32; W as usize]; H as usize],
+    turn_: i32,
+    pub charcter_: Coord,
+    pub game_score_: i32,
+    pub evaluated_score_: ScoreType,
+    pub first_action_: usize,
+    pub hash_: u64,
+}
+
+impl WallMazeState {
+    const dx: [i32; 4] = [1, -1, 0, 0];
+    const dy: [i32; 4] = [0, 0, 1, -1];
+
+    fn getDistanceToNearestPoint(&self) -> i32 {
+        let mut que = VecDeque::new();
+        que.push_back(DistanceCoord::newFromCoord(&self.charcter_));
+        let mut check = [[false; W as usize]; H as usize];
+        while !que.is_empty() {
+            let tmp_cod = que.pop_front().unwrap();
+            if self.points_[tmp_cod.y_ as usize][tmp_cod.x_ as usize] > 0 {
+                return tmp_cod.distance_;
+            }
+            check[tmp_cod.y_ as usize][tmp_cod.x_ as usize] = true;
+            for action in 0..4 {
+                let ty = tmp_cod.y_ + Self::dy[action];
+                let tx = tmp_cod.x_ + Self::dx[action];
+                if ty >= 0
+                    && ty < H
+                    && tx >= 0
+                    && tx < W
+                    && self.walls_[ty as usize][tx as usize] == 0
+                    && !check[ty as usize][tx as usize]
+                {
+                    que.push_back(DistanceCoord::new(ty, tx, tmp_cod.distance_ + 1));
+                }
+            }
+        }
+        H * W
+    }
+
+    fn init_hash(&mut self) {
+        self.hash_ = 0;
+        self.hash_ ^=
+            zobrist_hash::character[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+        for y in 0..H {
+            for x in 0..W {
+                let point = self.points_[y as usize][x as usize];
+                if point > 0 {
+                    self.hash_ ^= zobrist_hash::points[y as usize][x as usize][point as usize];
+                }
+            }
+        }
+    }
+
+    pub fn new(seed: usize) -> Self {
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(seed as u64);
+        let mut maze_state = WallMazeState {
+            points_: [[0 as i32; W as usize]; H as usize],
+            walls_: [[0 as i32; W as usize]; H as usize],
+            turn_: 0,
+            charcter_: Coord { y_: 0, x_: 0 },
+            game_score_: 0,
+            evaluated_score_: 0,
+            first_action_: 0,
+            hash_: 0,
+        };
+        maze_state.charcter_.y_ = rng.gen_range(0..H);
+        maze_state.charcter_.x_ = rng.gen_range(0..W);
+        for y in (1..H).step_by(2) {
+            for x in (1..W).step_by(2) {
+                let mut ty = y;
+                let mut tx = x;
+                if ty == maze_state.charcter_.y_ && tx == maze_state.charcter_.x_ {
+                    continue;
+                }
+                maze_state.walls_[ty as usize][tx as usize] = 1;
+                let mut direction_size = 3;
+                if y == 1 {
+                    direction_size = 4;
+                }
+                let direction = rng.gen_range(0..direction_size);
+                ty += Self::dy[direction as usize];
+                tx += Self::dx[direction as usize];
+                if ty == maze_state.charcter_.y_ && tx == maze_state.charcter_.x_ {
+                    continue;
+                }
+                maze_state.walls_[ty as usize][tx as usize] = 1;
+            }
+        }
+
+        for y in 0..H {
+            for x in 0..W {
+                if y == maze_state.charcter_.y_ && x == maze_state.charcter_.x_ {
+                    continue;
+                }
+                maze_state.points_[y as usize][x as usize] = rng.gen_range(0..10);
+            }
+        }
+        maze_state.init_hash();
+        maze_state
+    }
+
+    pub fn isDone(&self) -> bool {
+        return self.turn_ == END_TURN;
+    }
+
+    pub fn evaluateScore(&mut self) {
+        self.evaluated_score_ = self.game_score_ * H * W - self.getDistanceToNearestPoint();
+    }
+
+    pub fn advance(&mut self, action: usize) {
+        self.hash_ ^=
+            zobrist_hash::character[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+
+        self.charcter_.x_ += Self::dx[action];
+        self.charcter_.y_ += Self::dy[action];
+        let point = &mut self.points_[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+        self.hash_ ^=
+            zobrist_hash::character[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+
+        if *point > 0 {
+            self.hash_ ^= zobrist_hash::points[self.charcter_.y_ as usize]
+                [self.charcter_.x_ as usize][*point as usize];
+            self.game_score_ += *point;
+            *point = 0;
+        }
+        self.turn_ += 1;
+    }
+    // advanceと同じ遷移をし、undoで元に戻すために拾った得点を返す
+    pub fn advanceWithUndo(&mut self, action: usize) -> i32 {
+        let taken_point = self.points_[(self.charcter_.y_ + Self::dy[action]) as usize]
+            [(self.charcter_.x_ + Self::dx[action]) as usize];
+        self.advance(action);
+        taken_point
+    }
+    pub fn undo(&mut self, action: usize, taken_point: i32) {
+        self.turn_ -= 1;
+        if taken_point > 0 {
+            self.points_[self.charcter_.y_ as usize][self.charcter_.x_ as usize] = taken_point;
+            self.game_score_ -= taken_point;
+            self.hash_ ^= zobrist_hash::points[self.charcter_.y_ as usize]
+                [self.charcter_.x_ as usize][taken_point as usize];
+        }
+        self.hash_ ^=
+            zobrist_hash::character[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+        self.charcter_.x_ -= Self::dx[action];
+        self.charcter_.y_ -= Self::dy[action];
+        self.hash_ ^=
+            zobrist_hash::character[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+    }
+    pub fn legalActions(&self) -> Vec<usize> {
+        let mut actions = Vec::new();
+        for action in 0..4 {
+            let ty = self.charcter_.y_ + Self::dy[action];
+            let tx = self.charcter_.x_ + Self::dx[action];
+            if ty >= 0 && ty < H && tx >= 0 && tx < W && self.walls_[ty as usize][tx as usize] == 0
+            {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+    pub fn toString(&self) -> String {
+        let mut s = String::new();
+        s += format!("turn:\t{}\n", self.turn_).as_str();
+        s += format!("score:\t{}\n", self.game_score_).as_str();
+        for h in 0..H {
+            for w in 0..W {
+                if self.walls_[h as usize][w as usize] == 1 {
+                    s.push('#');
+                } else if self.charcter_.y_ == h && self.charcter_.x_ == w {
+                    s.push('@');
+                } else if self.points_[h as usize][w as usize] > 0 {
+                    s += &self.points_[h as usize][w as usize].to_string();
+                } else {
+                    s.push('.');
+                }
+            }
+            s.push('\n');
+        }
+        s
+    }
+}
+impl Ord for WallMazeState {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.evaluated_score_.cmp(&other.evaluated_score_)
+    }
+}
+impl PartialOrd for WallMazeState {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+type State = WallMazeState;
+
+fn randomAction(state: &State) -> usize {
+    let mut rng = rand::thread_rng();
+
+    let legal_actions = state.legalActions();
+    return legal_actions[(rng.gen_range(0..=10) as usize % legal_actions.len())];
+}
+
+fn beamSearchAction(state: &State, beam_width: usize, beam_depth: usize) -> usize {
+    beamSearchActionWithMemory(state, beam_width, beam_depth).0
+}
+
+// 重複チェック用のHashSetが確保したバイト数。ハッシュ値の分だけを数え、制御用のバイトは数えない
+fn hashSetBytes(hash_check: &HashSet<u64>) -> usize {
+    hash_check.capacity() * std::mem::size_of::<u64>()
+}
+
+// 行動と、探索中に同時に確保した状態と重複チェック用のHashSetのバイト数の最大値を返す
+// 木のビームサーチと同じく、要素数ではなく確保済みの容量で数える
+fn beamSearchActionWithMemory(
+    state: &State,
+    beam_width: usize,
+    beam_depth: usize,
+) -> (usize, usize) {
+    let mut peak_bytes = 0;
+    let mut now_beam: BinaryHeap<State> = BinaryHeap::new();
+    let mut best_state: State = State::new(11);
+    now_beam.push(state.clone());
+    let mut hash_check = HashSet::new();
+    for t in 0..beam_depth {
+        let mut next_beam: BinaryHeap<State> = BinaryHeap::new();
+        for i in 0..beam_width {
+            if now_beam.is_empty() {
+                break;
+            }
+            let mut now_state = now_beam.pop().unwrap();
+            let legal_actions = now_state.legalActions();
+            for action in legal_actions {
+                let mut next_state = now_state.clone();
+                next_state.advance(action);
+                if t >= 1 && hash_check.contains(&next_state.hash_) {
+                    continue;
+                }
+                hash_check.insert(next_state.hash_);
+                next_state.evaluateScore();
+                if t == 0 {
+                    next_state.first_action_ = action;
+                }
+                next_beam.push(next_state);
+            }
+        }
+        // 次の行でnext_beamを複製するので、複製の分(要素数と同じ容量になる)も数える
+        peak_bytes = peak_bytes.max(
+            (now_beam.capacity() + next_beam.capacity() + next_beam.len())
+                * std::mem::size_of::<State>()
+                + hashSetBytes(&hash_check),
+        );
+        now_beam = next_beam.clone();
+        best_state = next_beam.pop().unwrap();
+        if best_state.isDone() {
+            break;
+        }
+    }
+    (best_state.first_action_, peak_bytes)
+}
+
+struct TreeNode {
+    parent_: usize,
+    action_: usize,
+    children_: Vec<usize>,
+}
+
+struct Candidate {
+    score_: ScoreType,
+    parent_: usize,
+    action_: usize,
+    is_done_: bool,
+}
+
+// 状態は1つだけ持ち、行動だけを保存した探索木をオイラーツアーの順に辿りながら
+// advanceとundoで差分更新するビームサーチ
+// 各深さで新しく作るのは候補の評価値だけで、状態のコピーは作らない
+struct TreeBeamSearch {
+    state_: State,
+    nodes_: Vec<TreeNode>,
+    free_ids_: Vec<usize>,
+    hash_check_: HashSet<u64>,
+}
+impl TreeBeamSearch {
+    const ROOT: usize = 0;
+
+    pub fn new(state: &State) -> Self {
+        Self {
+            state_: state.clone(),
+            nodes_: vec![TreeNode {
+                parent_: Self::ROOT,
+                action_: 0,
+                children_: Vec::new(),
+            }],
+            free_ids_: Vec::new(),
+            hash_check_: HashSet::new(),
+        }
+    }
+
+    fn addNode(&mut self, parent_id: usize, action: usize) -> usize {
+        let node = TreeNode {
+            parent_: parent_id,
+            action_: action,
+            children_: Vec::new(),
+        };
+        let node_id = match self.free_ids_.pop() {
+            Some(node_id) => {
+                self.nodes_[node_id] = node;
+                node_id
+            }
+            None => {
+                self.nodes_.push(node);
+                self.nodes_.len() - 1
+            }
+        };
+        self.nodes_[parent_id].children_.push(node_id);
+        node_id
+    }
+
+    // 子を持たなくなった葉を削除し、その結果子がなくなった祖先も削除する
+    fn removeLeaf(&mut self, node_id: usize) {
+        let mut now_id = node_id;
+        while now_id != Self::ROOT && self.nodes_[now_id].children_.is_empty() {
+            let parent_id = self.nodes_[now_id].parent_;
+            let children = &mut self.nodes_[parent_id].children_;
+            let index = children.iter().position(|&id| id == now_id).unwrap();
+            children.swap_remove(index);
+            self.free_ids_.push(now_id);
+            now_id = parent_id;
+        }
+    }
+
+    // 深さtarget_depthにある葉の全ての子を評価してcandidatesに入れる
+    fn collectCandidates(
+        &mut self,
+        node_id: usize,
+        depth: usize,
+        target_depth: usize,
+        candidates: &mut Vec<Candidate>,
+    ) {
+        if depth == target_depth {
+            if self.state_.isDone() {
+                return;
+            }
+            for action in self.state_.legalActions() {
+                let point = self.state_.advanceWithUndo(action);
+                if target_depth >= 1 && self.hash_check_.contains(&self.state_.hash_) {
+                    self.state_.undo(action, point);
+                    continue;
+                }
+                self.hash_check_.insert(self.state_.hash_);
+                self.state_.evaluateScore();
+                candidates.push(Candidate {
+                    score_: self.state_.evaluated_score_,
+                    parent_: node_id,
+                    action_: action,
+                    is_done_: self.state_.isDone(),
+                });
+                self.state_.undo(action, point);
+            }
+            return;
+        }
+        for i in 0..self.nodes_[node_id].children_.len() {
+            let child_id = self.nodes_[node_id].children_[i];
+            let action = self.nodes_[child_id].action_;
+            let point = self.state_.advanceWithUndo(action);
+            self.collectCandidates(child_id, depth + 1, target_depth, candidates);
+            self.state_.undo(action, point);
+        }
+    }
+
+    // 探索木と状態1つ分、重複チェック用のHashSetのバイト数
+    fn treeBytes(&self) -> usize {
+        let children_bytes: usize = self
+            .nodes_
+            .iter()
+            .map(|node| node.children_.capacity() * std::mem::size_of::<usize>())
+            .sum();
+        self.nodes_.capacity() * std::mem::size_of::<TreeNode>()
+            + children_bytes
+            + self.free_ids_.capacity() * std::mem::size_of::<usize>()
+            + std::mem::size_of::<State>()
+            + hashSetBytes(&self.hash_check_)
+    }
+
+    fn firstAction(&self, node_id: usize) -> usize {
+        let mut now_id = node_id;
+        while self.nodes_[now_id].parent_ != Self::ROOT {
+            now_id = self.nodes_[now_id].parent_;
+        }
+        self.nodes_[now_id].action_
+    }
+}
+
+fn treeBeamSearchAction(state: &State, beam_width: usize, beam_depth: usize) -> usize {
+    treeBeamSearchActionWithMemory(state, beam_width, beam_depth).0
+}
+
+// 行動と、探索木と候補が同時に使ったバイト数の最大値を返す
+fn treeBeamSearchActionWithMemory(
+    state: &State,
+    beam_width: usize,
+    beam_depth: usize,
+) -> (usize, usize) {
+    let mut peak_bytes = 0;
+    let mut search = TreeBeamSearch::new(state);
+    let mut leaves = vec![TreeBeamSearch::ROOT];
+    let mut best_leaf = TreeBeamSearch::ROOT;
+    for t in 0..beam_depth {
+        let mut candidates = Vec::new();
+        search.collectCandidates(TreeBeamSearch::ROOT, 0, t, &mut candidates);
+        if candidates.is_empty() {
+            break;
+        }
+        peak_bytes = peak_bytes
+            .max(search.treeBytes() + candidates.capacity() * std::mem::size_of::<Candidate>());
+        candidates.sort_by(|a, b| b.score_.cmp(&a.score_));
+        candidates.truncate(beam_width);
+
+        let mut next_leaves = Vec::new();
+        for candidate in candidates.iter() {
+            next_leaves.push(search.addNode(candidate.parent_, candidate.action_));
+        }
+        for leaf in leaves {
+            search.removeLeaf(leaf);
+        }
+        peak_bytes = peak_bytes
+            .max(search.treeBytes() + candidates.capacity() * std::mem::size_of::<Candidate>());
+        leaves = next_leaves;
+        best_leaf = leaves[0];
+        if candidates[0].is_done_ {
+            break;
+        }
+    }
+    (search.firstAction(best_leaf), peak_bytes)
+}
+
+type AIFunction = fn(&State) -> usize;
+type StringAIPair = (String, AIFunction);
+type MemoryFunction = fn(&State) -> (usize, usize);
+type StringMemoryPair = (String, MemoryFunction);
+
+// 探索同士を同じ盤面で比べられるよう、seedは0..game_numberに固定する
+fn testAiScore(ai: &StringAIPair, game_number: usize) {
+    let mut score_mean = 0.0;
+    for seed in 0..game_number {
+        let mut state = State::new(seed);
+        while !state.isDone() {
+            state.advance(ai.1(&state));
+        }
+        score_mean += state.game_score_ as f64;
+    }
+    score_mean /= game_number as f64;
+    println!("Score of {}:\t{}", ai.0, score_mean);
+}
+
+fn testAiSpeed(ai: &StringAIPair, game_number: usize, per_game_number: usize) {
+    let mut diff_sum = time::Duration::from_millis(0);
+    for seed in 0..game_number {
+        let state = State::new(seed);
+        let start_time = time::Instant::now();
+        for j in 0..per_game_number {
+            ai.1(&state);
+        }
+        diff_sum += start_time.elapsed();
+    }
+    let time_mean = (diff_sum.as_millis() as f64) / per_game_number as f64;
+    println!("Time of {}:\t{}ms", ai.0, time_mean);
+}
+
+// 各ゲームの初期状態から1回探索したときのメモリ使用量の最大値を平均する
+fn testAiMemory(ai: &StringMemoryPair, game_number: usize) {
+    let mut bytes_mean = 0.0;
+    for seed in 0..game_number {
+        let state = State::new(seed);
+        bytes_mean += ai.1(&state).1 as f64;
+    }
+    bytes_mean /= game_number as f64;
+    println!("Peak memory of {}:\t{:.1}KB", ai.0, bytes_mean / 1024.0);
+}
+
+fn main() {
+    let f0: AIFunction = |state: &State| {
+        return beamSearchAction(state, 100, END_TURN as usize);
+    };
+    let f1: AIFunction = |state: &State| {
+        return treeBeamSearchAction(state, 100, END_TURN as usize);
+    };
+    let ais: [StringAIPair; 2] = [
+        ("beamSearchAction".to_string(), f0),
+        ("treeBeamSearchAction".to_string(), f1),
+    ];
+    for ai in ais.iter() {
+        testAiScore(ai, 100);
+        testAiSpeed(ai, 100, 10);
+    }
+
+    let m0: MemoryFunction = |state: &State| {
+        return beamSearchActionWithMemory(state, 100, END_TURN as usize);
+    };
+    let m1: MemoryFunction = |state: &State| {
+        return treeBeamSearchActionWithMemory(state, 100, END_TURN as usize);
+    };
+    let memory_ais: [StringMemoryPair; 2] = [
+        ("beamSearchAction".to_string(), m0),
+        ("treeBeamSearchAction".to_string(), m1),
+    ];
+    for ai in memory_ais.iter() {
+        testAiMemory(ai, 100);
+    }
+}
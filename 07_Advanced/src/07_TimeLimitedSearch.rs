@@ -0,0 +1,472 @@
+use std::collections::{BinaryHeap, HashSet, VecDeque};
+use std::time;
+
+use rand;
+use rand::prelude::*;
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct Coord {
+    y_: i32,
+    x_: i32,
+}
+impl Coord {
+    pub fn new(y_: i32, x_: i32) -> Self {
+        Self { y_, x_ }
+    }
+}
+
+struct TimeKeeper {
+    start_time_: std::time::Instant,
+    time_threshold_: u64,
+}
+impl TimeKeeper {
+    pub fn new(time_threshold: u64) -> Self {
+        Self {
+            start_time_: time::Instant::now(),
+            time_threshold_: time_threshold,
+        }
+    }
+    pub fn isTimeOver(&self) -> bool {
+        self.start_time_.elapsed() > time::Duration::from_millis(self.time_threshold_)
+    }
+    pub fn remainingTime(&self) -> time::Duration {
+        time::Duration::from_millis(self.time_threshold_).saturating_sub(self.start_time_.elapsed())
+    }
+}
+
+type ScoreType = i32;
+const INF: ScoreType = 10000000;
+
+const H: i32 = 7;
+const W: i32 = 7;
+const END_TURN: i32 = 49;
+const INVALID_ACTION: usize = 100000;
+mod zobrist_hash {
+    use crate::H;
+    use crate::W;
+    use lazy_static::lazy_static;
+    use rand;
+    use rand::prelude::*;
+
+    lazy_static! {
+        pub static ref points: [[[u64; 10]; W as usize]; H as usize] = {
+            let mut mt_init_hash: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0 as u64);
+            let mut points_ = [[[0 as u64; 10]; W as usize]; H as usize];
+            for y in 0..H as usize {
+                for x in 0..W as usize {
+                    for p in 1..10 {
+                        points_[y][x][p] = mt_init_hash.gen();
+                    }
+                }
+            }
+            points_
+        };
+        pub static ref character: [[u64; W as usize]; H as usize] = {
+            let mut mt_init_hash: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0 as u64);
+            let mut character_ = [[0; W as usize]; H as usize];
+            for y in 0..H as usize {
+                for x in 0..W as usize {
+                    character_[y][x] = mt_init_hash.gen();
+                }
+            }
+            character_
+        };
+    }
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct DistanceCoord {
+    y_: i32,
+    x_: i32,
+    distance_: i32,
+}
+impl DistanceCoord {
+    pub fn new(y: i32, x: i32, distance: i32) -> Self {
+        Self {
+            y_: y,
+            x_: x,
+            distance_: distance,
+        }
+    }
+    pub fn newFromCoord(coord: &Coord) -> Self {
+        Self {
+            y_: coord.y_,
+            x_: coord.x_,
+            distance_: 0,
+        }
+    }
+}
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct WallMazeState {
+    points_: [[i32; W as usize]; H as usize],
+    walls_: [[i32; W as usize]; H as usize],
+    turn_: i32,
+    pub charcter_: Coord,
+    pub game_score_: i32,
+    pub evaluated_score_: ScoreType,
+    pub first_action_: usize,
+    pub hash_: u64,
+}
+
+impl WallMazeState {
+    const dx: [i32; 4] = [1, -1, 0, 0];
+    const dy: [i32; 4] = [0, 0, 1, -1];
+
+    fn getDistanceToNearestPoint(&self) -> i32 {
+        let mut que = VecDeque::new();
+        que.push_back(DistanceCoord::newFromCoord(&self.charcter_));
+        let mut check = [[false; W as usize]; H as usize];
+        while !que.is_empty() {
+            let tmp_cod = que.pop_front().unwrap();
+            if self.points_[tmp_cod.y_ as usize][tmp_cod.x_ as usize] > 0 {
+                return tmp_cod.distance_;
+            }
+            check[tmp_cod.y_ as usize][tmp_cod.x_ as usize] = true;
+            for action in 0..4 {
+                let ty = tmp_cod.y_ + Self::dy[action];
+                let tx = tmp_cod.x_ + Self::dx[action];
+                if ty >= 0
+                    && ty < H
+                    && tx >= 0
+                    && tx < W
+                    && self.walls_[ty as usize][tx as usize] == 0
+                    && !check[ty as usize][tx as usize]
+                {
+                    que.push_back(DistanceCoord::new(ty, tx, tmp_cod.distance_ + 1));
+                }
+            }
+        }
+        H * W
+    }
+
+    fn init_hash(&mut self) {
+        self.hash_ = 0;
+        self.hash_ ^=
+            zobrist_hash::character[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+        for y in 0..H {
+            for x in 0..W {
+                let point = self.points_[y as usize][x as usize];
+                if point > 0 {
+                    self.hash_ ^= zobrist_hash::points[y as usize][x as usize][point as usize];
+                }
+            }
+        }
+    }
+
+    pub fn new(seed: usize) -> Self {
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(seed as u64);
+        let mut maze_state = WallMazeState {
+            points_: [[0 as i32; W as usize]; H as usize],
+            walls_: [[0 as i32; W as usize]; H as usize],
+            turn_: 0,
+            charcter_: Coord { y_: 0, x_: 0 },
+            game_score_: 0,
+            evaluated_score_: 0,
+            first_action_: 0,
+            hash_: 0,
+        };
+        maze_state.charcter_.y_ = rng.gen_range(0..H);
+        maze_state.charcter_.x_ = rng.gen_range(0..W);
+        for y in (1..H).step_by(2) {
+            for x in (1..W).step_by(2) {
+                let mut ty = y;
+                let mut tx = x;
+                if ty == maze_state.charcter_.y_ && tx == maze_state.charcter_.x_ {
+                    continue;
+                }
+                maze_state.walls_[ty as usize][tx as usize] = 1;
+                let mut direction_size = 3;
+                if y == 1 {
+                    direction_size = 4;
+                }
+                let direction = rng.gen_range(0..direction_size);
+                ty += Self::dy[direction as usize];
+                tx += Self::dx[direction as usize];
+                if ty == maze_state.charcter_.y_ && tx == maze_state.charcter_.x_ {
+                    continue;
+                }
+                maze_state.walls_[ty as usize][tx as usize] = 1;
+            }
+        }
+
+        for y in 0..H {
+            for x in 0..W {
+                if y == maze_state.charcter_.y_ && x == maze_state.charcter_.x_ {
+                    continue;
+                }
+                maze_state.points_[y as usize][x as usize] = rng.gen_range(0..10);
+            }
+        }
+        maze_state.init_hash();
+        maze_state
+    }
+
+    pub fn isDone(&self) -> bool {
+        return self.turn_ == END_TURN;
+    }
+
+    pub fn evaluateScore(&mut self) {
+        self.evaluated_score_ = self.game_score_ * H * W - self.getDistanceToNearestPoint();
+    }
+
+    pub fn advance(&mut self, action: usize) {
+        self.hash_ ^=
+            zobrist_hash::character[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+
+        self.charcter_.x_ += Self::dx[action];
+        self.charcter_.y_ += Self::dy[action];
+        let point = &mut self.points_[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+        self.hash_ ^=
+            zobrist_hash::character[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+
+        if *point > 0 {
+            self.hash_ ^= zobrist_hash::points[self.charcter_.y_ as usize]
+                [self.charcter_.x_ as usize][*point as usize];
+            self.game_score_ += *point;
+            *point = 0;
+        }
+        self.turn_ += 1;
+    }
+    pub fn legalActions(&self) -> Vec<usize> {
+        let mut actions = Vec::new();
+        for action in 0..4 {
+            let ty = self.charcter_.y_ + Self::dy[action];
+            let tx = self.charcter_.x_ + Self::dx[action];
+            if ty >= 0 && ty < H && tx >= 0 && tx < W && self.walls_[ty as usize][tx as usize] == 0
+            {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+    pub fn toString(&self) -> String {
+        let mut s = String::new();
+        s += format!("turn:\t{}\n", self.turn_).as_str();
+        s += format!("score:\t{}\n", self.game_score_).as_str();
+        for h in 0..H {
+            for w in 0..W {
+                if self.walls_[h as usize][w as usize] == 1 {
+                    s.push('#');
+                } else if self.charcter_.y_ == h && self.charcter_.x_ == w {
+                    s.push('@');
+                } else if self.points_[h as usize][w as usize] > 0 {
+                    s += &self.points_[h as usize][w as usize].to_string();
+                } else {
+                    s.push('.');
+                }
+            }
+            s.push('\n');
+        }
+        s
+    }
+}
+impl Ord for WallMazeState {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.evaluated_score_.cmp(&other.evaluated_score_)
+    }
+}
+impl PartialOrd for WallMazeState {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+type State = WallMazeState;
+
+fn randomAction(state: &State) -> usize {
+    let mut rng = rand::thread_rng();
+
+    let legal_actions = state.legalActions();
+    return legal_actions[(rng.gen_range(0..=10) as usize % legal_actions.len())];
+}
+
+fn beamSearchAction(state: &State, beam_width: usize, beam_depth: usize) -> usize {
+    let mut now_beam: BinaryHeap<State> = BinaryHeap::new();
+    let mut best_state: State = State::new(11);
+    now_beam.push(state.clone());
+    let mut hash_check = HashSet::new();
+    for t in 0..beam_depth {
+        let mut next_beam: BinaryHeap<State> = BinaryHeap::new();
+        for i in 0..beam_width {
+            if now_beam.is_empty() {
+                break;
+            }
+            let mut now_state = now_beam.pop().unwrap();
+            let legal_actions = now_state.legalActions();
+            for action in legal_actions {
+                let mut next_state = now_state.clone();
+                next_state.advance(action);
+                if t >= 1 && hash_check.contains(&next_state.hash_) {
+                    continue;
+                }
+                hash_check.insert(next_state.hash_);
+                next_state.evaluateScore();
+                if t == 0 {
+                    next_state.first_action_ = action;
+                }
+                next_beam.push(next_state);
+            }
+        }
+        now_beam = next_beam.clone();
+        best_state = next_beam.pop().unwrap();
+        if best_state.isDone() {
+            break;
+        }
+    }
+    best_state.first_action_
+}
+
+// 1層目を幅1で展開して1状態あたりの展開時間を測り、残り時間を残りの深さで割った時間に収まるよう
+// 層ごとにビーム幅を決め直す
+fn beamSearchActionWithTimeThreshold(
+    state: &State,
+    beam_depth: usize,
+    time_threshold: u64,
+) -> usize {
+    let time_keeper = TimeKeeper::new(time_threshold);
+    let mut now_beam: BinaryHeap<State> = BinaryHeap::new();
+    let mut best_state: State = state.clone();
+    now_beam.push(state.clone());
+    let mut hash_check = HashSet::new();
+    let mut beam_width = 1;
+    for t in 0..beam_depth {
+        let layer_start_time = time::Instant::now();
+        let mut next_beam: BinaryHeap<State> = BinaryHeap::new();
+        let mut expanded_number = 0;
+        for _ in 0..beam_width {
+            if now_beam.is_empty() {
+                break;
+            }
+            let now_state = now_beam.pop().unwrap();
+            let legal_actions = now_state.legalActions();
+            for action in legal_actions {
+                let mut next_state = now_state.clone();
+                next_state.advance(action);
+                if t >= 1 && hash_check.contains(&next_state.hash_) {
+                    continue;
+                }
+                hash_check.insert(next_state.hash_);
+                next_state.evaluateScore();
+                if t == 0 {
+                    next_state.first_action_ = action;
+                }
+                next_beam.push(next_state);
+            }
+            expanded_number += 1;
+        }
+        if next_beam.is_empty() {
+            break;
+        }
+        best_state = next_beam.peek().unwrap().clone();
+        if best_state.isDone() || time_keeper.isTimeOver() {
+            break;
+        }
+        now_beam = next_beam;
+
+        let remaining_depth = (beam_depth - t - 1).max(1) as f64;
+        let cost_per_state = layer_start_time.elapsed().as_secs_f64() / expanded_number as f64;
+        let budget_per_layer = time_keeper.remainingTime().as_secs_f64() / remaining_depth;
+        beam_width = if cost_per_state > 0.0 {
+            ((budget_per_layer / cost_per_state) as usize).max(1)
+        } else {
+            beam_width * 2
+        };
+    }
+    best_state.first_action_
+}
+
+// 時間切れになるまでビームを1本ずつ追加していくChokudaiサーチ
+fn chokudaiSearchActionWithTimeThreshold(
+    state: &State,
+    beam_width: usize,
+    beam_depth: usize,
+    time_threshold: u64,
+) -> usize {
+    let time_keeper = TimeKeeper::new(time_threshold);
+    let mut beam = Vec::new();
+    for _ in 0..beam_depth + 1 {
+        beam.push(BinaryHeap::<State>::new());
+    }
+    beam[0].push(state.clone());
+    loop {
+        for t in 0..beam_depth {
+            for _ in 0..beam_width {
+                if beam[t].is_empty() {
+                    break;
+                }
+                if beam[t].peek().unwrap().isDone() {
+                    break;
+                }
+                let now_state = beam[t].pop().unwrap();
+                let legal_actions = now_state.legalActions();
+                for action in legal_actions {
+                    let mut next_state = now_state.clone();
+                    next_state.advance(action);
+                    next_state.evaluateScore();
+                    if t == 0 {
+                        next_state.first_action_ = action;
+                    }
+                    beam[t + 1].push(next_state);
+                }
+            }
+        }
+        if time_keeper.isTimeOver() {
+            break;
+        }
+    }
+    for t in 0..=beam_depth {
+        let now_beam = &beam[beam_depth - t];
+        if !now_beam.is_empty() {
+            return now_beam.peek().unwrap().first_action_;
+        }
+    }
+    INVALID_ACTION
+}
+
+type AIFunction = fn(&State) -> usize;
+type StringAIPair = (String, AIFunction);
+
+fn testAiScore(ai: &StringAIPair, game_number: usize) {
+    let mut rng = rand::thread_rng();
+    let mut score_mean = 0.0;
+    for i in 0..game_number {
+        let mut state = State::new(rng.gen());
+        while !state.isDone() {
+            state.advance(ai.1(&state));
+        }
+        score_mean += state.game_score_ as f64;
+    }
+    score_mean /= game_number as f64;
+    println!("Score of {}:\t{}", ai.0, score_mean);
+}
+
+fn testAiSpeed(ai: &StringAIPair, game_number: usize, per_game_number: usize) {
+    let mut diff_sum = time::Duration::from_millis(0);
+    let mut rng = rand::thread_rng();
+    for i in 0..game_number {
+        let mut state = State::new(rng.gen());
+        let start_time = time::Instant::now();
+        for j in 0..per_game_number {
+            ai.1(&state);
+        }
+        diff_sum += start_time.elapsed();
+    }
+    let time_mean = (diff_sum.as_millis() as f64) / per_game_number as f64;
+    println!("Time of {}:\t{}ms", ai.0, time_mean);
+}
+
+fn main() {
+    let f0: AIFunction = |state: &State| {
+        return beamSearchActionWithTimeThreshold(state, END_TURN as usize, 10);
+    };
+    let f1: AIFunction = |state: &State| {
+        return chokudaiSearchActionWithTimeThreshold(state, 1, END_TURN as usize, 10);
+    };
+    let ais: [StringAIPair; 2] = [
+        ("beamSearchActionWithTimeThreshold".to_string(), f0),
+        ("chokudaiSearchActionWithTimeThreshold".to_string(), f1),
+    ];
+    for ai in ais.iter() {
+        testAiScore(ai, 10);
+        testAiSpeed(ai, 10, 10);
+    }
+}
@@ -0,0 +1,550 @@
+use std::collections::{BinaryHeap, HashSet, VecDeque};
+use std::time;
+
+use rand;
+use rand::prelude::*;
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct Coord {
+    y_: i32,
+    x_: i32,
+}
+impl Coord {
+    pub fn new(y_: i32, x_: i32) -> Self {
+        Self { y_, x_ }
+    }
+}
+
+struct TimeKeeper {
+    start_time_: std::time::Instant,
+    time_threshold_: u64,
+}
+impl TimeKeeper {
+    pub fn new(time_threshold: u64) -> Self {
+        Self {
+            start_time_: time::Instant::now(),
+            time_threshold_: time_threshold,
+        }
+    }
+    pub fn isTimeOver(&self) -> bool {
+        self.start_time_.elapsed() > time::Duration::from_millis(self.time_threshold_)
+    }
+}
+
+type ScoreType = i32;
+const INF: ScoreType = 10000000;
+
+const H: i32 = 7;
+const W: i32 = 7;
+const END_TURN: i32 = 49;
+mod zobrist_hash {
+    use crate::H;
+    use crate::W;
+    use lazy_static::lazy_static;
+    use rand;
+    use rand::prelude::*;
+
+    lazy_static! {
+        pub static ref points: [[[u64; 10]; W as usize]; H as usize] = {
+            let mut mt_init_hash: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0 as u64);
+            let mut points_ = [[[0 as u64; 10]; W as usize]; H as usize];
+            for y in 0..H as usize {
+                for x in 0..W as usize {
+                    for p in 1..10 {
+                        points_[y][x][p] = mt_init_hash.gen();
+                    }
+                }
+            }
+            points_
+        };
+        pub static ref character: [[u64; W as usize]; H as usize] = {
+            let mut mt_init_hash: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0 as u64);
+            let mut character_ = [[0; W as usize]; H as usize];
+            for y in 0..H as usize {
+                for x in 0..W as usize {
+                    character_[y][x] = mt_init_hash.gen();
+                }
+            }
+            character_
+        };
+    }
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct DistanceCoord {
+    y_: i32,
+    x_: i32,
+    distance_: i32,
+}
+impl DistanceCoord {
+    pub fn new(y: i32, x: i32, distance: i32) -> Self {
+        Self {
+            y_: y,
+            x_: x,
+            distance_: distance,
+        }
+    }
+    pub fn newFromCoord(coord: &Coord) -> Self {
+        Self {
+            y_: coord.y_,
+            x_: coord.x_,
+            distance_: 0,
+        }
+    }
+}
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct WallMazeState {
+    points_: [[i32; W as usize]; H as usize],
+    walls_: [[i32; W as usize]; H as usize],
+    turn_: i32,
+    pub charcter_: Coord,
+    pub game_score_: i32,
+    pub evaluated_score_: ScoreType,
+    pub first_action_: usize,
+    pub hash_: u64,
+}
+
+impl WallMazeState {
+    const dx: [i32; 4] = [1, -1, 0, 0];
+    const dy: [i32; 4] = [0, 0, 1, -1];
+
+    fn getDistanceToNearestPoint(&self) -> i32 {
+        let mut que = VecDeque::new();
+        que.push_back(DistanceCoord::newFromCoord(&self.charcter_));
+        let mut check = [[false; W as usize]; H as usize];
+        while !que.is_empty() {
+            let tmp_cod = que.pop_front().unwrap();
+            if self.points_[tmp_cod.y_ as usize][tmp_cod.x_ as usize] > 0 {
+                return tmp_cod.distance_;
+            }
+            check[tmp_cod.y_ as usize][tmp_cod.x_ as usize] = true;
+            for action in 0..4 {
+                let ty = tmp_cod.y_ + Self::dy[action];
+                let tx = tmp_cod.x_ + Self::dx[action];
+                if ty >= 0
+                    && ty < H
+                    && tx >= 0
+                    && tx < W
+                    && self.walls_[ty as usize][tx as usize] == 0
+                    && !check[ty as usize][tx as usize]
+                {
+                    que.push_back(DistanceCoord::new(ty, tx, tmp_cod.distance_ + 1));
+                }
+            }
+        }
+        H * W
+    }
+
+    fn init_hash(&mut self) {
+        self.hash_ = 0;
+        self.hash_ ^=
+            zobrist_hash::character[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+        for y in 0..H {
+            for x in 0..W {
+                let point = self.points_[y as usize][x as usize];
+                if point > 0 {
+                    self.hash_ ^= zobrist_hash::points[y as usize][x as usize][point as usize];
+                }
+            }
+        }
+    }
+
+    pub fn new(seed: usize) -> Self {
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(seed as u64);
+        let mut maze_state = WallMazeState {
+            points_: [[0 as i32; W as usize]; H as usize],
+            walls_: [[0 as i32; W as usize]; H as usize],
+            turn_: 0,
+            charcter_: Coord { y_: 0, x_: 0 },
+            game_score_: 0,
+            evaluated_score_: 0,
+            first_action_: 0,
+            hash_: 0,
+        };
+        maze_state.charcter_.y_ = rng.gen_range(0..H);
+        maze_state.charcter_.x_ = rng.gen_range(0..W);
+        for y in (1..H).step_by(2) {
+            for x in (1..W).step_by(2) {
+                let mut ty = y;
+                let mut tx = x;
+                if ty == maze_state.charcter_.y_ && tx == maze_state.charcter_.x_ {
+                    continue;
+                }
+                maze_state.walls_[ty as usize][tx as usize] = 1;
+                let mut direction_size = 3;
+                if y == 1 {
+                    direction_size = 4;
+                }
+                let direction = rng.gen_range(0..direction_size);
+                ty += Self::dy[direction as usize];
+                tx += Self::dx[direction as usize];
+                if ty == maze_state.charcter_.y_ && tx == maze_state.charcter_.x_ {
+                    continue;
+                }
+                maze_state.walls_[ty as usize][tx as usize] = 1;
+            }
+        }
+
+        for y in 0..H {
+            for x in 0..W {
+                if y == maze_state.charcter_.y_ && x == maze_state.charcter_.x_ {
+                    continue;
+                }
+                maze_state.points_[y as usize][x as usize] = rng.gen_range(0..10);
+            }
+        }
+        maze_state.init_hash();
+        maze_state
+    }
+
+    pub fn isDone(&self) -> bool {
+        return self.turn_ == END_TURN;
+    }
+
+    pub fn evaluateScore(&mut self) {
+        self.evaluated_score_ = self.game_score_ * H * W - self.getDistanceToNearestPoint();
+    }
+
+    pub fn advance(&mut self, action: usize) {
+        self.hash_ ^=
+            zobrist_hash::character[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+
+        self.charcter_.x_ += Self::dx[action];
+        self.charcter_.y_ += Self::dy[action];
+        let point = &mut self.points_[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+        self.hash_ ^=
+            zobrist_hash::character[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+
+        if *point > 0 {
+            self.hash_ ^= zobrist_hash::points[self.charcter_.y_ as usize]
+                [self.charcter_.x_ as usize][*point as usize];
+            self.game_score_ += *point;
+            *point = 0;
+        }
+        self.turn_ += 1;
+    }
+    pub fn legalActions(&self) -> Vec<usize> {
+        let mut actions = Vec::new();
+        for action in 0..4 {
+            let ty = self.charcter_.y_ + Self::dy[action];
+            let tx = self.charcter_.x_ + Self::dx[action];
+            if ty >= 0 && ty < H && tx >= 0 && tx < W && self.walls_[ty as usize][tx as usize] == 0
+            {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+    pub fn toString(&self) -> String {
+        let mut s = String::new();
+        s += format!("turn:\t{}\n", self.turn_).as_str();
+        s += format!("score:\t{}\n", self.game_score_).as_str();
+        for h in 0..H {
+            for w in 0..W {
+                if self.walls_[h as usize][w as usize] == 1 {
+                    s.push('#');
+                } else if self.charcter_.y_ == h && self.charcter_.x_ == w {
+                    s.push('@');
+                } else if self.points_[h as usize][w as usize] > 0 {
+                    s += &self.points_[h as usize][w as usize].to_string();
+                } else {
+                    s.push('.');
+                }
+            }
+            s.push('\n');
+        }
+        s
+    }
+}
+impl Ord for WallMazeState {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.evaluated_score_.cmp(&other.evaluated_score_)
+    }
+}
+impl PartialOrd for WallMazeState {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+type State = WallMazeState;
+
+fn randomAction(state: &State) -> usize {
+    let mut rng = rand::thread_rng();
+
+    let legal_actions = state.legalActions();
+    return legal_actions[(rng.gen_range(0..=10) as usize % legal_actions.len())];
+}
+
+fn beamSearchAction(state: &State, beam_width: usize, beam_depth: usize) -> usize {
+    let mut now_beam: BinaryHeap<State> = BinaryHeap::new();
+    let mut best_state: State = State::new(11);
+    now_beam.push(state.clone());
+    let mut hash_check = HashSet::new();
+    for t in 0..beam_depth {
+        let mut next_beam: BinaryHeap<State> = BinaryHeap::new();
+        for i in 0..beam_width {
+            if now_beam.is_empty() {
+                break;
+            }
+            let mut now_state = now_beam.pop().unwrap();
+            let legal_actions = now_state.legalActions();
+            for action in legal_actions {
+                let mut next_state = now_state.clone();
+                next_state.advance(action);
+                if t >= 1 && hash_check.contains(&next_state.hash_) {
+                    continue;
+                }
+                hash_check.insert(next_state.hash_);
+                next_state.evaluateScore();
+                if t == 0 {
+                    next_state.first_action_ = action;
+                }
+                next_beam.push(next_state);
+            }
+        }
+        now_beam = next_beam.clone();
+        best_state = next_beam.pop().unwrap();
+        if best_state.isDone() {
+            break;
+        }
+    }
+    best_state.first_action_
+}
+
+// ワーカースレッドに渡す仕事。Expandは深さtの状態を全ての合法手で展開し、Evaluateは状態を評価する
+enum BeamJob {
+    Expand(usize, Vec<State>),
+    Evaluate(Vec<State>),
+}
+fn runBeamJob(job: BeamJob) -> Vec<State> {
+    match job {
+        BeamJob::Expand(t, states) => {
+            let mut next_states = Vec::new();
+            for now_state in states.iter() {
+                for action in now_state.legalActions() {
+                    let mut next_state = now_state.clone();
+                    next_state.advance(action);
+                    if t == 0 {
+                        next_state.first_action_ = action;
+                    }
+                    next_states.push(next_state);
+                }
+            }
+            next_states
+        }
+        BeamJob::Evaluate(mut states) => {
+            for state in states.iter_mut() {
+                state.evaluateScore();
+            }
+            states
+        }
+    }
+}
+
+// statesをスレッド数のチャンクに分け、先頭のチャンクは呼び出し元で、残りはワーカーで処理する
+// 結果はチャンクの順につなげて返すので、スレッド数によらず同じ並びになる
+fn distributeBeamJob(
+    states: Vec<State>,
+    job_senders: &[std::sync::mpsc::Sender<(usize, BeamJob)>],
+    result_receiver: &std::sync::mpsc::Receiver<(usize, Vec<State>)>,
+    make_job: impl Fn(Vec<State>) -> BeamJob,
+) -> Vec<State> {
+    let thread_number = job_senders.len() + 1;
+    let chunk_size = ((states.len() + thread_number - 1) / thread_number).max(1);
+    let mut chunks: Vec<Vec<State>> = Vec::new();
+    let mut states = states.into_iter().peekable();
+    while states.peek().is_some() {
+        chunks.push(states.by_ref().take(chunk_size).collect());
+    }
+    if chunks.is_empty() {
+        return Vec::new();
+    }
+    let chunk_number = chunks.len();
+    let mut chunks = chunks.into_iter();
+    let first_chunk = chunks.next().unwrap();
+    for (chunk_id, chunk) in chunks.enumerate() {
+        job_senders[chunk_id]
+            .send((chunk_id + 1, make_job(chunk)))
+            .unwrap();
+    }
+    let mut results = vec![Vec::new(); chunk_number];
+    results[0] = runBeamJob(make_job(first_chunk));
+    for _ in 1..chunk_number {
+        let (chunk_id, result) = result_receiver.recv().unwrap();
+        results[chunk_id] = result;
+    }
+    results.concat()
+}
+
+// ビームの各状態の展開(clone、advance)と評価(evaluateScore)をthread_number本のスレッドに分けて行う
+// 候補は(親の順位, 行動)の順に並べてから重複除去と上位選択をするので、
+// 結果はスレッド数によらず同じになる
+// ワーカースレッドは探索の最初に一度だけ立ち上げ、全ての深さで使い回す
+fn parallelBeamSearchAction(
+    state: &State,
+    beam_width: usize,
+    beam_depth: usize,
+    thread_number: usize,
+) -> usize {
+    let thread_number = thread_number.max(1);
+    std::thread::scope(|scope| {
+        let (result_sender, result_receiver) = std::sync::mpsc::channel();
+        let mut job_senders = Vec::new();
+        for _ in 1..thread_number {
+            let (job_sender, job_receiver) = std::sync::mpsc::channel::<(usize, BeamJob)>();
+            job_senders.push(job_sender);
+            let result_sender = result_sender.clone();
+            scope.spawn(move || {
+                for (chunk_id, job) in job_receiver {
+                    result_sender.send((chunk_id, runBeamJob(job))).unwrap();
+                }
+            });
+        }
+
+        let mut now_beam: Vec<State> = vec![state.clone()];
+        let mut best_state: State = state.clone();
+        let mut hash_check = HashSet::new();
+        for t in 0..beam_depth {
+            let expanded = distributeBeamJob(now_beam, &job_senders, &result_receiver, |chunk| {
+                BeamJob::Expand(t, chunk)
+            });
+
+            // 評価の前に重複を除いておく
+            let mut candidates = Vec::new();
+            for next_state in expanded.into_iter() {
+                if t >= 1 && hash_check.contains(&next_state.hash_) {
+                    continue;
+                }
+                hash_check.insert(next_state.hash_);
+                candidates.push(next_state);
+            }
+            if candidates.is_empty() {
+                break;
+            }
+            let candidates = distributeBeamJob(
+                candidates,
+                &job_senders,
+                &result_receiver,
+                BeamJob::Evaluate,
+            );
+
+            // 状態そのものは動かさず(評価値, 生成順)で並べ替えるので、同点の候補は生成順に選ばれる
+            let mut order: Vec<(ScoreType, usize)> = candidates
+                .iter()
+                .enumerate()
+                .map(|(i, candidate)| (-candidate.evaluated_score_, i))
+                .collect();
+            let select_number = beam_width.min(order.len());
+            if select_number < order.len() {
+                order.select_nth_unstable(select_number);
+            }
+            order.truncate(select_number);
+            order.sort_unstable();
+            let mut candidates: Vec<Option<State>> = candidates.into_iter().map(Some).collect();
+            let next_beam: Vec<State> = order
+                .iter()
+                .map(|&(_, i)| candidates[i].take().unwrap())
+                .collect();
+            best_state = next_beam[0].clone();
+            now_beam = next_beam;
+            if best_state.isDone() {
+                break;
+            }
+        }
+        // 送り口を閉じるとワーカーのループが終わり、scopeを抜けるときに合流する
+        drop(job_senders);
+        best_state.first_action_
+    })
+}
+
+type AIFunction = fn(&State) -> usize;
+type StringAIPair = (String, AIFunction);
+
+fn testAiScore(ai: &StringAIPair, game_number: usize) {
+    let mut rng = rand::thread_rng();
+    let mut score_mean = 0.0;
+    for i in 0..game_number {
+        let mut state = State::new(rng.gen());
+        while !state.isDone() {
+            state.advance(ai.1(&state));
+        }
+        score_mean += state.game_score_ as f64;
+    }
+    score_mean /= game_number as f64;
+    println!("Score of {}:\t{}", ai.0, score_mean);
+}
+
+// 平均の思考時間(ミリ秒)を表示して返す。AIどうしで比べられるよう、盤面はシード0..game_numberで固定する
+fn testAiSpeed(ai: &StringAIPair, game_number: usize, per_game_number: usize) -> f64 {
+    let mut diff_sum = time::Duration::from_millis(0);
+    for i in 0..game_number {
+        let state = State::new(i);
+        let start_time = time::Instant::now();
+        for _ in 0..per_game_number {
+            ai.1(&state);
+        }
+        diff_sum += start_time.elapsed();
+    }
+    let time_mean = diff_sum.as_secs_f64() * 1000.0 / (game_number * per_game_number) as f64;
+    println!("Time of {}:\t{:.3}ms", ai.0, time_mean);
+    time_mean
+}
+
+// スレッド数を変えても同じ行動列になることを確かめる。ずれたときに再現できるよう盤面はシードで固定する
+fn testDeterminism(game_number: usize) {
+    for seed in 0..game_number {
+        let mut state = State::new(seed);
+        while !state.isDone() {
+            let action = parallelBeamSearchAction(&state, 100, END_TURN as usize, 1);
+            for thread_number in [2, 4] {
+                assert_eq!(
+                    action,
+                    parallelBeamSearchAction(&state, 100, END_TURN as usize, thread_number),
+                    "seed {} turn {} threads {}",
+                    seed,
+                    state.turn_,
+                    thread_number
+                );
+            }
+            state.advance(action);
+        }
+    }
+    println!(
+        "Same actions for 1, 2 and 4 threads in {} games",
+        game_number
+    );
+}
+
+fn main() {
+    let f0: AIFunction = |state: &State| {
+        return beamSearchAction(state, 100, END_TURN as usize);
+    };
+    let f1: AIFunction = |state: &State| {
+        return parallelBeamSearchAction(state, 100, END_TURN as usize, 1);
+    };
+    let f2: AIFunction = |state: &State| {
+        return parallelBeamSearchAction(state, 100, END_TURN as usize, 2);
+    };
+    let f3: AIFunction = |state: &State| {
+        return parallelBeamSearchAction(state, 100, END_TURN as usize, 4);
+    };
+    let ais: [StringAIPair; 4] = [
+        ("beamSearchAction".to_string(), f0),
+        ("parallelBeamSearchAction(1 thread)".to_string(), f1),
+        ("parallelBeamSearchAction(2 threads)".to_string(), f2),
+        ("parallelBeamSearchAction(4 threads)".to_string(), f3),
+    ];
+    testAiScore(&ais[1], 100);
+    println!(
+        "available parallelism: {}",
+        std::thread::available_parallelism().map_or(1, |n| n.get())
+    );
+    testAiSpeed(&ais[0], 100, 10);
+    // 1スレッドの並列版に対する速度比を表示する。使えるコアが1つなら、スレッドを増やしても切り替えの分だけ遅くなる
+    let single_thread_time = testAiSpeed(&ais[1], 100, 10);
+    for ai in ais[2..].iter() {
+        let time = testAiSpeed(ai, 100, 10);
+        println!("Speedup of {}:\t{:.2}x", ai.0, single_thread_time / time);
+    }
+    testDeterminism(3);
+}
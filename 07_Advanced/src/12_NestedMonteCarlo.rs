@@ -0,0 +1,593 @@
+use std::collections::{BinaryHeap, HashSet, VecDeque};
+use std::time;
+
+use rand;
+use rand::prelude::*;
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct Coord {
+    y_: i32,
+    x_: i32,
+}
+impl Coord {
+    pub fn new(y_: i32, x_: i32) -> Self {
+        Self { y_, x_ }
+    }
+}
+
+struct TimeKeeper {
+    start_time_: std::time::Instant,
+    time_threshold_: u64,
+}
+impl TimeKeeper {
+    pub fn new(time_threshold: u64) -> Self {
+        Self {
+            start_time_: time::Instant::now(),
+            time_threshold_: time_threshold,
+        }
+    }
+    pub fn isTimeOver(&self) -> bool {
+        self.start_time_.elapsed() > time::Duration::from_millis(self.time_threshold_)
+    }
+    pub fn remainingTime(&self) -> time::Duration {
+        time::Duration::from_millis(self.time_threshold_).saturating_sub(self.start_time_.elapsed())
+    }
+}
+
+type ScoreType = i32;
+const INF: ScoreType = 10000000;
+
+const H: i32 = 7;
+const W: i32 = 7;
+const END_TURN: i32 = 49;
+mod zobrist_hash {
+    use crate::H;
+    use crate::W;
+    use lazy_static::lazy_static;
+    use rand;
+    use rand::prelude::*;
+
+    lazy_static! {
+        pub static ref points: [[[u64; 10]; W as usize]; H as usize] = {
+            let mut mt_init_hash: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0 as u64);
+            let mut points_ = [[[0 as u64; 10]; W as usize]; H as usize];
+            for y in 0..H as usize {
+                for x in 0..W as usize {
+                    for p in 1..10 {
+                        points_[y][x][p] = mt_init_hash.gen();
+                    }
+                }
+            }
+            points_
+        };
+        pub static ref character: [[u64; W as usize]; H as usize] = {
+            let mut mt_init_hash: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0 as u64);
+            let mut character_ = [[0; W as usize]; H as usize];
+            for y in 0..H as usize {
+                for x in 0..W as usize {
+                    character_[y][x] = mt_init_hash.gen();
+                }
+            }
+            character_
+        };
+    }
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct DistanceCoord {
+    y_: i32,
+    x_: i32,
+    distance_: i32,
+}
+impl DistanceCoord {
+    pub fn new(y: i32, x: i32, distance: i32) -> Self {
+        Self {
+            y_: y,
+            x_: x,
+            distance_: distance,
+        }
+    }
+    pub fn newFromCoord(coord: &Coord) -> Self {
+        Self {
+            y_: coord.y_,
+            x_: coord.x_,
+            distance_: 0,
+        }
+    }
+}
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct WallMazeState {
+    points_: [[i32; W as usize]; H as usize],
+    walls_: [[i32; W as usize]; H as usize],
+    turn_: i32,
+    pub charcter_: Coord,
+    pub game_score_: i32,
+    pub evaluated_score_: ScoreType,
+    pub first_action_: usize,
+    pub hash_: u64,
+}
+
+impl WallMazeState {
+    const dx: [i32; 4] = [1, -1, 0, 0];
+    const dy: [i32; 4] = [0, 0, 1, -1];
+
+    fn getDistanceToNearestPoint(&self) -> i32 {
+        let mut que = VecDeque::new();
+        que.push_back(DistanceCoord::newFromCoord(&self.charcter_));
+        let mut check = [[false; W as usize]; H as usize];
+        while !que.is_empty() {
+            let tmp_cod = que.pop_front().unwrap();
+            if self.points_[tmp_cod.y_ as usize][tmp_cod.x_ as usize] > 0 {
+                return tmp_cod.distance_;
+            }
+            check[tmp_cod.y_ as usize][tmp_cod.x_ as usize] = true;
+            for action in 0..4 {
+                let ty = tmp_cod.y_ + Self::dy[action];
+                let tx = tmp_cod.x_ + Self::dx[action];
+                if ty >= 0
+                    && ty < H
+                    && tx >= 0
+                    && tx < W
+                    && self.walls_[ty as usize][tx as usize] == 0
+                    && !check[ty as usize][tx as usize]
+                {
+                    que.push_back(DistanceCoord::new(ty, tx, tmp_cod.distance_ + 1));
+                }
+            }
+        }
+        H * W
+    }
+
+    fn init_hash(&mut self) {
+        self.hash_ = 0;
+        self.hash_ ^=
+            zobrist_hash::character[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+        for y in 0..H {
+            for x in 0..W {
+                let point = self.points_[y as usize][x as usize];
+                if point > 0 {
+                    self.hash_ ^= zobrist_hash::points[y as usize][x as usize][point as usize];
+                }
+            }
+        }
+    }
+
+    pub fn new(seed: usize) -> Self {
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(seed as u64);
+        let mut maze_state = WallMazeState {
+            points_: [[0 as i32; W as usize]; H as usize],
+            walls_: [[0 as i32; W as usize]; H as usize],
+            turn_: 0,
+            charcter_: Coord { y_: 0, x_: 0 },
+            game_score_: 0,
+            evaluated_score_: 0,
+            first_action_: 0,
+            hash_: 0,
+        };
+        maze_state.charcter_.y_ = rng.gen_range(0..H);
+        maze_state.charcter_.x_ = rng.gen_range(0..W);
+        for y in (1..H).step_by(2) {
+            for x in (1..W).step_by(2) {
+                let mut ty = y;
+                let mut tx = x;
+                if ty == maze_state.charcter_.y_ && tx == maze_state.charcter_.x_ {
+                    continue;
+                }
+                maze_state.walls_[ty as usize][tx as usize] = 1;
+                let mut direction_size = 3;
+                if y == 1 {
+                    direction_size = 4;
+                }
+                let direction = rng.gen_range(0..direction_size);
+                ty += Self::dy[direction as usize];
+                tx += Self::dx[direction as usize];
+                if ty == maze_state.charcter_.y_ && tx == maze_state.charcter_.x_ {
+                    continue;
+                }
+                maze_state.walls_[ty as usize][tx as usize] = 1;
+            }
+        }
+
+        for y in 0..H {
+            for x in 0..W {
+                if y == maze_state.charcter_.y_ && x == maze_state.charcter_.x_ {
+                    continue;
+                }
+                maze_state.points_[y as usize][x as usize] = rng.gen_range(0..10);
+            }
+        }
+        maze_state.init_hash();
+        maze_state
+    }
+
+    pub fn isDone(&self) -> bool {
+        return self.turn_ == END_TURN;
+    }
+
+    pub fn evaluateScore(&mut self) {
+        self.evaluated_score_ = self.game_score_ * H * W - self.getDistanceToNearestPoint();
+    }
+
+    pub fn advance(&mut self, action: usize) {
+        self.hash_ ^=
+            zobrist_hash::character[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+
+        self.charcter_.x_ += Self::dx[action];
+        self.charcter_.y_ += Self::dy[action];
+        let point = &mut self.points_[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+        self.hash_ ^=
+            zobrist_hash::character[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+
+        if *point > 0 {
+            self.hash_ ^= zobrist_hash::points[self.charcter_.y_ as usize]
+                [self.charcter_.x_ as usize][*point as usize];
+            self.game_score_ += *point;
+            *point = 0;
+        }
+        self.turn_ += 1;
+    }
+    pub fn legalActions(&self) -> Vec<usize> {
+        let mut actions = Vec::new();
+        for action in 0..4 {
+            let ty = self.charcter_.y_ + Self::dy[action];
+            let tx = self.charcter_.x_ + Self::dx[action];
+            if ty >= 0 && ty < H && tx >= 0 && tx < W && self.walls_[ty as usize][tx as usize] == 0
+            {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+    pub fn toString(&self) -> String {
+        let mut s = String::new();
+        s += format!("turn:\t{}\n", self.turn_).as_str();
+        s += format!("score:\t{}\n", self.game_score_).as_str();
+        for h in 0..H {
+            for w in 0..W {
+                if self.walls_[h as usize][w as usize] == 1 {
+                    s.push('#');
+                } else if self.charcter_.y_ == h && self.charcter_.x_ == w {
+                    s.push('@');
+                } else if self.points_[h as usize][w as usize] > 0 {
+                    s += &self.points_[h as usize][w as usize].to_string();
+                } else {
+                    s.push('.');
+                }
+            }
+            s.push('\n');
+        }
+        s
+    }
+}
+impl Ord for WallMazeState {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.evaluated_score_.cmp(&other.evaluated_score_)
+    }
+}
+impl PartialOrd for WallMazeState {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+type State = WallMazeState;
+
+fn randomAction(state: &State) -> usize {
+    let mut rng = rand::thread_rng();
+
+    let legal_actions = state.legalActions();
+    return legal_actions[(rng.gen_range(0..=10) as usize % legal_actions.len())];
+}
+
+fn beamSearchAction(state: &State, beam_width: usize, beam_depth: usize) -> usize {
+    let mut now_beam: BinaryHeap<State> = BinaryHeap::new();
+    let mut best_state: State = State::new(11);
+    now_beam.push(state.clone());
+    let mut hash_check = HashSet::new();
+    for t in 0..beam_depth {
+        let mut next_beam: BinaryHeap<State> = BinaryHeap::new();
+        for i in 0..beam_width {
+            if now_beam.is_empty() {
+                break;
+            }
+            let mut now_state = now_beam.pop().unwrap();
+            let legal_actions = now_state.legalActions();
+            for action in legal_actions {
+                let mut next_state = now_state.clone();
+                next_state.advance(action);
+                if t >= 1 && hash_check.contains(&next_state.hash_) {
+                    continue;
+                }
+                hash_check.insert(next_state.hash_);
+                next_state.evaluateScore();
+                if t == 0 {
+                    next_state.first_action_ = action;
+                }
+                next_beam.push(next_state);
+            }
+        }
+        now_beam = next_beam.clone();
+        best_state = next_beam.pop().unwrap();
+        if best_state.isDone() {
+            break;
+        }
+    }
+    best_state.first_action_
+}
+
+// 1層目を幅1で展開して1状態あたりの展開時間を測り、残り時間を残りの深さで割った時間に収まるよう
+// 層ごとにビーム幅を決め直す
+fn beamSearchActionWithTimeThreshold(
+    state: &State,
+    beam_depth: usize,
+    time_threshold: u64,
+) -> usize {
+    let time_keeper = TimeKeeper::new(time_threshold);
+    let mut now_beam: BinaryHeap<State> = BinaryHeap::new();
+    let mut best_state: State = state.clone();
+    now_beam.push(state.clone());
+    let mut hash_check = HashSet::new();
+    let mut beam_width = 1;
+    for t in 0..beam_depth {
+        let layer_start_time = time::Instant::now();
+        let mut next_beam: BinaryHeap<State> = BinaryHeap::new();
+        let mut expanded_number = 0;
+        for _ in 0..beam_width {
+            if now_beam.is_empty() {
+                break;
+            }
+            let now_state = now_beam.pop().unwrap();
+            let legal_actions = now_state.legalActions();
+            for action in legal_actions {
+                let mut next_state = now_state.clone();
+                next_state.advance(action);
+                if t >= 1 && hash_check.contains(&next_state.hash_) {
+                    continue;
+                }
+                hash_check.insert(next_state.hash_);
+                next_state.evaluateScore();
+                if t == 0 {
+                    next_state.first_action_ = action;
+                }
+                next_beam.push(next_state);
+            }
+            expanded_number += 1;
+        }
+        if next_beam.is_empty() {
+            break;
+        }
+        best_state = next_beam.peek().unwrap().clone();
+        if best_state.isDone() || time_keeper.isTimeOver() {
+            break;
+        }
+        now_beam = next_beam;
+
+        let remaining_depth = (beam_depth - t - 1).max(1) as f64;
+        let cost_per_state = layer_start_time.elapsed().as_secs_f64() / expanded_number as f64;
+        let budget_per_layer = time_keeper.remainingTime().as_secs_f64() / remaining_depth;
+        beam_width = if cost_per_state > 0.0 {
+            ((budget_per_layer / cost_per_state) as usize).max(1)
+        } else {
+            beam_width * 2
+        };
+    }
+    best_state.first_action_
+}
+
+// ランダムに終局まで進め、(最終得点, 行動列)を返す
+fn playout(state: &State, rng: &mut rand::rngs::StdRng) -> (i32, Vec<usize>) {
+    let mut now_state = state.clone();
+    let mut actions = Vec::new();
+    while !now_state.isDone() {
+        let legal_actions = now_state.legalActions();
+        let action = legal_actions[rng.gen_range(0..legal_actions.len())];
+        now_state.advance(action);
+        actions.push(action);
+    }
+    (now_state.game_score_, actions)
+}
+
+// Nested Monte Carlo Search
+// 各手で全ての合法手をlevel-1の探索で評価し、それまでに見つかった最良の行動列に沿って1手進める
+// 時間切れになった後は残りの評価をランダムプレイアウトで済ませる
+fn nestedMonteCarloSearch(
+    state: &State,
+    level: usize,
+    time_keeper: &TimeKeeper,
+    rng: &mut rand::rngs::StdRng,
+) -> (i32, Vec<usize>) {
+    if level == 0 || state.isDone() {
+        return playout(state, rng);
+    }
+    let mut now_state = state.clone();
+    let mut actions = Vec::new();
+    let mut best_score = -1;
+    let mut best_actions: Vec<usize> = Vec::new();
+    while !now_state.isDone() {
+        for action in now_state.legalActions() {
+            let mut next_state = now_state.clone();
+            next_state.advance(action);
+            let next_level = if time_keeper.isTimeOver() {
+                0
+            } else {
+                level - 1
+            };
+            let (score, rest_actions) =
+                nestedMonteCarloSearch(&next_state, next_level, time_keeper, rng);
+            if score > best_score {
+                best_score = score;
+                best_actions = actions.clone();
+                best_actions.push(action);
+                best_actions.extend(rest_actions);
+            }
+        }
+        let action = best_actions[actions.len()];
+        now_state.advance(action);
+        actions.push(action);
+    }
+    (best_score, best_actions)
+}
+
+fn nestedMonteCarloSearchActionWithTimeThreshold(
+    state: &State,
+    level: usize,
+    time_threshold: u64,
+) -> usize {
+    let time_keeper = TimeKeeper::new(time_threshold);
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0);
+    let mut best_score = -1;
+    let mut best_action = state.legalActions()[0];
+    loop {
+        let (score, actions) = nestedMonteCarloSearch(state, level, &time_keeper, &mut rng);
+        if score > best_score {
+            best_score = score;
+            best_action = actions[0];
+        }
+        if time_keeper.isTimeOver() {
+            break;
+        }
+    }
+    best_action
+}
+
+const NRPA_ALPHA: f64 = 1.0;
+const NRPA_ITERATION_NUMBER: usize = 100;
+
+// NRPAの方策で使う行動の番号。キャラクターのいるマスと移動方向の組で表す
+fn policyCode(state: &State, action: usize) -> usize {
+    ((state.charcter_.y_ * W + state.charcter_.x_) * 4) as usize + action
+}
+
+fn policyPlayout(
+    state: &State,
+    policy: &Vec<f64>,
+    rng: &mut rand::rngs::StdRng,
+) -> (i32, Vec<usize>) {
+    let mut now_state = state.clone();
+    let mut actions = Vec::new();
+    while !now_state.isDone() {
+        let legal_actions = now_state.legalActions();
+        let weights: Vec<f64> = legal_actions
+            .iter()
+            .map(|&action| policy[policyCode(&now_state, action)].exp())
+            .collect();
+        let mut r = rng.gen::<f64>() * weights.iter().sum::<f64>();
+        let mut action = legal_actions[legal_actions.len() - 1];
+        for i in 0..legal_actions.len() {
+            if r < weights[i] {
+                action = legal_actions[i];
+                break;
+            }
+            r -= weights[i];
+        }
+        now_state.advance(action);
+        actions.push(action);
+    }
+    (now_state.game_score_, actions)
+}
+
+// 最良の行動列を取る確率が上がるように方策を更新する
+fn adaptPolicy(state: &State, policy: &Vec<f64>, actions: &Vec<usize>) -> Vec<f64> {
+    let mut new_policy = policy.clone();
+    let mut now_state = state.clone();
+    for &action in actions.iter() {
+        let legal_actions = now_state.legalActions();
+        let z: f64 = legal_actions
+            .iter()
+            .map(|&a| policy[policyCode(&now_state, a)].exp())
+            .sum();
+        new_policy[policyCode(&now_state, action)] += NRPA_ALPHA;
+        for &a in legal_actions.iter() {
+            let code = policyCode(&now_state, a);
+            new_policy[code] -= NRPA_ALPHA * policy[code].exp() / z;
+        }
+        now_state.advance(action);
+    }
+    new_policy
+}
+
+// Nested Rollout Policy Adaptation
+fn nrpa(
+    state: &State,
+    level: usize,
+    policy: &Vec<f64>,
+    time_keeper: &TimeKeeper,
+    rng: &mut rand::rngs::StdRng,
+) -> (i32, Vec<usize>) {
+    if level == 0 || state.isDone() {
+        return policyPlayout(state, policy, rng);
+    }
+    let mut best_score = -1;
+    let mut best_actions = Vec::new();
+    let mut policy = policy.clone();
+    for _ in 0..NRPA_ITERATION_NUMBER {
+        let (score, actions) = nrpa(state, level - 1, &policy, time_keeper, rng);
+        if score >= best_score {
+            best_score = score;
+            best_actions = actions;
+        }
+        policy = adaptPolicy(state, &policy, &best_actions);
+        if time_keeper.isTimeOver() {
+            break;
+        }
+    }
+    (best_score, best_actions)
+}
+
+fn nrpaActionWithTimeThreshold(state: &State, level: usize, time_threshold: u64) -> usize {
+    let time_keeper = TimeKeeper::new(time_threshold);
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0);
+    let policy = vec![0.0; (H * W * 4) as usize];
+    let mut best_score = -1;
+    let mut best_action = state.legalActions()[0];
+    loop {
+        let (score, actions) = nrpa(state, level, &policy, &time_keeper, &mut rng);
+        if score > best_score {
+            best_score = score;
+            best_action = actions[0];
+        }
+        if time_keeper.isTimeOver() {
+            break;
+        }
+    }
+    best_action
+}
+
+type AIFunction = fn(&State) -> usize;
+type StringAIPair = (String, AIFunction);
+
+// 手法ごとの差を比べやすいように、盤面はseed 0から順に固定する
+fn testAiScore(ai: &StringAIPair, game_number: usize) {
+    let mut score_mean = 0.0;
+    for i in 0..game_number {
+        let mut state = State::new(i);
+        while !state.isDone() {
+            state.advance(ai.1(&state));
+        }
+        score_mean += state.game_score_ as f64;
+    }
+    score_mean /= game_number as f64;
+    println!("Score of {}:\t{}", ai.0, score_mean);
+}
+
+fn main() {
+    let f0: AIFunction = |state: &State| {
+        return beamSearchActionWithTimeThreshold(state, END_TURN as usize, 10);
+    };
+    let f1: AIFunction = |state: &State| {
+        return nestedMonteCarloSearchActionWithTimeThreshold(state, 2, 10);
+    };
+    let f2: AIFunction = |state: &State| {
+        return nrpaActionWithTimeThreshold(state, 2, 10);
+    };
+    let ais: [StringAIPair; 3] = [
+        ("beamSearchActionWithTimeThreshold".to_string(), f0),
+        (
+            "nestedMonteCarloSearchActionWithTimeThreshold".to_string(),
+            f1,
+        ),
+        ("nrpaActionWithTimeThreshold".to_string(), f2),
+    ];
+    for ai in ais.iter() {
+        testAiScore(ai, 10);
+    }
+}
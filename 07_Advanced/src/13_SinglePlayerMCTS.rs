@@ -0,0 +1,524 @@
+use std::collections::{BinaryHeap, HashSet, VecDeque};
+use std::time;
+
+use rand;
+use rand::prelude::*;
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct Coord {
+    y_: i32,
+    x_: i32,
+}
+impl Coord {
+    pub fn new(y_: i32, x_: i32) -> Self {
+        Self { y_, x_ }
+    }
+}
+
+struct TimeKeeper {
+    start_time_: std::time::Instant,
+    time_threshold_: u64,
+}
+impl TimeKeeper {
+    pub fn new(time_threshold: u64) -> Self {
+        Self {
+            start_time_: time::Instant::now(),
+            time_threshold_: time_threshold,
+        }
+    }
+    pub fn isTimeOver(&self) -> bool {
+        self.start_time_.elapsed() > time::Duration::from_millis(self.time_threshold_)
+    }
+}
+
+type ScoreType = i32;
+const INF: ScoreType = 10000000;
+
+const H: i32 = 7;
+const W: i32 = 7;
+const END_TURN: i32 = 49;
+mod zobrist_hash {
+    use crate::H;
+    use crate::W;
+    use lazy_static::lazy_static;
+    use rand;
+    use rand::prelude::*;
+
+    lazy_static! {
+        pub static ref points: [[[u64; 10]; W as usize]; H as usize] = {
+            let mut mt_init_hash: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0 as u64);
+            let mut points_ = [[[0 as u64; 10]; W as usize]; H as usize];
+            for y in 0..H as usize {
+                for x in 0..W as usize {
+                    for p in 1..10 {
+                        points_[y][x][p] = mt_init_hash.gen();
+                    }
+                }
+            }
+            points_
+        };
+        pub static ref character: [[u64; W as usize]; H as usize] = {
+            let mut mt_init_hash: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(0 as u64);
+            let mut character_ = [[0; W as usize]; H as usize];
+            for y in 0..H as usize {
+                for x in 0..W as usize {
+                    character_[y][x] = mt_init_hash.gen();
+                }
+            }
+            character_
+        };
+    }
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct DistanceCoord {
+    y_: i32,
+    x_: i32,
+    distance_: i32,
+}
+impl DistanceCoord {
+    pub fn new(y: i32, x: i32, distance: i32) -> Self {
+        Self {
+            y_: y,
+            x_: x,
+            distance_: distance,
+        }
+    }
+    pub fn newFromCoord(coord: &Coord) -> Self {
+        Self {
+            y_: coord.y_,
+            x_: coord.x_,
+            distance_: 0,
+        }
+    }
+}
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct WallMazeState {
+    points_: [[i32; W as usize]; H as usize],
+    walls_: [[i32; W as usize]; H as usize],
+    turn_: i32,
+    pub charcter_: Coord,
+    pub game_score_: i32,
+    pub evaluated_score_: ScoreType,
+    pub first_action_: usize,
+    pub hash_: u64,
+}
+
+impl WallMazeState {
+    const dx: [i32; 4] = [1, -1, 0, 0];
+    const dy: [i32; 4] = [0, 0, 1, -1];
+
+    fn getDistanceToNearestPoint(&self) -> i32 {
+        let mut que = VecDeque::new();
+        que.push_back(DistanceCoord::newFromCoord(&self.charcter_));
+        let mut check = [[false; W as usize]; H as usize];
+        while !que.is_empty() {
+            let tmp_cod = que.pop_front().unwrap();
+            if self.points_[tmp_cod.y_ as usize][tmp_cod.x_ as usize] > 0 {
+                return tmp_cod.distance_;
+            }
+            check[tmp_cod.y_ as usize][tmp_cod.x_ as usize] = true;
+            for action in 0..4 {
+                let ty = tmp_cod.y_ + Self::dy[action];
+                let tx = tmp_cod.x_ + Self::dx[action];
+                if ty >= 0
+                    && ty < H
+                    && tx >= 0
+                    && tx < W
+                    && self.walls_[ty as usize][tx as usize] == 0
+                    && !check[ty as usize][tx as usize]
+                {
+                    que.push_back(DistanceCoord::new(ty, tx, tmp_cod.distance_ + 1));
+                }
+            }
+        }
+        H * W
+    }
+
+    fn init_hash(&mut self) {
+        self.hash_ = 0;
+        self.hash_ ^=
+            zobrist_hash::character[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+        for y in 0..H {
+            for x in 0..W {
+                let point = self.points_[y as usize][x as usize];
+                if point > 0 {
+                    self.hash_ ^= zobrist_hash::points[y as usize][x as usize][point as usize];
+                }
+            }
+        }
+    }
+
+    pub fn new(seed: usize) -> Self {
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(seed as u64);
+        let mut maze_state = WallMazeState {
+            points_: [[0 as i32; W as usize]; H as usize],
+            walls_: [[0 as i32; W as usize]; H as usize],
+            turn_: 0,
+            charcter_: Coord { y_: 0, x_: 0 },
+            game_score_: 0,
+            evaluated_score_: 0,
+            first_action_: 0,
+            hash_: 0,
+        };
+        maze_state.charcter_.y_ = rng.gen_range(0..H);
+        maze_state.charcter_.x_ = rng.gen_range(0..W);
+        for y in (1..H).step_by(2) {
+            for x in (1..W).step_by(2) {
+                let mut ty = y;
+                let mut tx = x;
+                if ty == maze_state.charcter_.y_ && tx == maze_state.charcter_.x_ {
+                    continue;
+                }
+                maze_state.walls_[ty as usize][tx as usize] = 1;
+                let mut direction_size = 3;
+                if y == 1 {
+                    direction_size = 4;
+                }
+                let direction = rng.gen_range(0..direction_size);
+                ty += Self::dy[direction as usize];
+                tx += Self::dx[direction as usize];
+                if ty == maze_state.charcter_.y_ && tx == maze_state.charcter_.x_ {
+                    continue;
+                }
+                maze_state.walls_[ty as usize][tx as usize] = 1;
+            }
+        }
+
+        for y in 0..H {
+            for x in 0..W {
+                if y == maze_state.charcter_.y_ && x == maze_state.charcter_.x_ {
+                    continue;
+                }
+                maze_state.points_[y as usize][x as usize] = rng.gen_range(0..10);
+            }
+        }
+        maze_state.init_hash();
+        maze_state
+    }
+
+    pub fn isDone(&self) -> bool {
+        return self.turn_ == END_TURN;
+    }
+
+    pub fn evaluateScore(&mut self) {
+        self.evaluated_score_ = self.game_score_ * H * W - self.getDistanceToNearestPoint();
+    }
+
+    pub fn advance(&mut self, action: usize) {
+        self.hash_ ^=
+            zobrist_hash::character[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+
+        self.charcter_.x_ += Self::dx[action];
+        self.charcter_.y_ += Self::dy[action];
+        let point = &mut self.points_[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+        self.hash_ ^=
+            zobrist_hash::character[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+
+        if *point > 0 {
+            self.hash_ ^= zobrist_hash::points[self.charcter_.y_ as usize]
+                [self.charcter_.x_ as usize][*point as usize];
+            self.game_score_ += *point;
+            *point = 0;
+        }
+        self.turn_ += 1;
+    }
+    pub fn legalActions(&self) -> Vec<usize> {
+        let mut actions = Vec::new();
+        for action in 0..4 {
+            let ty = self.charcter_.y_ + Self::dy[action];
+            let tx = self.charcter_.x_ + Self::dx[action];
+            if ty >= 0 && ty < H && tx >= 0 && tx < W && self.walls_[ty as usize][tx as usize] == 0
+            {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+    pub fn toString(&self) -> String {
+        let mut s = String::new();
+        s += format!("turn:\t{}\n", self.turn_).as_str();
+        s += format!("score:\t{}\n", self.game_score_).as_str();
+        for h in 0..H {
+            for w in 0..W {
+                if self.walls_[h as usize][w as usize] == 1 {
+                    s.push('#');
+                } else if self.charcter_.y_ == h && self.charcter_.x_ == w {
+                    s.push('@');
+                } else if self.points_[h as usize][w as usize] > 0 {
+                    s += &self.points_[h as usize][w as usize].to_string();
+                } else {
+                    s.push('.');
+                }
+            }
+            s.push('\n');
+        }
+        s
+    }
+}
+impl Ord for WallMazeState {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.evaluated_score_.cmp(&other.evaluated_score_)
+    }
+}
+impl PartialOrd for WallMazeState {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+type State = WallMazeState;
+
+fn randomAction(state: &State) -> usize {
+    let mut rng = rand::thread_rng();
+
+    let legal_actions = state.legalActions();
+    return legal_actions[(rng.gen_range(0..=10) as usize % legal_actions.len())];
+}
+
+fn beamSearchAction(state: &State, beam_width: usize, beam_depth: usize) -> usize {
+    let mut now_beam: BinaryHeap<State> = BinaryHeap::new();
+    let mut best_state: State = State::new(11);
+    now_beam.push(state.clone());
+    let mut hash_check = HashSet::new();
+    for t in 0..beam_depth {
+        let mut next_beam: BinaryHeap<State> = BinaryHeap::new();
+        for i in 0..beam_width {
+            if now_beam.is_empty() {
+                break;
+            }
+            let mut now_state = now_beam.pop().unwrap();
+            let legal_actions = now_state.legalActions();
+            for action in legal_actions {
+                let mut next_state = now_state.clone();
+                next_state.advance(action);
+                if t >= 1 && hash_check.contains(&next_state.hash_) {
+                    continue;
+                }
+                hash_check.insert(next_state.hash_);
+                next_state.evaluateScore();
+                if t == 0 {
+                    next_state.first_action_ = action;
+                }
+                next_beam.push(next_state);
+            }
+        }
+        now_beam = next_beam.clone();
+        best_state = next_beam.pop().unwrap();
+        if best_state.isDone() {
+            break;
+        }
+    }
+    best_state.first_action_
+}
+
+pub mod sp_mcts {
+    use rand::Rng;
+
+    use crate::State;
+    use crate::H;
+    use crate::INF;
+    use crate::W;
+
+    const C: f64 = 0.5;
+    // 分散項に足す定数。訪問回数が少ないノードの分散を大きめに見積もる
+    const D: f64 = 0.01;
+    const EXPAND_THRESHOLD: usize = 10;
+
+    // 探索全体で共有する情報
+    struct Context {
+        rng_: rand::rngs::StdRng,
+        score_scale_: f64,
+        actions_: Vec<usize>,
+        best_score_: i32,
+        best_actions_: Vec<usize>,
+    }
+    impl Context {
+        // 根からの行動列actions_にrest_actionsを続けた行動列で、scoreが得られた
+        fn record(&mut self, score: i32, rest_actions: &[usize]) {
+            if score > self.best_score_ {
+                self.best_score_ = score;
+                self.best_actions_ = self.actions_.clone();
+                self.best_actions_.extend_from_slice(rest_actions);
+            }
+        }
+    }
+
+    fn playout(state: &State, rng: &mut rand::rngs::StdRng) -> (i32, Vec<usize>) {
+        let mut now_state = state.clone();
+        let mut actions = Vec::new();
+        while !now_state.isDone() {
+            let legal_actions = now_state.legalActions();
+            let action = legal_actions[rng.gen_range(0..legal_actions.len())];
+            now_state.advance(action);
+            actions.push(action);
+        }
+        (now_state.game_score_, actions)
+    }
+
+    // 勝敗ではなく、得点を[0, 1]に正規化した値を逆伝播する
+    struct Node {
+        state_: State,
+        w_: f64,
+        w2_: f64,
+        pub child_nodes: Vec<Self>,
+        pub n_: f64,
+        legal_actions_: Vec<usize>,
+    }
+    impl Node {
+        pub fn new(state: &State) -> Self {
+            Self {
+                state_: state.clone(),
+                w_: 0.0,
+                w2_: 0.0,
+                child_nodes: Vec::new(),
+                n_: 0.0,
+                legal_actions_: Vec::new(),
+            }
+        }
+
+        fn update(&mut self, value: f64) {
+            self.w_ += value;
+            self.w2_ += value * value;
+            self.n_ += 1.0;
+        }
+
+        fn evaluate(&mut self, context: &mut Context) -> f64 {
+            if self.state_.isDone() {
+                context.record(self.state_.game_score_, &[]);
+                let value = self.state_.game_score_ as f64 / context.score_scale_;
+                self.update(value);
+                return value;
+            }
+            if self.child_nodes.is_empty() {
+                let (score, rest_actions) = playout(&self.state_, &mut context.rng_);
+                context.record(score, &rest_actions);
+                let value = score as f64 / context.score_scale_;
+                self.update(value);
+                if self.n_ == EXPAND_THRESHOLD as f64 {
+                    self.expand();
+                }
+                return value;
+            } else {
+                let index = self.nextChildIndex();
+                context.actions_.push(self.legal_actions_[index]);
+                let value = self.child_nodes[index].evaluate(context);
+                context.actions_.pop();
+                self.update(value);
+                return value;
+            }
+        }
+
+        fn expand(&mut self) {
+            self.legal_actions_ = self.state_.legalActions();
+            self.child_nodes.clear();
+            for &action in self.legal_actions_.iter() {
+                let mut add_state = Self::new(&self.state_);
+                add_state.state_.advance(action);
+                self.child_nodes.push(add_state);
+            }
+        }
+
+        // UCB1に、報酬の分散が大きい子ほど大きくなる項を加えて選ぶ
+        fn nextChildIndex(&self) -> usize {
+            for i in 0..self.child_nodes.len() {
+                if self.child_nodes[i].n_ == 0.0 {
+                    return i;
+                }
+            }
+            let mut t = 0.0;
+            for child_node in self.child_nodes.iter() {
+                t += child_node.n_;
+            }
+            let mut best_value = -INF as f64;
+            let mut best_action_index = 0;
+            for i in 0..self.child_nodes.len() {
+                let child_node = &self.child_nodes[i];
+                let mean = child_node.w_ / child_node.n_;
+                let variance = (child_node.w2_ - child_node.n_ * mean * mean + D) / child_node.n_;
+                let ucb_value =
+                    mean + C * (2.0 * t.ln() / child_node.n_).sqrt() + variance.max(0.0).sqrt();
+                if ucb_value > best_value {
+                    best_action_index = i;
+                    best_value = ucb_value;
+                }
+            }
+            best_action_index
+        }
+    }
+
+    // 探索中に見つかった最も得点の高い行動列を返す
+    pub fn spMctsActions(state: &State, playout_number: usize) -> Vec<usize> {
+        let mut remaining_point = 0;
+        for y in 0..H as usize {
+            for x in 0..W as usize {
+                remaining_point += state.points_[y][x];
+            }
+        }
+        let mut context = Context {
+            rng_: rand::SeedableRng::seed_from_u64(0),
+            score_scale_: (state.game_score_ + remaining_point).max(1) as f64,
+            actions_: Vec::new(),
+            best_score_: -1,
+            best_actions_: Vec::new(),
+        };
+        let mut root_node = Node::new(state);
+        root_node.expand();
+        for _ in 0..playout_number {
+            root_node.evaluate(&mut context);
+        }
+        context.best_actions_
+    }
+
+    pub fn spMctsAction(state: &State, playout_number: usize) -> usize {
+        spMctsActions(state, playout_number)[0]
+    }
+}
+
+type AIFunction = fn(&State) -> usize;
+type StringAIPair = (String, AIFunction);
+
+fn testAiScore(ai: &StringAIPair, game_number: usize) {
+    let mut rng = rand::thread_rng();
+    let mut score_mean = 0.0;
+    for i in 0..game_number {
+        let mut state = State::new(rng.gen());
+        while !state.isDone() {
+            state.advance(ai.1(&state));
+        }
+        score_mean += state.game_score_ as f64;
+    }
+    score_mean /= game_number as f64;
+    println!("Score of {}:\t{}", ai.0, score_mean);
+}
+
+fn testAiSpeed(ai: &StringAIPair, game_number: usize, per_game_number: usize) {
+    let mut diff_sum = time::Duration::from_millis(0);
+    let mut rng = rand::thread_rng();
+    for i in 0..game_number {
+        let mut state = State::new(rng.gen());
+        let start_time = time::Instant::now();
+        for j in 0..per_game_number {
+            ai.1(&state);
+        }
+        diff_sum += start_time.elapsed();
+    }
+    let time_mean = (diff_sum.as_millis() as f64) / per_game_number as f64;
+    println!("Time of {}:\t{}ms", ai.0, time_mean);
+}
+
+fn main() {
+    let f0: AIFunction = |state: &State| {
+        return beamSearchAction(state, 100, END_TURN as usize);
+    };
+    let f1: AIFunction = |state: &State| {
+        return sp_mcts::spMctsAction(state, 3000);
+    };
+    let ais: [StringAIPair; 2] = [
+        ("beamSearchAction".to_string(), f0),
+        ("spMctsAction".to_string(), f1),
+    ];
+    for ai in ais.iter() {
+        testAiScore(ai, 10);
+        testAiSpeed(ai, 10, 1);
+    }
+}
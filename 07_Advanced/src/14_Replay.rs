@@ -0,0 +1,942 @@
+use std::collections::BinaryHeap;
+use std::io::{self, BufRead, Write};
+use std::{thread, time};
+
+use rand;
+use rand::prelude::*;
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+struct Coord {
+    y_: i32,
+    x_: i32,
+}
+impl Coord {
+    pub fn new(y_: i32, x_: i32) -> Self {
+        Self { y_, x_ }
+    }
+}
+
+type ScoreType = i32;
+
+const H: i32 = 5;
+const W: i32 = 5;
+const END_TURN: i32 = 4;
+const dstr: [&str; 4] = ["RIGHT", "LEFT", "DOWN", "UP"];
+const INVALID_ACTION: usize = 100000;
+
+// 端末の表示に使うANSIエスケープシーケンス
+const RESET: &str = "\x1b[0m";
+const CLEAR: &str = "\x1b[2J\x1b[H";
+const PLAYER_COLOR: &str = "\x1b[1;33m";
+const WALL_COLOR: &str = "\x1b[90m";
+
+// 得点が大きいマスほど目立つ色で表示する
+fn pointColor(point: i32) -> &'static str {
+    match point {
+        1..=3 => "\x1b[32m",
+        4..=6 => "\x1b[36m",
+        _ => "\x1b[1;35m",
+    }
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct WallMazeState {
+    points_: [[i32; W as usize]; H as usize],
+    walls_: [[i32; W as usize]; H as usize],
+    turn_: i32,
+    pub charcter_: Coord,
+    pub game_score_: i32,
+    pub evaluated_score_: ScoreType,
+    pub fist_action_: usize,
+    // ActionTreeの中でこの盤面に至った行動のノード番号
+    pub node_id_: usize,
+}
+impl WallMazeState {
+    const dx: [i32; 4] = [1, -1, 0, 0];
+    const dy: [i32; 4] = [0, 0, 1, -1];
+    pub fn new(seed: usize) -> Self {
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(seed as u64);
+        let mut maze_state = WallMazeState {
+            points_: [[0 as i32; W as usize]; H as usize],
+            walls_: [[0 as i32; W as usize]; H as usize],
+            turn_: 0,
+            charcter_: Coord { y_: 0, x_: 0 },
+            game_score_: 0,
+            evaluated_score_: 0,
+            fist_action_: 0,
+            node_id_: 0,
+        };
+        maze_state.charcter_.y_ = rng.gen_range(0..H);
+        maze_state.charcter_.x_ = rng.gen_range(0..W);
+        for y in (1..H).step_by(2) {
+            for x in (1..W).step_by(2) {
+                let mut ty = y;
+                let mut tx = x;
+                if ty == maze_state.charcter_.y_ && tx == maze_state.charcter_.x_ {
+                    continue;
+                }
+                maze_state.walls_[ty as usize][tx as usize] = 1;
+                let mut direction_size = 3;
+                if y == 1 {
+                    direction_size = 4;
+                }
+                let direction = rng.gen_range(0..direction_size);
+                ty += Self::dy[direction as usize];
+                tx += Self::dx[direction as usize];
+                if ty == maze_state.charcter_.y_ && tx == maze_state.charcter_.x_ {
+                    continue;
+                }
+                maze_state.walls_[ty as usize][tx as usize] = 1;
+            }
+        }
+
+        for y in 0..H {
+            for x in 0..W {
+                if y == maze_state.charcter_.y_ && x == maze_state.charcter_.x_ {
+                    continue;
+                }
+                let point = rng.gen_range(0..10);
+                // 障害物の下の得点は取れず盤面にも出ないので0にする。乱数は引いて他のマスの得点を変えない
+                if maze_state.walls_[y as usize][x as usize] == 0 {
+                    maze_state.points_[y as usize][x as usize] = point;
+                }
+            }
+        }
+        maze_state
+    }
+
+    pub fn isDone(&self) -> bool {
+        return self.turn_ == END_TURN;
+    }
+
+    pub fn evaluateScore(&mut self) {
+        self.evaluated_score_ = self.game_score_;
+    }
+
+    pub fn advance(&mut self, action: usize) {
+        self.charcter_.x_ += Self::dx[action];
+        self.charcter_.y_ += Self::dy[action];
+        let point = &mut self.points_[self.charcter_.y_ as usize][self.charcter_.x_ as usize];
+        if *point > 0 {
+            self.game_score_ += *point;
+            *point = 0;
+        }
+        self.turn_ += 1;
+    }
+    pub fn legalActions(&self) -> Vec<usize> {
+        let mut actions = Vec::new();
+        for action in 0..4 {
+            let ty = self.charcter_.y_ + Self::dy[action];
+            let tx = self.charcter_.x_ + Self::dx[action];
+            if ty >= 0 && ty < H && tx >= 0 && tx < W && self.walls_[ty as usize][tx as usize] == 0
+            {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+    pub fn toString(&self) -> String {
+        let mut s = String::new();
+        s += format!("turn:\t{}\n", self.turn_).as_str();
+        s += format!("score:\t{}\n", self.game_score_).as_str();
+        for h in 0..H {
+            for w in 0..W {
+                if self.walls_[h as usize][w as usize] == 1 {
+                    s.push('#');
+                } else if self.charcter_.y_ == h && self.charcter_.x_ == w {
+                    s.push('@');
+                } else if self.points_[h as usize][w as usize] > 0 {
+                    s += &self.points_[h as usize][w as usize].to_string();
+                } else {
+                    s.push('.');
+                }
+            }
+            s.push('\n');
+        }
+        s
+    }
+    // toStringと同じ盤面に色を付けて返す
+    pub fn toColorString(&self) -> String {
+        let mut s = String::new();
+        s += format!("turn:\t{}\n", self.turn_).as_str();
+        s += format!("score:\t{}{}{}\n", PLAYER_COLOR, self.game_score_, RESET).as_str();
+        for h in 0..H {
+            for w in 0..W {
+                let point = self.points_[h as usize][w as usize];
+                if self.walls_[h as usize][w as usize] == 1 {
+                    s += format!("{}#{}", WALL_COLOR, RESET).as_str();
+                } else if self.charcter_.y_ == h && self.charcter_.x_ == w {
+                    s += format!("{}@{}", PLAYER_COLOR, RESET).as_str();
+                } else if point > 0 {
+                    s += format!("{}{}{}", pointColor(point), point, RESET).as_str();
+                } else {
+                    s.push('.');
+                }
+            }
+            s.push('\n');
+        }
+        s
+    }
+}
+impl Ord for WallMazeState {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.evaluated_score_.cmp(&other.evaluated_score_)
+    }
+}
+impl PartialOrd for WallMazeState {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+type State = WallMazeState;
+
+fn randomAction(state: &State) -> usize {
+    let mut rng = rand::thread_rng();
+
+    let legal_actions = state.legalActions();
+    return legal_actions[(rng.gen_range(0..=10) as usize % legal_actions.len())];
+}
+
+// 棋譜の形式のバージョン。形式を変えたら上げる
+const RECORD_VERSION: usize = 2;
+const RECORD_DIRECTORY: &str = "records";
+
+fn parseNumbers<T: std::str::FromStr>(words: &[&str]) -> Result<Vec<T>, String> {
+    words
+        .iter()
+        .map(|word| {
+            word.parse::<T>()
+                .map_err(|_| format!("invalid number: {}", word))
+        })
+        .collect()
+}
+fn parseAction(word: &str) -> Result<usize, String> {
+    dstr.iter()
+        .position(|name| *name == word)
+        .ok_or(format!("invalid action: {}", word))
+}
+fn gridToString(grid: &[[i32; W as usize]; H as usize]) -> String {
+    let mut ss = String::new();
+    for row in grid.iter() {
+        let values: Vec<String> = row.iter().map(|value| value.to_string()).collect();
+        ss += values.join(" ").as_str();
+        ss += "\n";
+    }
+    ss
+}
+
+// 棋譜を1行ずつ読む。各行は「キー 値...」の形で、空行と#で始まる行は読み飛ばす
+struct RecordReader<'a> {
+    lines_: std::str::Lines<'a>,
+}
+impl<'a> RecordReader<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            lines_: text.lines(),
+        }
+    }
+    pub fn readWords(&mut self) -> Result<Vec<&'a str>, String> {
+        loop {
+            match self.lines_.next() {
+                None => return Err("unexpected end of record".to_string()),
+                Some(line) => {
+                    let line = line.trim();
+                    if !line.is_empty() && !line.starts_with('#') {
+                        return Ok(line.split_whitespace().collect());
+                    }
+                }
+            }
+        }
+    }
+    // keyで始まる行を読み、残りの値を返す
+    pub fn read(&mut self, key: &str) -> Result<Vec<&'a str>, String> {
+        let words = self.readWords()?;
+        if words[0] != key {
+            return Err(format!("expected {} but found {}", key, words[0]));
+        }
+        Ok(words[1..].to_vec())
+    }
+    // keyの行の値が1つの数字であるときに、その数字を返す
+    pub fn readNumber<T: std::str::FromStr>(&mut self, key: &str) -> Result<T, String> {
+        let mut values = parseNumbers::<T>(&self.read(key)?)?;
+        if values.len() != 1 {
+            return Err(format!("{} must have exactly one value", key));
+        }
+        Ok(values.pop().unwrap())
+    }
+    // keyの行に続くH行W列の数字を読む
+    pub fn readGrid(&mut self, key: &str) -> Result<[[i32; W as usize]; H as usize], String> {
+        self.read(key)?;
+        let mut grid = [[0; W as usize]; H as usize];
+        for y in 0..H as usize {
+            let values = parseNumbers::<i32>(&self.readWords()?)?;
+            if values.len() != W as usize {
+                return Err(format!("row {} of {} must have {} values", y, key, W));
+            }
+            grid[y].copy_from_slice(&values);
+        }
+        Ok(grid)
+    }
+}
+
+// 盤面のH行を読み、得点、壁、キャラクターの位置を返す
+// 数字は得点、.は得点のないマス、#は壁、@はキャラクターを表す
+fn parseGrid(
+    rows: &[&str],
+) -> Result<
+    (
+        [[i32; W as usize]; H as usize],
+        [[i32; W as usize]; H as usize],
+        Vec<Coord>,
+    ),
+    String,
+> {
+    if rows.len() != H as usize {
+        return Err(format!("board must have {} rows but has {}", H, rows.len()));
+    }
+    let mut points = [[0; W as usize]; H as usize];
+    let mut walls = [[0; W as usize]; H as usize];
+    let mut characters = Vec::new();
+    for (y, row) in rows.iter().enumerate() {
+        let cells: Vec<char> = row.chars().collect();
+        if cells.len() != W as usize {
+            return Err(format!("row {} must have {} cells: {}", y, W, row));
+        }
+        for (x, &cell) in cells.iter().enumerate() {
+            match cell {
+                '0'..='9' => points[y][x] = cell.to_digit(10).unwrap() as i32,
+                '.' => {}
+                '#' => walls[y][x] = 1,
+                '@' => characters.push(Coord::new(y as i32, x as i32)),
+                _ => return Err(format!("invalid cell {} at ({}, {})", cell, y, x)),
+            }
+        }
+    }
+    Ok((points, walls, characters))
+}
+
+impl std::str::FromStr for WallMazeState {
+    type Err = String;
+    // toStringの出力を読み込む。turn:とscore:の行は省略できる
+    // 障害物のマスには得点を置かず、キャラクターのいるマスの得点は取られて0になっているので、どちらも0として読む
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut state = State::default();
+        let mut rows = Vec::new();
+        for line in text.lines().map(|line| line.trim()) {
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(value) = line.strip_prefix("turn:") {
+                state.turn_ = parseNumbers::<i32>(&[value.trim()])?[0];
+            } else if let Some(value) = line.strip_prefix("score:") {
+                state.game_score_ = parseNumbers::<i32>(&[value.trim()])?[0];
+            } else {
+                rows.push(line);
+            }
+        }
+        let (points, walls, characters) = parseGrid(&rows)?;
+        if characters.len() != 1 {
+            return Err(format!(
+                "board must have one @ but has {}",
+                characters.len()
+            ));
+        }
+        state.points_ = points;
+        state.walls_ = walls;
+        state.charcter_ = characters[0];
+        Ok(state)
+    }
+}
+impl WallMazeState {
+    // AtCoder形式の入力を読み込む
+    // 1行目にH W T、続くH行に盤面(数字、.、#)、最後の行にキャラクターの位置y xを書く
+    pub fn fromInput(text: &str) -> Result<Self, String> {
+        let mut lines = text
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty());
+        let header = lines.next().ok_or("empty input")?;
+        let size = parseNumbers::<i32>(&header.split_whitespace().collect::<Vec<_>>())?;
+        if size != [H, W, END_TURN] {
+            return Err(format!(
+                "H W T must be {} {} {}: {}",
+                H, W, END_TURN, header
+            ));
+        }
+        let rows: Vec<&str> = lines.by_ref().take(H as usize).collect();
+        let (points, walls, characters) = parseGrid(&rows)?;
+        if !characters.is_empty() {
+            return Err("character must be given after the board".to_string());
+        }
+        let position = lines.next().ok_or("missing character position")?;
+        let position = parseNumbers::<i32>(&position.split_whitespace().collect::<Vec<_>>())?;
+        if position.len() != 2 {
+            return Err("character position must be y x".to_string());
+        }
+        let (y, x) = (position[0], position[1]);
+        if y < 0 || y >= H || x < 0 || x >= W || walls[y as usize][x as usize] == 1 {
+            return Err(format!("character ({}, {}) is not on a floor", y, x));
+        }
+        let mut state = State::default();
+        state.points_ = points;
+        state.walls_ = walls;
+        state.charcter_ = Coord::new(y, x);
+        // キャラクターの初期位置の得点は取れないので0にしておく
+        state.points_[y as usize][x as usize] = 0;
+        Ok(state)
+    }
+}
+
+// 問題ファイルを読み込む。1行目が3つの数字ならAtCoder形式、そうでなければtoStringの形式として読む
+fn loadProblem(path: &str) -> Result<State, String> {
+    let text = std::fs::read_to_string(path).map_err(|error| error.to_string())?;
+    let first_line = text
+        .lines()
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty())
+        .unwrap_or("");
+    let words: Vec<&str> = first_line.split_whitespace().collect();
+    if words.len() == 3 && parseNumbers::<i32>(&words).is_ok() {
+        State::fromInput(&text)
+    } else {
+        text.parse()
+    }
+}
+
+// 1試合分の記録。初期盤面と行動列から、各ターンの盤面を再生して持つ
+struct Replay {
+    pub seed_: usize,
+    pub actions_: Vec<usize>,
+    // 各手を決めるのにかかった時間(ミリ秒)
+    pub thinking_times_: Vec<f64>,
+    states_: Vec<State>,
+}
+impl Replay {
+    pub fn new(seed: usize) -> Self {
+        Self::fromState(seed, State::new(seed))
+    }
+    pub fn fromState(seed: usize, state: State) -> Self {
+        Self {
+            seed_: seed,
+            actions_: Vec::new(),
+            thinking_times_: Vec::new(),
+            states_: vec![state],
+        }
+    }
+    // 記録したシードと行動列から試合を再生する
+    pub fn fromActions(seed: usize, actions: &[usize]) -> Self {
+        let mut replay = Self::new(seed);
+        for &action in actions {
+            replay.push(action, 0.0);
+        }
+        replay
+    }
+    pub fn push(&mut self, action: usize, thinking_time: f64) {
+        let mut next_state = self.lastState().clone();
+        next_state.advance(action);
+        self.actions_.push(action);
+        self.thinking_times_.push(thinking_time);
+        self.states_.push(next_state);
+    }
+    pub fn lastTurn(&self) -> usize {
+        self.actions_.len()
+    }
+    pub fn lastState(&self) -> &State {
+        self.states_.last().unwrap()
+    }
+    // turnの盤面を、そのターンに至った行動と一緒に表示用の文字列にする
+    pub fn render(&self, turn: usize) -> String {
+        let mut ss = String::new();
+        ss += format!("seed:\t{}\t({}/{})\n", self.seed_, turn, self.lastTurn()).as_str();
+        if turn > 0 {
+            ss += format!(
+                "action:\t{}\t({:.3}ms)\n",
+                dstr[self.actions_[turn - 1]],
+                self.thinking_times_[turn - 1]
+            )
+            .as_str();
+        } else {
+            ss += "action:\t-\n";
+        }
+        ss += self.states_[turn].toColorString().as_str();
+        ss
+    }
+
+    // 棋譜を文字列にする。シードだけでなく初期盤面も書くので、盤面の作り方が変わっても再生できる
+    pub fn toRecord(&self) -> String {
+        let initial_state = &self.states_[0];
+        let mut ss = String::new();
+        ss += format!("version\t{}\n", RECORD_VERSION).as_str();
+        ss += "game\tWallMazeState\n";
+        ss += format!("board\t{}\t{}\t{}\n", H, W, END_TURN).as_str();
+        ss += format!("seed\t{}\n", self.seed_).as_str();
+        // 途中の盤面から始めた試合も再生できるよう、初期盤面のターン数と得点も書く
+        ss += format!("turn\t{}\n", initial_state.turn_).as_str();
+        ss += format!("score\t{}\n", initial_state.game_score_).as_str();
+        ss += format!(
+            "character\t{}\t{}\n",
+            initial_state.charcter_.y_, initial_state.charcter_.x_
+        )
+        .as_str();
+        ss += "points\n";
+        ss += gridToString(&initial_state.points_).as_str();
+        ss += "walls\n";
+        ss += gridToString(&initial_state.walls_).as_str();
+        ss += format!("actions\t{}\n", self.lastTurn()).as_str();
+        for (action, thinking_time) in self.actions_.iter().zip(self.thinking_times_.iter()) {
+            ss += format!("{}\t{:.3}\n", dstr[*action], thinking_time).as_str();
+        }
+        ss += format!("result\t{}\n", self.lastState().game_score_).as_str();
+        ss
+    }
+    // 棋譜を読んで初期盤面から再生し、行動が合法で結果が書かれた得点と一致するか確かめる
+    pub fn fromRecord(text: &str) -> Result<Self, String> {
+        let mut reader = RecordReader::new(text);
+        let version = reader.readNumber::<usize>("version")?;
+        if version != RECORD_VERSION {
+            return Err(format!("unsupported version: {}", version));
+        }
+        let game = reader.read("game")?;
+        if game != ["WallMazeState"] {
+            return Err(format!("unsupported game: {}", game.join(" ")));
+        }
+        let board = parseNumbers::<i32>(&reader.read("board")?)?;
+        if board != [H, W, END_TURN] {
+            return Err(format!(
+                "board {:?} does not match {} {} {}",
+                board, H, W, END_TURN
+            ));
+        }
+        let seed = reader.readNumber::<usize>("seed")?;
+        let mut state = State::default();
+        state.turn_ = reader.readNumber::<i32>("turn")?;
+        if state.turn_ < 0 || state.turn_ > END_TURN {
+            return Err(format!("turn {} is out of the game", state.turn_));
+        }
+        state.game_score_ = reader.readNumber::<i32>("score")?;
+        let character = parseNumbers::<i32>(&reader.read("character")?)?;
+        if character.len() != 2 {
+            return Err("character must have y and x".to_string());
+        }
+        state.charcter_ = Coord::new(character[0], character[1]);
+        state.points_ = reader.readGrid("points")?;
+        state.walls_ = reader.readGrid("walls")?;
+        let (y, x) = (state.charcter_.y_, state.charcter_.x_);
+        if y < 0 || y >= H || x < 0 || x >= W || state.walls_[y as usize][x as usize] == 1 {
+            return Err(format!("character ({}, {}) is not on a floor", y, x));
+        }
+
+        let mut replay = Self::fromState(seed, state);
+        let action_number = reader.readNumber::<usize>("actions")?;
+        for turn in 0..action_number {
+            let words = reader.readWords()?;
+            if words.len() != 2 {
+                return Err(format!(
+                    "action of turn {} must have a name and a time",
+                    turn
+                ));
+            }
+            let action = parseAction(words[0])?;
+            let thinking_time = parseNumbers::<f64>(&words[1..])?[0];
+            if replay.lastState().isDone() || !replay.lastState().legalActions().contains(&action) {
+                return Err(format!("illegal action {} at turn {}", words[0], turn));
+            }
+            replay.push(action, thinking_time);
+        }
+        let result = reader.readNumber::<i32>("result")?;
+        if result != replay.lastState().game_score_ {
+            return Err(format!(
+                "result {} does not match replayed score {}",
+                result,
+                replay.lastState().game_score_
+            ));
+        }
+        Ok(replay)
+    }
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        std::fs::write(path, self.toRecord()).map_err(|error| error.to_string())
+    }
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|error| error.to_string())?;
+        Self::fromRecord(&text)
+    }
+}
+
+// SVGで1マスを描く大きさ(ピクセル)
+const CELL_SIZE: i32 = 48;
+const SVG_PLAYER_COLOR: &str = "#d62728";
+
+impl Replay {
+    // turnの盤面の説明。HTMLでは盤面の上に表示する
+    fn caption(&self, turn: usize) -> String {
+        let action = if turn > 0 {
+            dstr[self.actions_[turn - 1]]
+        } else {
+            "-"
+        };
+        format!(
+            "turn {}/{}&emsp;score {}&emsp;action {}",
+            turn,
+            self.lastTurn(),
+            self.states_[turn].game_score_,
+            action
+        )
+    }
+    // turnの盤面をSVGにする。得点が大きいマスほど濃く塗り、turnまでの移動の軌跡を線で描く
+    pub fn toSvg(&self, turn: usize) -> String {
+        let state = &self.states_[turn];
+        let mut ss = String::new();
+        ss += format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n",
+            W * CELL_SIZE,
+            H * CELL_SIZE
+        )
+        .as_str();
+        for y in 0..H {
+            for x in 0..W {
+                let point = state.points_[y as usize][x as usize];
+                let fill = if state.walls_[y as usize][x as usize] == 1 {
+                    "#444444".to_string()
+                } else {
+                    format!("rgba(255, 160, 0, {:.2})", point as f64 / 9.0)
+                };
+                ss += format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"#cccccc\"/>\n",
+                    x * CELL_SIZE,
+                    y * CELL_SIZE,
+                    CELL_SIZE,
+                    CELL_SIZE,
+                    fill
+                )
+                .as_str();
+                if point > 0 {
+                    ss += format!(
+                        "<text x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>\n",
+                        x * CELL_SIZE + CELL_SIZE / 2,
+                        y * CELL_SIZE + CELL_SIZE / 2,
+                        CELL_SIZE / 3,
+                        point
+                    )
+                    .as_str();
+                }
+            }
+        }
+        let path: Vec<String> = (0..=turn)
+            .map(|t| {
+                let character = &self.states_[t].charcter_;
+                format!(
+                    "{},{}",
+                    character.x_ * CELL_SIZE + CELL_SIZE / 2,
+                    character.y_ * CELL_SIZE + CELL_SIZE / 2
+                )
+            })
+            .collect();
+        ss += format!(
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"4\" stroke-opacity=\"0.7\"/>\n",
+            path.join(" "),
+            SVG_PLAYER_COLOR
+        )
+        .as_str();
+        ss += format!(
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>\n",
+            state.charcter_.x_ * CELL_SIZE + CELL_SIZE / 2,
+            state.charcter_.y_ * CELL_SIZE + CELL_SIZE / 2,
+            CELL_SIZE / 5,
+            SVG_PLAYER_COLOR
+        )
+        .as_str();
+        ss += "</svg>\n";
+        ss
+    }
+    // 全ターンのSVGと表示するターンを選ぶスライダーを1つのHTMLにまとめる
+    // 外部のスクリプトやスタイルシートを読まないので、このファイルだけで開ける
+    pub fn toHtml(&self) -> String {
+        let mut ss = String::new();
+        ss += "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n";
+        ss += format!("<title>WallMazeState seed {}</title>\n", self.seed_).as_str();
+        ss += "</head>\n<body>\n";
+        ss += format!(
+            "<input type=\"range\" id=\"turn\" min=\"0\" max=\"{}\" value=\"0\">\n",
+            self.lastTurn()
+        )
+        .as_str();
+        for turn in 0..=self.lastTurn() {
+            ss += "<div class=\"frame\">\n";
+            ss += format!("<p>{}</p>\n", self.caption(turn)).as_str();
+            ss += self.toSvg(turn).as_str();
+            ss += "</div>\n";
+        }
+        ss += r#"<script>
+const slider = document.getElementById("turn");
+function show() {
+  document.querySelectorAll(".frame").forEach((frame, turn) => {
+    frame.style.display = turn == slider.value ? "block" : "none";
+  });
+}
+slider.addEventListener("input", show);
+show();
+</script>
+</body>
+</html>
+"#;
+        ss
+    }
+    pub fn saveSvg(&self, path: &str, turn: usize) -> Result<(), String> {
+        std::fs::write(path, self.toSvg(turn)).map_err(|error| error.to_string())
+    }
+    pub fn saveHtml(&self, path: &str) -> Result<(), String> {
+        std::fs::write(path, self.toHtml()).map_err(|error| error.to_string())
+    }
+}
+
+// 記録した試合を端末で1ターンずつ表示する
+// Enterかn: 1手進む、p: 1手戻る、g <turn>: 指定したターンへ移動、a <ms>: ms間隔で最後まで自動再生、q: 終了
+struct ReplayViewer<'a> {
+    replay_: &'a Replay,
+    turn_: usize,
+}
+impl<'a> ReplayViewer<'a> {
+    pub fn new(replay: &'a Replay) -> Self {
+        Self {
+            replay_: replay,
+            turn_: 0,
+        }
+    }
+    fn draw(&self) {
+        print!("{}{}", CLEAR, self.replay_.render(self.turn_));
+        println!("[Enter/n] next  [p] prev  [g <turn>] jump  [a <ms>] auto  [q] quit");
+        io::stdout().flush().unwrap();
+    }
+    pub fn autoPlay(&mut self, interval: u64) {
+        while self.turn_ < self.replay_.lastTurn() {
+            thread::sleep(time::Duration::from_millis(interval));
+            self.turn_ += 1;
+            self.draw();
+        }
+    }
+    pub fn run(&mut self) {
+        self.draw();
+        for line in io::stdin().lock().lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+            let mut words = line.split_whitespace();
+            let command = words.next();
+            let argument = words.next().and_then(|word| word.parse::<u64>().ok());
+            match command {
+                None | Some("n") => self.turn_ = (self.turn_ + 1).min(self.replay_.lastTurn()),
+                Some("p") => self.turn_ = self.turn_.saturating_sub(1),
+                Some("g") => {
+                    if let Some(turn) = argument {
+                        self.turn_ = (turn as usize).min(self.replay_.lastTurn());
+                    }
+                }
+                Some("a") => self.autoPlay(argument.unwrap_or(500)),
+                Some("q") => break,
+                _ => {}
+            }
+            self.draw();
+        }
+    }
+}
+
+fn playGame(seed: usize) -> Replay {
+    playFrom(Replay::new(seed))
+}
+fn playFrom(mut replay: Replay) -> Replay {
+    while !replay.lastState().isDone() {
+        let start_time = time::Instant::now();
+        let action = randomAction(replay.lastState());
+        replay.push(action, start_time.elapsed().as_secs_f64() * 1000.0);
+    }
+    replay
+}
+
+// 各ノードは親ノードの番号と、親からそのノードに至った行動だけを持つ
+// ビームに残った状態はnode_id_でこの木を指し、最後に根まで辿れば行動列が復元できる
+struct ActionTree {
+    nodes_: Vec<(usize, usize)>,
+}
+impl ActionTree {
+    pub fn new() -> Self {
+        Self {
+            nodes_: vec![(0, INVALID_ACTION)],
+        }
+    }
+    pub fn push(&mut self, parent_id: usize, action: usize) -> usize {
+        self.nodes_.push((parent_id, action));
+        self.nodes_.len() - 1
+    }
+    pub fn actions(&self, node_id: usize) -> Vec<usize> {
+        let mut actions = Vec::new();
+        let mut now_id = node_id;
+        while now_id != 0 {
+            let (parent_id, action) = self.nodes_[now_id];
+            actions.push(action);
+            now_id = parent_id;
+        }
+        actions.reverse();
+        actions
+    }
+}
+
+fn beamSearchActions(state: &State, beam_width: usize, beam_depth: usize) -> Vec<usize> {
+    let mut tree = ActionTree::new();
+    let mut now_beam: BinaryHeap<State> = BinaryHeap::new();
+    let mut root_state = state.clone();
+    root_state.node_id_ = 0;
+    let mut best_state = root_state.clone();
+    now_beam.push(root_state);
+    for _ in 0..beam_depth {
+        let mut next_beam: BinaryHeap<State> = BinaryHeap::new();
+        for _ in 0..beam_width {
+            if now_beam.is_empty() {
+                break;
+            }
+            let now_state = now_beam.pop().unwrap();
+            let legal_actions = now_state.legalActions();
+            for action in legal_actions {
+                let mut next_state = now_state.clone();
+                next_state.advance(action);
+                next_state.evaluateScore();
+                next_state.node_id_ = tree.push(now_state.node_id_, action);
+                next_beam.push(next_state);
+            }
+        }
+        if next_beam.is_empty() {
+            break;
+        }
+        now_beam = next_beam;
+        best_state = now_beam.peek().unwrap().clone();
+        if best_state.isDone() {
+            break;
+        }
+    }
+    tree.actions(best_state.node_id_)
+}
+// 最初に一度だけ探索した行動列を実行する。探索にかかった時間は最初の手に記録する
+fn playPlan(mut replay: Replay, beam_width: usize) -> Replay {
+    let start_time = time::Instant::now();
+    let beam_depth = (END_TURN - replay.lastState().turn_) as usize;
+    let actions = beamSearchActions(replay.lastState(), beam_width, beam_depth);
+    let mut thinking_time = start_time.elapsed().as_secs_f64() * 1000.0;
+    for action in actions {
+        replay.push(action, thinking_time);
+        thinking_time = 0.0;
+    }
+    replay
+}
+
+// toStringの出力を読み直して、得点と障害物も含めて同じ盤面に戻るか確かめる
+fn testParse(game_number: usize) {
+    let mut failed_number = 0;
+    for seed in 0..game_number {
+        // @のマスの得点が0のまま進むか確かめるため、途中の盤面も読み直す
+        let mut state = State::new(seed);
+        for _ in 0..seed % END_TURN as usize {
+            let action = state.legalActions()[0];
+            state.advance(action);
+        }
+        match state.toString().parse::<State>() {
+            Ok(parsed)
+                if parsed.toString() == state.toString()
+                    && parsed.points_ == state.points_
+                    && parsed.walls_ == state.walls_
+                    && parsed.charcter_ == state.charcter_ => {}
+            Ok(_) => {
+                println!("seed {}: parsed board differs", seed);
+                failed_number += 1;
+            }
+            Err(message) => {
+                println!("seed {}: {}", seed, message);
+                failed_number += 1;
+            }
+        }
+    }
+    println!("parsed {} boards, {} failed", game_number, failed_number);
+}
+// シードと行動列だけから再生し直して、同じ試合に戻るか確かめる
+fn testActions(game_number: usize) {
+    let mut failed_number = 0;
+    for seed in 0..game_number {
+        let replay = playFrom(Replay::new(seed));
+        if Replay::fromActions(seed, &replay.actions_).states_ != replay.states_ {
+            println!("seed {}: replayed game differs", seed);
+            failed_number += 1;
+        }
+    }
+    println!("replayed {} games, {} failed", game_number, failed_number);
+}
+
+// 途中の盤面から始めた試合も含めて、棋譜を書いて読み直すと同じ試合に戻るか確かめる
+fn testRecord(game_number: usize) {
+    let mut failed_number = 0;
+    for seed in 0..game_number {
+        let mut state = State::new(seed);
+        for _ in 0..seed % END_TURN as usize {
+            let action = state.legalActions()[0];
+            state.advance(action);
+        }
+        let replay = playFrom(Replay::fromState(seed, state));
+        match Replay::fromRecord(&replay.toRecord()) {
+            Ok(loaded)
+                if loaded.actions_ == replay.actions_
+                    && (0..=replay.lastTurn()).all(|turn| {
+                        loaded.states_[turn].toString() == replay.states_[turn].toString()
+                    }) => {}
+            Ok(_) => {
+                println!("seed {}: loaded game differs", seed);
+                failed_number += 1;
+            }
+            Err(message) => {
+                println!("seed {}: {}", seed, message);
+                failed_number += 1;
+            }
+        }
+    }
+    println!("loaded {} records, {} failed", game_number, failed_number);
+}
+
+fn main() {
+    testParse(100);
+    testRecord(100);
+    testActions(100);
+    std::fs::create_dir_all(RECORD_DIRECTORY).unwrap();
+    // 引数に問題ファイルを渡すとその盤面で、渡さなければシード0の盤面で遊ぶ
+    let replay = match std::env::args().nth(1) {
+        Some(problem_path) => match loadProblem(&problem_path) {
+            // 問題ファイルの盤面にはシードがないので0として記録する
+            Ok(state) => playFrom(Replay::fromState(0, state)),
+            Err(message) => {
+                println!("invalid problem {}: {}", problem_path, message);
+                return;
+            }
+        },
+        None => playGame(0),
+    };
+    let path = format!("{}/WallMazeState_0.txt", RECORD_DIRECTORY);
+    replay.save(&path).unwrap();
+    replay
+        .saveHtml(&format!("{}/WallMazeState_0.html", RECORD_DIRECTORY))
+        .unwrap();
+    // 同じ盤面でビームサーチの計画も書き出し、ランダムな行動と見比べられるようにする
+    let plan = playPlan(
+        Replay::fromState(replay.seed_, replay.states_[0].clone()),
+        100,
+    );
+    plan.saveHtml(&format!(
+        "{}/WallMazeState_0_beamSearch.html",
+        RECORD_DIRECTORY
+    ))
+    .unwrap();
+    plan.saveSvg(
+        &format!("{}/WallMazeState_0_beamSearch.svg", RECORD_DIRECTORY),
+        plan.lastTurn(),
+    )
+    .unwrap();
+    match Replay::load(&path) {
+        Ok(replay) => ReplayViewer::new(&replay).run(),
+        Err(message) => println!("invalid record {}: {}", path, message),
+    }
+}